@@ -5,6 +5,7 @@ use alsa::card::Iter;
 use alsa::device_name::HintIter;
 use alsa::ctl::{Ctl, DeviceIter};
 use alsa::{Direction, Error};
+use alsa::pcm::{Capabilities, PCM};
 
 // Each card can have multiple devices and subdevices, list them all
 fn list_devices_for_card(card: &Card, direction: Direction) -> Result<(), Error>{
@@ -29,6 +30,13 @@ fn list_devices_for_card(card: &Card, direction: Direction) -> Result<(), Error>
 
         println!("card: {:<2} id: {:<10} device: {:<2} card name: '{}' PCM name: '{}'", card.get_index(), card_id, device, card_name, pcm_name);
 
+        // Open the device to find out what it supports. This fails if it is already in use.
+        let pcm = PCM::new(&format!("hw:{},{}", card.get_index(), device), direction, true);
+        if let Ok(caps) = pcm.and_then(|p| Capabilities::probe(&p)) {
+            println!("  formats: {:?}", caps.formats);
+            println!("  channels: {:?} rates: {:?}", caps.channels, caps.rates);
+        }
+
         // Loop through subdevices and get their names
         let subdevs = pcm_info.get_subdevices_count();
         for subdev in 0..subdevs {
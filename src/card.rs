@@ -1,9 +1,12 @@
-//! Sound card enumeration
-use libc::{c_int, c_char};
+//! Sound card enumeration and hotplug
+
+use libc::{c_int, c_char, pollfd};
 use super::error::*;
-use crate::alsa;
+use crate::{alsa, poll, Ctl};
+use crate::ctl_int::CardInfo;
 use core::ffi::CStr;
 use ::alloc::string::String;
+use ::alloc::vec::Vec;
 
 /// An ALSA sound card, uniquely identified by its index.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -48,6 +51,110 @@ impl Card {
     pub fn get_index(&self) -> c_int { self.0 }
 }
 
+/// A change reported by `CardMonitor`
+#[derive(Debug)]
+pub enum CardEvent {
+    /// A card appeared. The info is None if the card's control device could not be opened (yet),
+    /// e g because udev has not given the user access to it.
+    Added(Card, Option<CardInfo>),
+    /// The card with this index disappeared.
+    Removed(c_int),
+    /// A device node of a card appeared, e g "pcmC1D0p" or "midiC1D0".
+    DeviceAdded(c_int, String),
+    DeviceRemoved(c_int, String),
+    /// Events were lost because too many happened at once, or the watched directory was removed.
+    ///
+    /// Enumerate the cards again with `card::Iter` to find out what changed. If the directory was
+    /// removed (e g because the ALSA kernel modules were unloaded), no more events will come, so
+    /// a new `CardMonitor` needs to be created once it is back.
+    Rescan,
+}
+
+/// Watches `/dev/snd` for cards and devices being added or removed, e g USB sound cards being plugged in.
+///
+/// Uses inotify, so there is no dependency on udev. Poll it using the `poll::Descriptors` trait,
+/// then call `read` to get the events.
+#[derive(Debug)]
+pub struct CardMonitor(c_int);
+
+impl Drop for CardMonitor {
+    fn drop(&mut self) { unsafe { libc::close(self.0) }; }
+}
+
+/// Returns the card index of a device node name such as "controlC1" or "pcmC1D0p".
+fn node_card(name: &str) -> Option<c_int> {
+    let i = name.find('C')?;
+    if i == 0 || !name[..i].bytes().all(|b| b.is_ascii_lowercase()) { return None }
+    let digits = name[i+1..].bytes().take_while(|b| b.is_ascii_digit()).count();
+    name[i+1..i+1+digits].parse().ok()
+}
+
+impl CardMonitor {
+    /// Starts watching `/dev/snd`, which exists as soon as the ALSA kernel modules are loaded.
+    pub fn new() -> Result<CardMonitor> { CardMonitor::with_dir(c"/dev/snd") }
+
+    /// Starts watching another directory for ALSA device nodes.
+    pub fn with_dir(dir: &CStr) -> Result<CardMonitor> {
+        let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if fd < 0 { return Err(Error::last("inotify_init1")) }
+        let m = CardMonitor(fd);
+        let mask = libc::IN_CREATE | libc::IN_DELETE | libc::IN_MOVED_TO | libc::IN_MOVED_FROM;
+        if unsafe { libc::inotify_add_watch(fd, dir.as_ptr(), mask) } < 0 { return Err(Error::last("inotify_add_watch")) }
+        Ok(m)
+    }
+
+    /// Returns the events that happened since the last call, or an empty vector if there are none.
+    pub fn read(&mut self) -> Result<Vec<CardEvent>> {
+        // inotify_event is aligned to 4 bytes
+        let mut buf = [0u32; 1024];
+        let mut r = Vec::new();
+        loop {
+            let n = unsafe { libc::read(self.0, buf.as_mut_ptr() as *mut _, core::mem::size_of_val(&buf)) };
+            if n < 0 {
+                let e = Error::last("read");
+                if e.errno() == libc::EAGAIN { return Ok(r) }
+                return Err(e);
+            }
+            let bytes = unsafe { core::slice::from_raw_parts(buf.as_ptr() as *const u8, n as usize) };
+            let mut pos = 0;
+            while pos + core::mem::size_of::<libc::inotify_event>() <= bytes.len() {
+                let ev = unsafe { &*(bytes.as_ptr().add(pos) as *const libc::inotify_event) };
+                let name_start = pos + core::mem::size_of::<libc::inotify_event>();
+                pos = name_start + ev.len as usize;
+                let name = bytes.get(name_start..pos).unwrap_or(&[]);
+                let name = name.split(|&b| b == 0).next().and_then(|x| core::str::from_utf8(x).ok()).unwrap_or("");
+                if ev.mask & (libc::IN_Q_OVERFLOW | libc::IN_IGNORED) != 0 {
+                    r.push(CardEvent::Rescan);
+                    continue;
+                }
+                let Some(index) = node_card(name) else { continue };
+                let added = ev.mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0;
+                r.push(match (name.starts_with("controlC"), added) {
+                    (true, true) => {
+                        let card = Card(index);
+                        CardEvent::Added(card, Ctl::from_card(&card, false).and_then(|c| c.card_info()).ok())
+                    }
+                    (true, false) => CardEvent::Removed(index),
+                    (false, true) => CardEvent::DeviceAdded(index, name.into()),
+                    (false, false) => CardEvent::DeviceRemoved(index, name.into()),
+                });
+            }
+        }
+    }
+}
+
+impl poll::Descriptors for CardMonitor {
+    fn count(&self) -> usize { 1 }
+    fn fill(&self, p: &mut [pollfd]) -> Result<usize> {
+        if p.is_empty() { return Err(Error::new("CardMonitor::fill", libc::EINVAL)) }
+        p[0] = pollfd { fd: self.0, events: libc::POLLIN, revents: 0 };
+        Ok(1)
+    }
+    fn revents(&self, p: &[pollfd]) -> Result<poll::Flags> {
+        Ok(poll::Flags::from_bits_truncate(p.first().map(|p| p.revents).unwrap_or(0)))
+    }
+}
+
 #[test]
 fn print_cards() {
     extern crate std;
@@ -55,3 +162,29 @@ fn print_cards() {
         std::println!("Card #{}: {} ({})", a.get_index(), a.get_name().unwrap(), a.get_longname().unwrap())
     }
 }
+
+#[test]
+fn card_monitor() {
+    extern crate std;
+    use std::fs;
+    use ::alloc::ffi::CString;
+    let dir = std::env::temp_dir().join(std::format!("alsa-rs-card-monitor-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let cdir = CString::new(dir.to_str().unwrap()).unwrap();
+    let mut m = CardMonitor::with_dir(&cdir).unwrap();
+    assert!(m.read().unwrap().is_empty());
+
+    for name in ["controlC99", "pcmC99D0p", "timer"] { fs::write(dir.join(name), b"").unwrap(); }
+    fs::remove_file(dir.join("controlC99")).unwrap();
+    assert_eq!(poll::poll(&mut poll::Descriptors::get(&m).unwrap(), 0).unwrap(), 1);
+    let ev = m.read().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(m.read().unwrap().last(), Some(CardEvent::Rescan)));
+
+    assert_eq!(ev.len(), 3);
+    assert!(matches!(ev[0], CardEvent::Added(Card(99), None)));
+    assert!(matches!(&ev[1], CardEvent::DeviceAdded(99, s) if s == "pcmC99D0p"));
+    assert!(matches!(ev[2], CardEvent::Removed(99)));
+    assert_eq!(node_card("hwC0D12"), Some(0));
+    assert_eq!(node_card("seq"), None);
+}
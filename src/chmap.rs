@@ -1,5 +1,5 @@
 use crate::alsa;
-use core::{fmt, mem, slice};
+use core::{fmt, mem, ptr, slice};
 use super::error::*;
 use ::alloc::vec::Vec;
 use ::alloc::vec;
@@ -95,7 +95,7 @@ impl fmt::Display for Chmap {
 
 impl<'a> From<&'a [ChmapPosition]> for Chmap {
     fn from(a: &'a [ChmapPosition]) -> Chmap {
-        let p = unsafe { libc::malloc((mem::size_of::<alsa::snd_pcm_chmap_t>() + mem::size_of::<libc::c_uint>() * a.len()) as libc::size_t) };
+        let p = unsafe { libc::malloc(chmap_size(a.len()) as libc::size_t) };
         if p.is_null() { panic!("Out of memory") }
         let mut r = Chmap(p as *mut alsa::snd_pcm_chmap_t, true);
         r.set_channels(a.len() as libc::c_uint);
@@ -112,6 +112,44 @@ impl<'a> From<&'a Chmap> for Vec<ChmapPosition> {
 
 pub fn chmap_new(a: *mut alsa::snd_pcm_chmap_t) -> Chmap { Chmap(a, true) }
 pub fn chmap_handle(a: &Chmap) -> *mut alsa::snd_pcm_chmap_t { a.0 }
+pub fn chmap_ref(a: *mut alsa::snd_pcm_chmap_t) -> Chmap { Chmap(a, false) }
+
+fn chmap_size(channels: usize) -> usize { mem::size_of::<alsa::snd_pcm_chmap_t>() + mem::size_of::<libc::c_uint>() * channels }
+
+/// Hands a malloc'ed chmap over to alsa-lib, which frees it with free().
+pub fn chmap_into_raw(a: Chmap) -> *mut alsa::snd_pcm_chmap_t {
+    if a.1 {
+        let p = a.0;
+        mem::forget(a);
+        return p;
+    }
+    let size = chmap_size(a.as_slice().len());
+    let p = unsafe { libc::malloc(size as libc::size_t) };
+    if p.is_null() { panic!("Out of memory") }
+    unsafe { ptr::copy_nonoverlapping(a.0 as *const u8, p as *mut u8, size) };
+    p as *mut alsa::snd_pcm_chmap_t
+}
+
+/// Builds a NULL terminated, malloc'ed array of chmap queries, to be freed with snd_pcm_free_chmaps.
+pub fn chmaps_query_into_raw(v: &[(ChmapType, Chmap)]) -> *mut *mut alsa::snd_pcm_chmap_query_t {
+    if v.is_empty() { return ptr::null_mut() }
+    let arr = unsafe { libc::calloc(v.len() + 1, mem::size_of::<*mut alsa::snd_pcm_chmap_query_t>()) }
+        as *mut *mut alsa::snd_pcm_chmap_query_t;
+    if arr.is_null() { panic!("Out of memory") }
+    for (i, (t, m)) in v.iter().enumerate() {
+        let pos = m.as_slice();
+        let size = mem::size_of::<alsa::snd_pcm_chmap_query_t>() + mem::size_of::<libc::c_uint>() * pos.len();
+        let q = unsafe { libc::malloc(size as libc::size_t) } as *mut alsa::snd_pcm_chmap_query_t;
+        if q.is_null() { panic!("Out of memory") }
+        unsafe {
+            (*q).type_ = *t as libc::c_uint;
+            (*q).map.channels = pos.len() as libc::c_uint;
+            ptr::copy_nonoverlapping(pos.as_ptr(), (*q).map.pos.as_mut_ptr(), pos.len());
+            *arr.add(i) = q;
+        }
+    }
+    arr
+}
 
 
 /// Iterator over available channel maps - see [snd_pcm_chmap_query_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___p_c_m.html)
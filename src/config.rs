@@ -1,12 +1,31 @@
 //! Configuration file API
 //!
-//! For now, just contains functions regarding the global configuration
-//! stored as a cache inside alsa-lib. Calling `update_free_global` might help
-//! against valgrind reporting memory leaks.
+//! Contains functions regarding the global configuration stored as a cache
+//! inside alsa-lib (calling `update_free_global` might help against valgrind
+//! reporting memory leaks), as well as a wrapper around configuration trees.
+//!
+//! A `Config` owns a configuration tree (or subtree). It dereferences to a `Node`,
+//! and nodes inside the tree are handed out as `&Node` (for reading) or
+//! `&mut Node` (for modification), so the borrow checker makes sure no node is
+//! accessed after the tree is freed.
+//!
+//! # Example
+//!
+//! ```
+//! let c = alsa::config::Config::load_string("a { b 1 c \"hello\" }").unwrap();
+//! assert_eq!(c.search("a.b").unwrap().get_integer().unwrap(), 1);
+//! for n in c.search("a").unwrap().iter() {
+//!     println!("{}: {:?}", n.get_id().unwrap(), n.get_type().unwrap());
+//! }
+//! ```
 use crate::{alsa};
 use super::error::*;
 use super::Output;
-use core::ptr;
+use libc::{c_int, c_long, c_void, c_char};
+use ::alloc::ffi::CString;
+use ::alloc::string::String;
+use core::{ptr, fmt};
+use core::marker::PhantomData;
 
 pub fn update() -> Result<bool> {
 	acheck!(snd_config_update()).map(|x| x != 0)
@@ -16,7 +35,21 @@ pub fn update_free_global() -> Result<()> {
 	acheck!(snd_config_update_free_global()).map(|_| ())
 }
 
+alsa_enum!(
+    /// [SND_CONFIG_TYPE_xxx](https://alsa-project.org/alsa-doc/alsa-lib/group___config.html) constants
+    Type, ALL_TYPES[6],
+
+    Integer = SND_CONFIG_TYPE_INTEGER,
+    Integer64 = SND_CONFIG_TYPE_INTEGER64,
+    Real = SND_CONFIG_TYPE_REAL,
+    String = SND_CONFIG_TYPE_STRING,
+    Pointer = SND_CONFIG_TYPE_POINTER,
+    Compound = SND_CONFIG_TYPE_COMPOUND,
+);
+
 /// [snd_config_t](https://alsa-project.org/alsa-doc/alsa-lib/group___config.html) wrapper
+///
+/// Owns a configuration tree, which is freed when dropped. Dereferences to its top `Node`.
 #[derive(Debug)]
 pub struct Config(*mut alsa::snd_config_t);
 
@@ -24,17 +57,297 @@ impl Drop for Config {
     fn drop(&mut self) { unsafe { alsa::snd_config_unref(self.0) }; }
 }
 
-pub fn update_ref() -> Result<Config> {
-	let mut top = ptr::null_mut();
-	acheck!(snd_config_update_ref(&mut top)).map(|_| Config(top))
+/// A reference to the global configuration, as returned by `update_ref`
+///
+/// The tree is shared with the rest of alsa-lib, so it only dereferences to `&Node`.
+/// Use `copy` to get a tree that can be modified.
+#[derive(Debug)]
+pub struct GlobalConfig(*mut alsa::snd_config_t);
+
+impl Drop for GlobalConfig {
+    fn drop(&mut self) { unsafe { alsa::snd_config_unref(self.0) }; }
+}
+
+impl core::ops::Deref for GlobalConfig {
+    type Target = Node;
+    fn deref(&self) -> &Node { unsafe { Node::from_ptr(self.0) } }
+}
+
+/// Updates the global configuration if needed, and returns a reference to it.
+pub fn update_ref() -> Result<GlobalConfig> {
+    let mut top = ptr::null_mut();
+    acheck!(snd_config_update_ref(&mut top)).map(|_| GlobalConfig(top))
 }
 
 impl Config {
+    /// Creates a new, empty top level compound node.
+    pub fn top() -> Result<Config> {
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_top(&mut p)).map(|_| Config(p))
+    }
+
+    /// Parses configuration file syntax into a new top level node.
+    pub fn load_string(s: &str) -> Result<Config> {
+        // A size of zero would make alsa-lib call strlen on the (unterminated) string
+        if s.is_empty() { return Self::top() }
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_load_string(&mut p, s.as_ptr() as *const c_char, s.len())).map(|_| Config(p))
+    }
+
+    pub fn make_compound(id: &str, join: bool) -> Result<Config> {
+        let id = CString::new(id).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_make_compound(&mut p, id.as_ptr(), join as c_int)).map(|_| Config(p))
+    }
+
+    pub fn make_integer(id: &str, v: i64) -> Result<Config> {
+        let id = CString::new(id).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_imake_integer(&mut p, id.as_ptr(), v as c_long)).map(|_| Config(p))
+    }
+
+    pub fn make_integer64(id: &str, v: i64) -> Result<Config> {
+        let id = CString::new(id).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_imake_integer64(&mut p, id.as_ptr(), v)).map(|_| Config(p))
+    }
+
+    pub fn make_real(id: &str, v: f64) -> Result<Config> {
+        let id = CString::new(id).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_imake_real(&mut p, id.as_ptr(), v)).map(|_| Config(p))
+    }
+
+    pub fn make_string(id: &str, v: &str) -> Result<Config> {
+        let id = CString::new(id).unwrap();
+        let v = CString::new(v).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_imake_string(&mut p, id.as_ptr(), v.as_ptr())).map(|_| Config(p))
+    }
+
+    /// The pointer is stored as-is; alsa-lib does not take ownership of what it points to.
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub fn make_pointer(id: &str, v: *const c_void) -> Result<Config> {
+        let id = CString::new(id).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_imake_pointer(&mut p, id.as_ptr(), v)).map(|_| Config(p))
+    }
+}
+
+pub(crate) fn config_ptr(c: &Config) -> *mut alsa::snd_config_t { c.0 }
+
+impl core::ops::Deref for Config {
+    type Target = Node;
+    fn deref(&self) -> &Node { unsafe { Node::from_ptr(self.0) } }
+}
+
+impl core::ops::DerefMut for Config {
+    fn deref_mut(&mut self) -> &mut Node { unsafe { Node::from_ptr_mut(self.0) } }
+}
+
+/// A node inside a configuration tree
+///
+/// This type is only ever used behind a reference, borrowed from the `Config` owning the tree.
+#[repr(transparent)]
+pub struct Node(alsa::snd_config_t);
+
+impl Node {
+    pub(crate) unsafe fn from_ptr<'a>(p: *mut alsa::snd_config_t) -> &'a Node { &*(p as *const Node) }
+    unsafe fn from_ptr_mut<'a>(p: *mut alsa::snd_config_t) -> &'a mut Node { &mut *(p as *mut Node) }
+
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *mut alsa::snd_config_t { self as *const Node as *mut alsa::snd_config_t }
+
+    pub fn get_id(&self) -> Result<&str> {
+        let mut s = ptr::null();
+        acheck!(snd_config_get_id(self.as_ptr(), &mut s))?;
+        from_const("snd_config_get_id", s)
+    }
+
+    pub fn set_id(&mut self, id: &str) -> Result<()> {
+        let id = CString::new(id).unwrap();
+        acheck!(snd_config_set_id(self.as_ptr(), id.as_ptr())).map(|_| ())
+    }
+
+    pub fn get_type(&self) -> Result<Type> {
+        Type::from_c_int(unsafe { alsa::snd_config_get_type(self.as_ptr()) } as c_int, "snd_config_get_type")
+    }
+
+    /// Returns true if this is a compound node where all ids are consecutive numbers, starting from zero.
+    pub fn is_array(&self) -> Result<bool> { acheck!(snd_config_is_array(self.as_ptr())).map(|x| x != 0) }
+
+    pub fn get_integer(&self) -> Result<i64> {
+        let mut v = 0;
+        acheck!(snd_config_get_integer(self.as_ptr(), &mut v)).map(|_| v as i64)
+    }
+
+    pub fn get_integer64(&self) -> Result<i64> {
+        let mut v = 0;
+        acheck!(snd_config_get_integer64(self.as_ptr(), &mut v)).map(|_| v)
+    }
+
+    pub fn get_real(&self) -> Result<f64> {
+        let mut v = 0.0;
+        acheck!(snd_config_get_real(self.as_ptr(), &mut v)).map(|_| v)
+    }
+
+    pub fn get_string(&self) -> Result<&str> {
+        let mut s = ptr::null();
+        acheck!(snd_config_get_string(self.as_ptr(), &mut s))?;
+        from_const("snd_config_get_string", s)
+    }
+
+    pub fn get_pointer(&self) -> Result<*const c_void> {
+        let mut v = ptr::null();
+        acheck!(snd_config_get_pointer(self.as_ptr(), &mut v)).map(|_| v)
+    }
+
+    /// Returns the value of any non-compound node, converted to a string.
+    pub fn get_ascii(&self) -> Result<String> {
+        let mut s = ptr::null_mut();
+        acheck!(snd_config_get_ascii(self.as_ptr(), &mut s))?;
+        from_alloc("snd_config_get_ascii", s)
+    }
+
+    /// Interprets the value as a boolean, e g "yes", "on", "true" or 1.
+    pub fn get_bool(&self) -> Result<bool> { acheck!(snd_config_get_bool(self.as_ptr())).map(|x| x != 0) }
+
+    pub fn set_integer(&mut self, v: i64) -> Result<()> {
+        acheck!(snd_config_set_integer(self.as_ptr(), v as c_long)).map(|_| ())
+    }
+
+    pub fn set_integer64(&mut self, v: i64) -> Result<()> {
+        acheck!(snd_config_set_integer64(self.as_ptr(), v)).map(|_| ())
+    }
+
+    pub fn set_real(&mut self, v: f64) -> Result<()> {
+        acheck!(snd_config_set_real(self.as_ptr(), v)).map(|_| ())
+    }
+
+    pub fn set_string(&mut self, v: &str) -> Result<()> {
+        let v = CString::new(v).unwrap();
+        acheck!(snd_config_set_string(self.as_ptr(), v.as_ptr())).map(|_| ())
+    }
+
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub fn set_pointer(&mut self, v: *const c_void) -> Result<()> {
+        acheck!(snd_config_set_pointer(self.as_ptr(), v)).map(|_| ())
+    }
+
+    /// Parses the string according to the type of the node, and sets the value.
+    pub fn set_ascii(&mut self, v: &str) -> Result<()> {
+        let v = CString::new(v).unwrap();
+        acheck!(snd_config_set_ascii(self.as_ptr(), v.as_ptr())).map(|_| ())
+    }
+
+    /// Searches for a node, the key might contain dots to search in subnodes, e g "pcm.default".
+    pub fn search(&self, key: &str) -> Result<&Node> {
+        let key = CString::new(key).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_search(self.as_ptr(), key.as_ptr(), &mut p)).map(|_| unsafe { Node::from_ptr(p) })
+    }
+
+    pub fn search_mut(&mut self, key: &str) -> Result<&mut Node> {
+        let key = CString::new(key).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_search(self.as_ptr(), key.as_ptr(), &mut p)).map(|_| unsafe { Node::from_ptr_mut(p) })
+    }
+
+    /// Searches for a definition, e g `search_definition("pcm", "hw:0,0")`, with
+    /// aliases followed and arguments expanded. The result is a copy.
+    pub fn search_definition(&self, base: &str, name: &str) -> Result<Config> {
+        let base = CString::new(base).unwrap();
+        let name = CString::new(name).unwrap();
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_search_definition(self.as_ptr(), base.as_ptr(), name.as_ptr(), &mut p)).map(|_| Config(p))
+    }
+
+    /// Expands this node, substituting arguments and evaluating functions.
+    ///
+    /// `root` is the tree used to look up references, typically the global configuration.
+    pub fn expand(&self, root: &Node, args: Option<&str>) -> Result<Config> {
+        let args = args.map(|a| CString::new(a).unwrap());
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_expand(self.as_ptr(), root.as_ptr(), args.as_ref().map(|a| a.as_ptr()).unwrap_or(ptr::null()),
+            ptr::null_mut(), &mut p)).map(|_| Config(p))
+    }
+
+    /// Makes a deep copy of this node and all its children.
+    pub fn copy(&self) -> Result<Config> {
+        let mut p = ptr::null_mut();
+        acheck!(snd_config_copy(&mut p, self.as_ptr())).map(|_| Config(p))
+    }
+
+    /// Iterates over the children of a compound node. Yields nothing for other node types.
+    pub fn iter(&self) -> Iter<'_> {
+        if self.get_type() != Ok(Type::Compound) { return Iter(ptr::null_mut(), ptr::null_mut(), PhantomData) }
+        unsafe { Iter(alsa::snd_config_iterator_first(self.as_ptr()), alsa::snd_config_iterator_end(self.as_ptr()), PhantomData) }
+    }
+
+    /// Adds a child to a compound node. The child is consumed.
+    pub fn add(&mut self, child: Config) -> Result<()> {
+        acheck!(snd_config_add(self.as_ptr(), child.0))?;
+        core::mem::forget(child);
+        Ok(())
+    }
+
+    /// Detaches a child (searched for by key) from this node, and returns it.
+    pub fn remove(&mut self, key: &str) -> Result<Config> {
+        let p = self.search_mut(key)?.as_ptr();
+        acheck!(snd_config_remove(p)).map(|_| Config(p))
+    }
+
+    /// Removes and frees a child (searched for by key) from this node.
+    pub fn delete(&mut self, key: &str) -> Result<()> {
+        let p = self.search_mut(key)?.as_ptr();
+        acheck!(snd_config_delete(p)).map(|_| ())
+    }
+
+    /// Parses configuration file syntax and merges it into this (compound) node.
+    pub fn load(&mut self, data: &[u8]) -> Result<()> {
+        let mut i = ptr::null_mut();
+        acheck!(snd_input_buffer_open(&mut i, data.as_ptr() as *const c_char, data.len() as isize))?;
+        let r = acheck!(snd_config_load(self.as_ptr(), i));
+        unsafe { alsa::snd_input_close(i) };
+        r.map(|_| ())
+    }
+
+    /// Like `load`, but reads all data from a reader first.
+    #[cfg(feature = "std")]
+    pub fn load_reader<R: std::io::Read>(&mut self, mut r: R) -> Result<()> {
+        let mut data = ::alloc::vec::Vec::new();
+        r.read_to_end(&mut data).map_err(|e| Error::new("read_to_end", e.raw_os_error().unwrap_or(libc::EIO)))?;
+        self.load(&data)
+    }
+
     pub fn save(&self, o: &mut Output) -> Result<()> {
-        acheck!(snd_config_save(self.0, super::io::output_handle(o))).map(|_| ())
+        acheck!(snd_config_save(self.as_ptr(), super::io::output_handle(o))).map(|_| ())
+    }
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Node({:?}, {:?})", self.get_id(), self.get_type())
     }
 }
 
+/// Iterator over the children of a compound `Node`
+pub struct Iter<'a>(alsa::snd_config_iterator_t, alsa::snd_config_iterator_t, PhantomData<&'a Node>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Node;
+    fn next(&mut self) -> Option<&'a Node> {
+        if self.0 == self.1 { return None }
+        let n = unsafe { Node::from_ptr(alsa::snd_config_iterator_entry(self.0)) };
+        self.0 = unsafe { alsa::snd_config_iterator_next(self.0) };
+        Some(n)
+    }
+}
+
+impl<'a> fmt::Debug for Iter<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "Iter({:?})", self.0) }
+}
+
 #[test]
 fn config_save() {
     extern crate std;
@@ -43,3 +356,31 @@ fn config_save() {
 	c.save(&mut outp).unwrap();
     std::println!("== Config save ==\n{}", outp);
 }
+
+#[test]
+fn config_tree() {
+    use ::alloc::vec::Vec;
+    let mut c = Config::load_string("a { b 1 c 2.5 d \"hello\" e [ x y ] }").unwrap();
+    let a = c.search("a").unwrap();
+    assert_eq!(a.get_type().unwrap(), Type::Compound);
+    assert_eq!(a.iter().map(|n| n.get_id().unwrap()).collect::<Vec<_>>(), &["b", "c", "d", "e"]);
+    assert_eq!(c.search("a.b").unwrap().get_integer().unwrap(), 1);
+    assert_eq!(c.search("a.c").unwrap().get_real().unwrap(), 2.5);
+    assert_eq!(c.search("a.d").unwrap().get_string().unwrap(), "hello");
+    assert!(c.search("a.e").unwrap().is_array().unwrap());
+    assert!(c.search("a.d").unwrap().get_integer().is_err());
+    assert!(c.search("a.f").is_err());
+
+    c.search_mut("a.b").unwrap().set_integer(5).unwrap();
+    c.search_mut("a").unwrap().add(Config::make_integer64("f", 1 << 40).unwrap()).unwrap();
+    assert_eq!(c.search("a.b").unwrap().get_ascii().unwrap(), "5");
+    assert_eq!(c.search("a.f").unwrap().get_integer64().unwrap(), 1 << 40);
+
+    let d = c.search_mut("a").unwrap().remove("d").unwrap();
+    assert_eq!(d.get_string().unwrap(), "hello");
+    c.delete("a.e").unwrap();
+    let a2 = c.search("a").unwrap().copy().unwrap();
+    assert_eq!(a2.iter().count(), 3);
+    c.load(b"g 7").unwrap();
+    assert_eq!(c.search("g").unwrap().get_integer().unwrap(), 7);
+}
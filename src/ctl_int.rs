@@ -3,14 +3,18 @@ use crate::alsa;
 use super::pcm::Info;
 use core::ffi::CStr;
 use ::alloc::ffi::CString;
+use ::alloc::string::{String, ToString};
+use ::alloc::vec::Vec;
 use super::Direction;
 use super::error::*;
 use super::mixer::MilliBel;
 use super::Round;
+use super::iec958::Iec958Status;
 use core::{ptr, mem, fmt, cmp};
-use crate::{Card, poll};
+use crate::{Card, poll, config};
+use crate::config::Config;
 use core::cell::UnsafeCell;
-use libc::{c_uint, c_void, size_t, c_long, c_int, pollfd, c_short};
+use libc::{c_char, c_uint, c_void, size_t, c_long, c_int, pollfd, c_short};
 
 /// We prefer not to allocate for every ElemId, ElemInfo or ElemValue.
 /// But we don't know if these will increase in the future or on other platforms.
@@ -62,6 +66,13 @@ impl Ctl {
         acheck!(snd_ctl_open(&mut r, c.as_ptr(), flags)).map(|_| Ctl(r))
     }
 
+    /// Like open, but looks up the name in `lconf` instead of the global configuration.
+    pub fn open_lconf(c: &CStr, nonblock: bool, lconf: &Config) -> Result<Ctl> {
+        let mut r = ptr::null_mut();
+        let flags = if nonblock { 1 } else { 0 }; // FIXME: alsa::SND_CTL_NONBLOCK does not exist in alsa-sys
+        acheck!(snd_ctl_open_lconf(&mut r, c.as_ptr(), flags, config::config_ptr(lconf))).map(|_| Ctl(r))
+    }
+
     pub fn from_card(c: &Card, nonblock: bool) -> Result<Ctl> {
         let s = ::alloc::format!("hw:{}", c.get_index());
         Ctl::open(&CString::new(s).unwrap(), nonblock)
@@ -100,6 +111,83 @@ impl Ctl {
         acheck!(snd_ctl_elem_write(self.0, elem_value_ptr(val))).map(|_| ())
     }
 
+    pub fn elem_info(&self, id: &ElemId) -> Result<ElemInfo> {
+        let info = elem_info_new()?;
+        unsafe { alsa::snd_ctl_elem_info_set_id(info.0, elem_id_ptr(id)) };
+        acheck!(snd_ctl_elem_info(self.0, info.0)).map(|_| info)
+    }
+
+    /// Returns the item names of an element of type `ElemType::Enumerated`.
+    pub fn elem_item_names(&self, id: &ElemId) -> Result<Vec<String>> {
+        let mut info = self.elem_info(id)?;
+        elem_info_item_names(&mut info, |i| acheck!(snd_ctl_elem_info(self.0, i.0)).map(|_| ()))
+    }
+
+    fn new_elem_info(id: &ElemId) -> Result<ElemInfo> {
+        let info = elem_info_new()?;
+        unsafe { alsa::snd_ctl_elem_info_set_id(info.0, elem_id_ptr(id)) };
+        Ok(info)
+    }
+
+    /// Adds a user element with `count` values, in the range `min..=max`.
+    pub fn elem_add_integer(&self, id: &ElemId, count: u32, min: i32, max: i32, step: i32) -> Result<()> {
+        let info = Self::new_elem_info(id)?;
+        acheck!(snd_ctl_add_integer_elem_set(self.0, info.0, 1, count as c_uint, min as c_long, max as c_long, step as c_long)).map(|_| ())
+    }
+
+    pub fn elem_add_integer64(&self, id: &ElemId, count: u32, min: i64, max: i64, step: i64) -> Result<()> {
+        let info = Self::new_elem_info(id)?;
+        acheck!(snd_ctl_add_integer64_elem_set(self.0, info.0, 1, count as c_uint, min, max, step)).map(|_| ())
+    }
+
+    pub fn elem_add_boolean(&self, id: &ElemId, count: u32) -> Result<()> {
+        let info = Self::new_elem_info(id)?;
+        acheck!(snd_ctl_add_boolean_elem_set(self.0, info.0, 1, count as c_uint)).map(|_| ())
+    }
+
+    pub fn elem_add_enumerated(&self, id: &ElemId, count: u32, items: &[&str]) -> Result<()> {
+        let info = Self::new_elem_info(id)?;
+        let names: Vec<CString> = items.iter().map(|s| CString::new(*s).unwrap()).collect();
+        let ptrs: Vec<*const c_char> = names.iter().map(|s| s.as_ptr()).collect();
+        acheck!(snd_ctl_add_enumerated_elem_set(self.0, info.0, 1, count as c_uint, ptrs.len() as c_uint, ptrs.as_ptr())).map(|_| ())
+    }
+
+    pub fn elem_add_bytes(&self, id: &ElemId, count: u32) -> Result<()> {
+        let info = Self::new_elem_info(id)?;
+        acheck!(snd_ctl_add_bytes_elem_set(self.0, info.0, 1, count as c_uint)).map(|_| ())
+    }
+
+    /// Adds a user element holding one IEC958 (S/PDIF) status.
+    ///
+    /// There is no elem_set function for this type, so this wraps `snd_ctl_elem_add_iec958`.
+    pub fn elem_add_iec958(&self, id: &ElemId) -> Result<()> {
+        acheck!(snd_ctl_elem_add_iec958(self.0, elem_id_ptr(id))).map(|_| ())
+    }
+
+    /// Removes the user element `id`, and adds a new one with the same id.
+    ///
+    /// alsa-lib does not export `snd_ctl_elem_replace`, so unlike the kernel's replace ioctl,
+    /// this is not atomic: other applications can see the element disappear and reappear.
+    /// `count` and `spec` are checked before anything is removed, but if adding the new element
+    /// fails anyway (e g because the card is out of memory for user elements), the old element is lost.
+    pub fn elem_replace(&self, id: &ElemId, count: u32, spec: ElemSpec) -> Result<()> {
+        spec.validate(count)?;
+        self.elem_remove(id)?;
+        match spec {
+            ElemSpec::Boolean => self.elem_add_boolean(id, count),
+            ElemSpec::Integer { min, max, step } => self.elem_add_integer(id, count, min, max, step),
+            ElemSpec::Integer64 { min, max, step } => self.elem_add_integer64(id, count, min, max, step),
+            ElemSpec::Enumerated(items) => self.elem_add_enumerated(id, count, items),
+            ElemSpec::Bytes => self.elem_add_bytes(id, count),
+            ElemSpec::IEC958 => self.elem_add_iec958(id),
+        }
+    }
+
+    /// Removes a user element.
+    pub fn elem_remove(&self, id: &ElemId) -> Result<()> {
+        acheck!(snd_ctl_elem_remove(self.0, elem_id_ptr(id))).map(|_| ())
+    }
+
     pub fn elem_lock(&self, id: &ElemId) -> Result<i32> {
         acheck!(snd_ctl_elem_lock(self.0, elem_id_ptr(id)))
     }
@@ -108,6 +196,26 @@ impl Ctl {
         acheck!(snd_ctl_elem_unlock(self.0, elem_id_ptr(id)))
     }
 
+    /// Reads the TLV data of an element, e g its dB scale. Parse it with `tlv::Tlv::parse`.
+    pub fn elem_tlv_read(&self, id: &ElemId) -> Result<Vec<u32>> {
+        let mut buf = ::alloc::vec![0 as c_uint; TLV_MAX_WORDS];
+        acheck!(snd_ctl_elem_tlv_read(self.0, elem_id_ptr(id), buf.as_mut_ptr(), (buf.len() * 4) as c_uint))?;
+        buf.truncate(tlv_len(&buf, "snd_ctl_elem_tlv_read")?);
+        Ok(buf)
+    }
+
+    /// Writes TLV data to an element. `tlv` is type, length in bytes, and data, as returned from `tlv::Tlv::to_raw`.
+    pub fn elem_tlv_write(&self, id: &ElemId, tlv: &[u32]) -> Result<bool> {
+        tlv_len(tlv, "snd_ctl_elem_tlv_write")?;
+        acheck!(snd_ctl_elem_tlv_write(self.0, elem_id_ptr(id), tlv.as_ptr())).map(|r| r > 0)
+    }
+
+    /// Sends a TLV command to an element. `tlv` is type, length in bytes, and data.
+    pub fn elem_tlv_command(&self, id: &ElemId, tlv: &[u32]) -> Result<()> {
+        tlv_len(tlv, "snd_ctl_elem_tlv_command")?;
+        acheck!(snd_ctl_elem_tlv_command(self.0, elem_id_ptr(id), tlv.as_ptr())).map(|_| ())
+    }
+
     pub fn elem_list(&self) -> Result<ElemList> {
         // obtain the list of all the elements now that we know how many there are
         let list = elem_list_new(|list| {
@@ -162,6 +270,18 @@ impl poll::Descriptors for Ctl {
 
 pub fn ctl_ptr(a: &Ctl) -> *mut alsa::snd_ctl_t { a.0 }
 
+pub fn ctl_from_ptr(p: *mut alsa::snd_ctl_t) -> Ctl { Ctl(p) }
+
+/// Size of the buffer for reading TLV data, in words
+pub const TLV_MAX_WORDS: usize = 1024;
+
+/// Checks that `tlv` holds at least its type, length and data, and returns its size in words.
+pub fn tlv_len(tlv: &[u32], func: &'static str) -> Result<usize> {
+    if tlv.len() < 2 { return Err(Error::new(func, libc::EINVAL)) }
+    let len = 2 + (tlv[1] as usize).div_ceil(4);
+    if len > tlv.len() { Err(Error::new(func, libc::EINVAL)) } else { Ok(len) }
+}
+
 /// [snd_ctl_card_info_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___control.html) wrapper
 #[derive(Debug)]
 pub struct CardInfo(*mut alsa::snd_ctl_card_info_t);
@@ -217,6 +337,35 @@ alsa_enum!(
     Integer64 = SND_CTL_ELEM_TYPE_INTEGER64,
 );
 
+/// Type and range of a new user element, for `Ctl::elem_replace`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ElemSpec<'a> {
+    Boolean,
+    Integer { min: i32, max: i32, step: i32 },
+    Integer64 { min: i64, max: i64, step: i64 },
+    /// The names of the items
+    Enumerated(&'a [&'a str]),
+    Bytes,
+    IEC958,
+}
+
+impl ElemSpec<'_> {
+    /// Does the same checks as the kernel does when adding an element.
+    fn validate(&self, count: u32) -> Result<()> {
+        let (maxcount, ok) = match *self {
+            ElemSpec::Boolean => (128, true),
+            ElemSpec::Integer { min, max, step } => (128, min <= max && step >= 0),
+            ElemSpec::Integer64 { min, max, step } => (64, min <= max && step >= 0),
+            ElemSpec::Enumerated(items) => (128, !items.is_empty() &&
+                items.iter().all(|s| !s.is_empty() && s.len() < 64 && !s.contains('\0'))),
+            ElemSpec::Bytes => (512, true),
+            ElemSpec::IEC958 => (1, true),
+        };
+        if ok && (1..=maxcount).contains(&count) { Ok(()) }
+        else { Err(Error::new("Ctl::elem_replace", libc::EINVAL)) }
+    }
+}
+
 /// [snd_ctl_elem_value_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___control.html) wrapper
 pub struct ElemValue {
     ptr: *mut alsa::snd_ctl_elem_value_t,
@@ -272,7 +421,7 @@ impl ElemValue {
     }
 
     pub fn set_integer64(&mut self, idx: u32, val: i64) -> Option<()> {
-        if self.etype != ElemType::Integer || idx >= self.count { None }
+        if self.etype != ElemType::Integer64 || idx >= self.count { None }
         else { unsafe { alsa::snd_ctl_elem_value_set_integer64(self.ptr, idx as c_uint, val) }; Some(()) }
     }
 
@@ -310,6 +459,27 @@ impl ElemValue {
         else { unsafe { alsa::snd_ctl_elem_set_bytes(self.ptr, val.as_ptr() as *mut c_void, val.len() as size_t) }; Some(()) }
     }
 
+    /// Returns the channel status of an element of type `ElemType::IEC958`, e g "IEC958 Playback Default".
+    pub fn get_iec958(&self) -> Option<Iec958Status> {
+        if self.etype != ElemType::IEC958 { None }
+        else { Some(Iec958Status(self.iec958().status)) }
+    }
+
+    /// Sets the channel status, keeping the subcode and the rest of the IEC958 value as is.
+    pub fn set_iec958(&mut self, val: &Iec958Status) -> Option<()> {
+        if self.etype != ElemType::IEC958 { return None }
+        let mut v = self.iec958();
+        v.status = val.0;
+        unsafe { alsa::snd_ctl_elem_value_set_iec958(self.ptr, &v) };
+        Some(())
+    }
+
+    fn iec958(&self) -> alsa::snd_aes_iec958_t {
+        let mut v: alsa::snd_aes_iec958_t = unsafe { mem::zeroed() };
+        unsafe { alsa::snd_ctl_elem_value_get_iec958(self.ptr, &mut v) };
+        v
+    }
+
     /// Creates a new ElemValue.
     pub fn new(t: ElemType) -> Result<ElemValue> {
         // See max length in include/uapi/sound/asound.h in linux kernel for these values
@@ -340,6 +510,7 @@ impl fmt::Debug for ElemValue {
             Integer64 => write!(f, ",{:?}", self.get_integer64(a).unwrap()),
             Enumerated => write!(f, ",{:?}", self.get_enumerated(a).unwrap()),
             Bytes => write!(f, ",{:?}", self.get_byte(a).unwrap()),
+            IEC958 => write!(f, ",{:?}", self.get_iec958().unwrap().0),
             _ => Ok(()),
         }?};
         write!(f, ")")
@@ -361,10 +532,93 @@ pub fn elem_info_new() -> Result<ElemInfo> {
     acheck!(snd_ctl_elem_info_malloc(&mut p)).map(|_| ElemInfo(p))
 }
 
+/// Loads the name of every item into `info`, using `f` to refresh it.
+pub fn elem_info_item_names<F: FnMut(&ElemInfo) -> Result<()>>(info: &mut ElemInfo, mut f: F) -> Result<Vec<String>> {
+    let items = info.get_items().ok_or_else(|| Error::unsupported("snd_ctl_elem_info_get_items"))?;
+    (0..items).map(|i| {
+        info.set_item(i);
+        f(info)?;
+        info.get_item_name().map(|s| s.to_string())
+    }).collect()
+}
+
 impl ElemInfo {
     pub fn get_type(&self) -> ElemType { ElemType::from_c_int(
         unsafe { alsa::snd_ctl_elem_info_get_type(self.0) } as c_int, "snd_ctl_elem_info_get_type").unwrap() }
     pub fn get_count(&self) -> u32 { unsafe { alsa::snd_ctl_elem_info_get_count(self.0) as u32 } }
+
+    pub fn get_id(&self) -> ElemId {
+        let r = elem_id_new().unwrap();
+        unsafe { alsa::snd_ctl_elem_info_get_id(self.0, elem_id_ptr(&r)) };
+        r
+    }
+
+    pub fn is_readable(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_readable(self.0) != 0 } }
+    pub fn is_writable(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_writable(self.0) != 0 } }
+    pub fn is_volatile(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_volatile(self.0) != 0 } }
+    pub fn is_inactive(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_inactive(self.0) != 0 } }
+    pub fn is_locked(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_locked(self.0) != 0 } }
+    pub fn is_tlv_readable(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_tlv_readable(self.0) != 0 } }
+    pub fn is_tlv_writable(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_tlv_writable(self.0) != 0 } }
+    pub fn is_tlv_commandable(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_tlv_commandable(self.0) != 0 } }
+    /// Whether this element was added by user space, see `Ctl::elem_add_integer` etc.
+    pub fn is_user(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_user(self.0) != 0 } }
+    /// Whether this element is locked by the calling application.
+    pub fn is_owner(&self) -> bool { unsafe { alsa::snd_ctl_elem_info_is_owner(self.0) != 0 } }
+    /// The process that has locked this element, if any.
+    pub fn get_owner(&self) -> Option<i32> {
+        let r = unsafe { alsa::snd_ctl_elem_info_get_owner(self.0) };
+        if r > 0 { Some(r as i32) } else { None }
+    }
+
+    // Like for ElemValue, the range getters return None if the type is wrong.
+
+    pub fn get_min(&self) -> Option<i32> {
+        if self.get_type() != ElemType::Integer { None }
+        else { Some(unsafe { alsa::snd_ctl_elem_info_get_min(self.0) } as i32) }
+    }
+    pub fn get_max(&self) -> Option<i32> {
+        if self.get_type() != ElemType::Integer { None }
+        else { Some(unsafe { alsa::snd_ctl_elem_info_get_max(self.0) } as i32) }
+    }
+    pub fn get_step(&self) -> Option<i32> {
+        if self.get_type() != ElemType::Integer { None }
+        else { Some(unsafe { alsa::snd_ctl_elem_info_get_step(self.0) } as i32) }
+    }
+
+    pub fn get_min64(&self) -> Option<i64> {
+        if self.get_type() != ElemType::Integer64 { None }
+        else { Some(unsafe { alsa::snd_ctl_elem_info_get_min64(self.0) }) }
+    }
+    pub fn get_max64(&self) -> Option<i64> {
+        if self.get_type() != ElemType::Integer64 { None }
+        else { Some(unsafe { alsa::snd_ctl_elem_info_get_max64(self.0) }) }
+    }
+    pub fn get_step64(&self) -> Option<i64> {
+        if self.get_type() != ElemType::Integer64 { None }
+        else { Some(unsafe { alsa::snd_ctl_elem_info_get_step64(self.0) }) }
+    }
+
+    /// Number of items of an element of type `ElemType::Enumerated`
+    pub fn get_items(&self) -> Option<u32> {
+        if self.get_type() != ElemType::Enumerated { None }
+        else { Some(unsafe { alsa::snd_ctl_elem_info_get_items(self.0) } as u32) }
+    }
+
+    /// Selects the item for `get_item_name`. The info must then be refreshed, e g with `Ctl::elem_info`.
+    ///
+    /// `Ctl::elem_item_names` and `hctl::Elem::item_names` do all of this for you.
+    pub fn set_item(&mut self, item: u32) { unsafe { alsa::snd_ctl_elem_info_set_item(self.0, item as c_uint) } }
+
+    pub fn get_item_name(&self) -> Result<&str> {
+        from_const("snd_ctl_elem_info_get_item_name", unsafe { alsa::snd_ctl_elem_info_get_item_name(self.0) })
+    }
+
+    /// The dimensions of the values of this element, if it is a matrix. Empty otherwise.
+    pub fn get_dimensions(&self) -> Vec<u32> {
+        let n = unsafe { alsa::snd_ctl_elem_info_get_dimensions(self.0) };
+        (0..cmp::max(n, 0) as c_uint).map(|i| unsafe { alsa::snd_ctl_elem_info_get_dimension(self.0, i) } as u32).collect()
+    }
 }
 
 //
@@ -564,3 +818,87 @@ fn print_sizeof() {
 
     std::println!("Elem id: {}, Elem value: {}, Elem info: {}", elemid, elemvalue, eleminfo);
 }
+
+#[test]
+fn value_integer64() {
+    let mut v = ElemValue::new(ElemType::Integer64).unwrap();
+    assert_eq!(v.set_integer64(0, -1 << 40), Some(()));
+    assert_eq!(v.get_integer64(0), Some(-1 << 40));
+    let mut v = ElemValue::new(ElemType::Integer).unwrap();
+    assert_eq!(v.set_integer64(0, 5), None);
+}
+
+#[test]
+fn elem_info_from_plugin() {
+    use crate::plugin::ctl_ext::{self, CtlExt, Access};
+
+    struct Elems;
+
+    impl CtlExt for Elems {
+        fn elem_count(&mut self) -> u32 { 2 }
+        fn elem_list(&mut self, offset: u32) -> Result<ElemId> {
+            let mut id = ElemId::new(ElemIface::Mixer);
+            id.set_name(if offset == 0 { c"Gain" } else { c"Mode" });
+            Ok(id)
+        }
+        fn get_attribute(&mut self, key: u32) -> Result<(ElemType, Access, u32)> {
+            Ok(if key == 0 { (ElemType::Integer, Access::READ, 2) } else { (ElemType::Enumerated, Access::READWRITE, 1) })
+        }
+        fn get_integer_info(&mut self, _: u32) -> Result<(i32, i32, i32)> { Ok((-10, 90, 5)) }
+        fn get_enumerated_info(&mut self, _: u32) -> Result<u32> { Ok(3) }
+        fn get_enumerated_name(&mut self, _: u32, item: u32) -> Result<&str> { Ok(["Off", "Low", "High"][item as usize]) }
+        fn read(&mut self, _: u32, _: &mut ElemValue) -> Result<()> { Ok(()) }
+    }
+
+    let ctl = ctl_ext::create("rust_elem_info", false, Elems).unwrap();
+    let list = ctl.elem_list().unwrap();
+    let gain = ctl.elem_info(&list.get_id(0).unwrap()).unwrap();
+    assert_eq!(gain.get_id().get_name().unwrap(), "Gain");
+    assert_eq!((gain.get_type(), gain.get_count()), (ElemType::Integer, 2));
+    assert_eq!((gain.get_min(), gain.get_max(), gain.get_step()), (Some(-10), Some(90), Some(5)));
+    assert_eq!((gain.get_min64(), gain.get_items()), (None, None));
+    assert!(gain.is_readable() && !gain.is_writable() && !gain.is_user());
+    assert_eq!(gain.get_dimensions(), &[]);
+
+    let mode = list.get_id(1).unwrap();
+    let info = ctl.elem_info(&mode).unwrap();
+    assert_eq!((info.get_items(), info.get_min()), (Some(3), None));
+    assert!(info.is_writable());
+    assert_eq!(ctl.elem_item_names(&mode).unwrap(), &["Off", "Low", "High"]);
+    assert!(ctl.elem_item_names(&list.get_id(0).unwrap()).is_err());
+}
+
+#[test]
+fn user_elems() {
+    use crate::plugin::ctl_ext::{self, CtlExt, Access};
+
+    // Plugins cannot have user elements, so elem_remove always fails with ENXIO
+    struct Fixed;
+
+    impl CtlExt for Fixed {
+        fn elem_count(&mut self) -> u32 { 1 }
+        fn elem_list(&mut self, _: u32) -> Result<ElemId> {
+            let mut id = ElemId::new(ElemIface::Mixer);
+            id.set_name(c"Rust Test Volume");
+            Ok(id)
+        }
+        fn get_attribute(&mut self, _: u32) -> Result<(ElemType, Access, u32)> { Ok((ElemType::Integer, Access::READ, 2)) }
+        fn get_integer_info(&mut self, _: u32) -> Result<(i32, i32, i32)> { Ok((0, 50, 1)) }
+        fn read(&mut self, _: u32, _: &mut ElemValue) -> Result<()> { Ok(()) }
+    }
+
+    let ctl = ctl_ext::create("rust_user_elems", false, Fixed).unwrap();
+    let id = ctl.elem_list().unwrap().get_id(0).unwrap();
+    let errno = |r: Result<()>| r.unwrap_err().errno();
+    assert_eq!(errno(ctl.elem_replace(&id, 0, ElemSpec::Boolean)), libc::EINVAL);
+    assert_eq!(errno(ctl.elem_replace(&id, 65, ElemSpec::Integer64 { min: 0, max: 1, step: 1 })), libc::EINVAL);
+    assert_eq!(errno(ctl.elem_replace(&id, 1, ElemSpec::Integer { min: 5, max: 0, step: 1 })), libc::EINVAL);
+    assert_eq!(errno(ctl.elem_replace(&id, 1, ElemSpec::Enumerated(&[]))), libc::EINVAL);
+    assert_eq!(errno(ctl.elem_replace(&id, 1, ElemSpec::Enumerated(&["A", "B\0"]))), libc::EINVAL);
+    assert_eq!(errno(ctl.elem_replace(&id, 1, ElemSpec::Enumerated(&["A", "B"]))), libc::ENXIO);
+    assert!(ctl.elem_add_integer(&id, 2, 0, 50, 1).is_err());
+
+    let info = ctl.elem_info(&id).unwrap();
+    assert!(!info.is_user());
+    assert_eq!((info.get_count(), info.get_max()), (2, Some(50)));
+}
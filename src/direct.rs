@@ -3,3 +3,7 @@
 pub mod pcm;
 
 mod ffi;
+
+// Kernel structs as generated by bindgen from include/uapi/sound/asound.h
+#[allow(dead_code, non_camel_case_types, non_upper_case_globals, non_snake_case, clippy::all)]
+pub(crate) mod asound_ioctl;
@@ -334,7 +334,7 @@ fn bindgen_test_layout___kernel_fd_set() {
         concat!("Alignment of ", stringify!(__kernel_fd_set))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<__kernel_fd_set>())).fds_bits as *const _ as usize },
+        ::core::mem::offset_of!(__kernel_fd_set, fds_bits),
         0usize,
         concat!(
             "Offset of field: ",
@@ -384,7 +384,7 @@ fn bindgen_test_layout___kernel_fsid_t() {
         concat!("Alignment of ", stringify!(__kernel_fsid_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<__kernel_fsid_t>())).val as *const _ as usize },
+        ::core::mem::offset_of!(__kernel_fsid_t, val),
         0usize,
         concat!(
             "Offset of field: ",
@@ -458,7 +458,7 @@ fn bindgen_test_layout___fsid_t() {
         concat!("Alignment of ", stringify!(__fsid_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<__fsid_t>())).__val as *const _ as usize },
+        ::core::mem::offset_of!(__fsid_t, __val),
         0usize,
         concat!(
             "Offset of field: ",
@@ -652,7 +652,7 @@ fn bindgen_test_layout_wait() {
         concat!("Alignment of ", stringify!(wait))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<wait>())).w_status as *const _ as usize },
+        ::core::mem::offset_of!(wait, w_status),
         0usize,
         concat!(
             "Offset of field: ",
@@ -662,7 +662,7 @@ fn bindgen_test_layout_wait() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<wait>())).__wait_terminated as *const _ as usize },
+        ::core::mem::offset_of!(wait, __wait_terminated),
         0usize,
         concat!(
             "Offset of field: ",
@@ -672,7 +672,7 @@ fn bindgen_test_layout_wait() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<wait>())).__wait_stopped as *const _ as usize },
+        ::core::mem::offset_of!(wait, __wait_stopped),
         0usize,
         concat!(
             "Offset of field: ",
@@ -702,7 +702,7 @@ fn bindgen_test_layout___WAIT_STATUS() {
         concat!("Alignment of ", stringify!(__WAIT_STATUS))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<__WAIT_STATUS>())).__uptr as *const _ as usize },
+        ::core::mem::offset_of!(__WAIT_STATUS, __uptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -712,7 +712,7 @@ fn bindgen_test_layout___WAIT_STATUS() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<__WAIT_STATUS>())).__iptr as *const _ as usize },
+        ::core::mem::offset_of!(__WAIT_STATUS, __iptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -741,7 +741,7 @@ fn bindgen_test_layout_div_t() {
         concat!("Alignment of ", stringify!(div_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<div_t>())).quot as *const _ as usize },
+        ::core::mem::offset_of!(div_t, quot),
         0usize,
         concat!(
             "Offset of field: ",
@@ -751,7 +751,7 @@ fn bindgen_test_layout_div_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<div_t>())).rem as *const _ as usize },
+        ::core::mem::offset_of!(div_t, rem),
         4usize,
         concat!(
             "Offset of field: ",
@@ -780,7 +780,7 @@ fn bindgen_test_layout_ldiv_t() {
         concat!("Alignment of ", stringify!(ldiv_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<ldiv_t>())).quot as *const _ as usize },
+        ::core::mem::offset_of!(ldiv_t, quot),
         0usize,
         concat!(
             "Offset of field: ",
@@ -790,7 +790,7 @@ fn bindgen_test_layout_ldiv_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<ldiv_t>())).rem as *const _ as usize },
+        ::core::mem::offset_of!(ldiv_t, rem),
         8usize,
         concat!(
             "Offset of field: ",
@@ -819,7 +819,7 @@ fn bindgen_test_layout_lldiv_t() {
         concat!("Alignment of ", stringify!(lldiv_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<lldiv_t>())).quot as *const _ as usize },
+        ::core::mem::offset_of!(lldiv_t, quot),
         0usize,
         concat!(
             "Offset of field: ",
@@ -829,7 +829,7 @@ fn bindgen_test_layout_lldiv_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<lldiv_t>())).rem as *const _ as usize },
+        ::core::mem::offset_of!(lldiv_t, rem),
         8usize,
         concat!(
             "Offset of field: ",
@@ -971,7 +971,7 @@ fn bindgen_test_layout___sigset_t() {
         concat!("Alignment of ", stringify!(__sigset_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<__sigset_t>())).__val as *const _ as usize },
+        ::core::mem::offset_of!(__sigset_t, __val),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1001,7 +1001,7 @@ fn bindgen_test_layout_timespec() {
         concat!("Alignment of ", stringify!(timespec))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<timespec>())).tv_sec as *const _ as usize },
+        ::core::mem::offset_of!(timespec, tv_sec),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1011,7 +1011,7 @@ fn bindgen_test_layout_timespec() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<timespec>())).tv_nsec as *const _ as usize },
+        ::core::mem::offset_of!(timespec, tv_nsec),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1040,7 +1040,7 @@ fn bindgen_test_layout_timeval() {
         concat!("Alignment of ", stringify!(timeval))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<timeval>())).tv_sec as *const _ as usize },
+        ::core::mem::offset_of!(timeval, tv_sec),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1050,7 +1050,7 @@ fn bindgen_test_layout_timeval() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<timeval>())).tv_usec as *const _ as usize },
+        ::core::mem::offset_of!(timeval, tv_usec),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1080,7 +1080,7 @@ fn bindgen_test_layout_fd_set() {
         concat!("Alignment of ", stringify!(fd_set))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<fd_set>())).__fds_bits as *const _ as usize },
+        ::core::mem::offset_of!(fd_set, __fds_bits),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1147,7 +1147,7 @@ fn bindgen_test_layout_pthread_attr_t() {
         concat!("Alignment of ", stringify!(pthread_attr_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_attr_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_attr_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1157,7 +1157,7 @@ fn bindgen_test_layout_pthread_attr_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_attr_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_attr_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1186,7 +1186,7 @@ fn bindgen_test_layout___pthread_internal_list() {
         concat!("Alignment of ", stringify!(__pthread_internal_list))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<__pthread_internal_list>())).__prev as *const _ as usize },
+        ::core::mem::offset_of!(__pthread_internal_list, __prev),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1196,7 +1196,7 @@ fn bindgen_test_layout___pthread_internal_list() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<__pthread_internal_list>())).__next as *const _ as usize },
+        ::core::mem::offset_of!(__pthread_internal_list, __next),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1243,10 +1243,7 @@ fn bindgen_test_layout_pthread_mutex_t___pthread_mutex_s() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_mutex_t___pthread_mutex_s>())).__lock as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_mutex_t___pthread_mutex_s, __lock),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1256,10 +1253,7 @@ fn bindgen_test_layout_pthread_mutex_t___pthread_mutex_s() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_mutex_t___pthread_mutex_s>())).__count as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_mutex_t___pthread_mutex_s, __count),
         4usize,
         concat!(
             "Offset of field: ",
@@ -1269,10 +1263,7 @@ fn bindgen_test_layout_pthread_mutex_t___pthread_mutex_s() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_mutex_t___pthread_mutex_s>())).__owner as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_mutex_t___pthread_mutex_s, __owner),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1282,10 +1273,7 @@ fn bindgen_test_layout_pthread_mutex_t___pthread_mutex_s() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_mutex_t___pthread_mutex_s>())).__nusers as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_mutex_t___pthread_mutex_s, __nusers),
         12usize,
         concat!(
             "Offset of field: ",
@@ -1295,10 +1283,7 @@ fn bindgen_test_layout_pthread_mutex_t___pthread_mutex_s() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_mutex_t___pthread_mutex_s>())).__kind as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_mutex_t___pthread_mutex_s, __kind),
         16usize,
         concat!(
             "Offset of field: ",
@@ -1308,10 +1293,7 @@ fn bindgen_test_layout_pthread_mutex_t___pthread_mutex_s() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_mutex_t___pthread_mutex_s>())).__spins as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_mutex_t___pthread_mutex_s, __spins),
         20usize,
         concat!(
             "Offset of field: ",
@@ -1321,10 +1303,7 @@ fn bindgen_test_layout_pthread_mutex_t___pthread_mutex_s() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_mutex_t___pthread_mutex_s>())).__elision as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_mutex_t___pthread_mutex_s, __elision),
         22usize,
         concat!(
             "Offset of field: ",
@@ -1334,10 +1313,7 @@ fn bindgen_test_layout_pthread_mutex_t___pthread_mutex_s() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_mutex_t___pthread_mutex_s>())).__list as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_mutex_t___pthread_mutex_s, __list),
         24usize,
         concat!(
             "Offset of field: ",
@@ -1360,7 +1336,7 @@ fn bindgen_test_layout_pthread_mutex_t() {
         concat!("Alignment of ", stringify!(pthread_mutex_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_mutex_t>())).__data as *const _ as usize },
+        ::core::mem::offset_of!(pthread_mutex_t, __data),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1370,7 +1346,7 @@ fn bindgen_test_layout_pthread_mutex_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_mutex_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_mutex_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1380,7 +1356,7 @@ fn bindgen_test_layout_pthread_mutex_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_mutex_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_mutex_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1410,7 +1386,7 @@ fn bindgen_test_layout_pthread_mutexattr_t() {
         concat!("Alignment of ", stringify!(pthread_mutexattr_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_mutexattr_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_mutexattr_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1420,7 +1396,7 @@ fn bindgen_test_layout_pthread_mutexattr_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_mutexattr_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_mutexattr_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1463,9 +1439,7 @@ fn bindgen_test_layout_pthread_cond_t__bindgen_ty_1() {
         concat!("Alignment of ", stringify!(pthread_cond_t__bindgen_ty_1))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_cond_t__bindgen_ty_1>())).__lock as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_cond_t__bindgen_ty_1, __lock),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1475,9 +1449,7 @@ fn bindgen_test_layout_pthread_cond_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_cond_t__bindgen_ty_1>())).__futex as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_cond_t__bindgen_ty_1, __futex),
         4usize,
         concat!(
             "Offset of field: ",
@@ -1487,10 +1459,7 @@ fn bindgen_test_layout_pthread_cond_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_cond_t__bindgen_ty_1>())).__total_seq as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_cond_t__bindgen_ty_1, __total_seq),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1500,10 +1469,7 @@ fn bindgen_test_layout_pthread_cond_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_cond_t__bindgen_ty_1>())).__wakeup_seq as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_cond_t__bindgen_ty_1, __wakeup_seq),
         16usize,
         concat!(
             "Offset of field: ",
@@ -1513,10 +1479,7 @@ fn bindgen_test_layout_pthread_cond_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_cond_t__bindgen_ty_1>())).__woken_seq as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_cond_t__bindgen_ty_1, __woken_seq),
         24usize,
         concat!(
             "Offset of field: ",
@@ -1526,9 +1489,7 @@ fn bindgen_test_layout_pthread_cond_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_cond_t__bindgen_ty_1>())).__mutex as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_cond_t__bindgen_ty_1, __mutex),
         32usize,
         concat!(
             "Offset of field: ",
@@ -1538,9 +1499,7 @@ fn bindgen_test_layout_pthread_cond_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_cond_t__bindgen_ty_1>())).__nwaiters as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_cond_t__bindgen_ty_1, __nwaiters),
         40usize,
         concat!(
             "Offset of field: ",
@@ -1550,10 +1509,7 @@ fn bindgen_test_layout_pthread_cond_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_cond_t__bindgen_ty_1>())).__broadcast_seq as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_cond_t__bindgen_ty_1, __broadcast_seq),
         44usize,
         concat!(
             "Offset of field: ",
@@ -1576,7 +1532,7 @@ fn bindgen_test_layout_pthread_cond_t() {
         concat!("Alignment of ", stringify!(pthread_cond_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_cond_t>())).__data as *const _ as usize },
+        ::core::mem::offset_of!(pthread_cond_t, __data),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1586,7 +1542,7 @@ fn bindgen_test_layout_pthread_cond_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_cond_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_cond_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1596,7 +1552,7 @@ fn bindgen_test_layout_pthread_cond_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_cond_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_cond_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1626,7 +1582,7 @@ fn bindgen_test_layout_pthread_condattr_t() {
         concat!("Alignment of ", stringify!(pthread_condattr_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_condattr_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_condattr_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1636,7 +1592,7 @@ fn bindgen_test_layout_pthread_condattr_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_condattr_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_condattr_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1685,9 +1641,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         concat!("Alignment of ", stringify!(pthread_rwlock_t__bindgen_ty_1))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__lock as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __lock),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1697,10 +1651,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__nr_readers as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __nr_readers),
         4usize,
         concat!(
             "Offset of field: ",
@@ -1710,10 +1661,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__readers_wakeup as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __readers_wakeup),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1723,10 +1671,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__writer_wakeup as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __writer_wakeup),
         12usize,
         concat!(
             "Offset of field: ",
@@ -1736,10 +1681,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__nr_readers_queued
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __nr_readers_queued),
         16usize,
         concat!(
             "Offset of field: ",
@@ -1749,10 +1691,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__nr_writers_queued
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __nr_writers_queued),
         20usize,
         concat!(
             "Offset of field: ",
@@ -1762,9 +1701,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__writer as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __writer),
         24usize,
         concat!(
             "Offset of field: ",
@@ -1774,9 +1711,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__shared as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __shared),
         28usize,
         concat!(
             "Offset of field: ",
@@ -1786,10 +1721,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__rwelision as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __rwelision),
         32usize,
         concat!(
             "Offset of field: ",
@@ -1799,9 +1731,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__pad1 as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __pad1),
         33usize,
         concat!(
             "Offset of field: ",
@@ -1811,9 +1741,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__pad2 as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __pad2),
         40usize,
         concat!(
             "Offset of field: ",
@@ -1823,9 +1751,7 @@ fn bindgen_test_layout_pthread_rwlock_t__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<pthread_rwlock_t__bindgen_ty_1>())).__flags as *const _ as usize
-        },
+        ::core::mem::offset_of!(pthread_rwlock_t__bindgen_ty_1, __flags),
         48usize,
         concat!(
             "Offset of field: ",
@@ -1848,7 +1774,7 @@ fn bindgen_test_layout_pthread_rwlock_t() {
         concat!("Alignment of ", stringify!(pthread_rwlock_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_rwlock_t>())).__data as *const _ as usize },
+        ::core::mem::offset_of!(pthread_rwlock_t, __data),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1858,7 +1784,7 @@ fn bindgen_test_layout_pthread_rwlock_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_rwlock_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_rwlock_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1868,7 +1794,7 @@ fn bindgen_test_layout_pthread_rwlock_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_rwlock_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_rwlock_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1898,7 +1824,7 @@ fn bindgen_test_layout_pthread_rwlockattr_t() {
         concat!("Alignment of ", stringify!(pthread_rwlockattr_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_rwlockattr_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_rwlockattr_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1908,7 +1834,7 @@ fn bindgen_test_layout_pthread_rwlockattr_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_rwlockattr_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_rwlockattr_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1939,7 +1865,7 @@ fn bindgen_test_layout_pthread_barrier_t() {
         concat!("Alignment of ", stringify!(pthread_barrier_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_barrier_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_barrier_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1949,7 +1875,7 @@ fn bindgen_test_layout_pthread_barrier_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_barrier_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_barrier_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1979,7 +1905,7 @@ fn bindgen_test_layout_pthread_barrierattr_t() {
         concat!("Alignment of ", stringify!(pthread_barrierattr_t))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_barrierattr_t>())).__size as *const _ as usize },
+        ::core::mem::offset_of!(pthread_barrierattr_t, __size),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1989,7 +1915,7 @@ fn bindgen_test_layout_pthread_barrierattr_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<pthread_barrierattr_t>())).__align as *const _ as usize },
+        ::core::mem::offset_of!(pthread_barrierattr_t, __align),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2039,7 +1965,7 @@ fn bindgen_test_layout_random_data() {
         concat!("Alignment of ", stringify!(random_data))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<random_data>())).fptr as *const _ as usize },
+        ::core::mem::offset_of!(random_data, fptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2049,7 +1975,7 @@ fn bindgen_test_layout_random_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<random_data>())).rptr as *const _ as usize },
+        ::core::mem::offset_of!(random_data, rptr),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2059,7 +1985,7 @@ fn bindgen_test_layout_random_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<random_data>())).state as *const _ as usize },
+        ::core::mem::offset_of!(random_data, state),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2069,7 +1995,7 @@ fn bindgen_test_layout_random_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<random_data>())).rand_type as *const _ as usize },
+        ::core::mem::offset_of!(random_data, rand_type),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2079,7 +2005,7 @@ fn bindgen_test_layout_random_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<random_data>())).rand_deg as *const _ as usize },
+        ::core::mem::offset_of!(random_data, rand_deg),
         28usize,
         concat!(
             "Offset of field: ",
@@ -2089,7 +2015,7 @@ fn bindgen_test_layout_random_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<random_data>())).rand_sep as *const _ as usize },
+        ::core::mem::offset_of!(random_data, rand_sep),
         32usize,
         concat!(
             "Offset of field: ",
@@ -2099,7 +2025,7 @@ fn bindgen_test_layout_random_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<random_data>())).end_ptr as *const _ as usize },
+        ::core::mem::offset_of!(random_data, end_ptr),
         40usize,
         concat!(
             "Offset of field: ",
@@ -2190,7 +2116,7 @@ fn bindgen_test_layout_drand48_data() {
         concat!("Alignment of ", stringify!(drand48_data))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<drand48_data>())).__x as *const _ as usize },
+        ::core::mem::offset_of!(drand48_data, __x),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2200,7 +2126,7 @@ fn bindgen_test_layout_drand48_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<drand48_data>())).__old_x as *const _ as usize },
+        ::core::mem::offset_of!(drand48_data, __old_x),
         6usize,
         concat!(
             "Offset of field: ",
@@ -2210,7 +2136,7 @@ fn bindgen_test_layout_drand48_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<drand48_data>())).__c as *const _ as usize },
+        ::core::mem::offset_of!(drand48_data, __c),
         12usize,
         concat!(
             "Offset of field: ",
@@ -2220,7 +2146,7 @@ fn bindgen_test_layout_drand48_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<drand48_data>())).__init as *const _ as usize },
+        ::core::mem::offset_of!(drand48_data, __init),
         14usize,
         concat!(
             "Offset of field: ",
@@ -2230,7 +2156,7 @@ fn bindgen_test_layout_drand48_data() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<drand48_data>())).__a as *const _ as usize },
+        ::core::mem::offset_of!(drand48_data, __a),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2587,7 +2513,7 @@ fn bindgen_test_layout_snd_aes_iec958() {
         concat!("Alignment of ", stringify!(snd_aes_iec958))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_aes_iec958>())).status as *const _ as usize },
+        ::core::mem::offset_of!(snd_aes_iec958, status),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2597,7 +2523,7 @@ fn bindgen_test_layout_snd_aes_iec958() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_aes_iec958>())).subcode as *const _ as usize },
+        ::core::mem::offset_of!(snd_aes_iec958, subcode),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2607,7 +2533,7 @@ fn bindgen_test_layout_snd_aes_iec958() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_aes_iec958>())).pad as *const _ as usize },
+        ::core::mem::offset_of!(snd_aes_iec958, pad),
         171usize,
         concat!(
             "Offset of field: ",
@@ -2617,7 +2543,7 @@ fn bindgen_test_layout_snd_aes_iec958() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_aes_iec958>())).dig_subframe as *const _ as usize },
+        ::core::mem::offset_of!(snd_aes_iec958, dig_subframe),
         172usize,
         concat!(
             "Offset of field: ",
@@ -2652,7 +2578,7 @@ fn bindgen_test_layout_snd_cea_861_aud_if() {
         concat!("Alignment of ", stringify!(snd_cea_861_aud_if))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_cea_861_aud_if>())).db1_ct_cc as *const _ as usize },
+        ::core::mem::offset_of!(snd_cea_861_aud_if, db1_ct_cc),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2662,7 +2588,7 @@ fn bindgen_test_layout_snd_cea_861_aud_if() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_cea_861_aud_if>())).db2_sf_ss as *const _ as usize },
+        ::core::mem::offset_of!(snd_cea_861_aud_if, db2_sf_ss),
         1usize,
         concat!(
             "Offset of field: ",
@@ -2672,7 +2598,7 @@ fn bindgen_test_layout_snd_cea_861_aud_if() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_cea_861_aud_if>())).db3 as *const _ as usize },
+        ::core::mem::offset_of!(snd_cea_861_aud_if, db3),
         2usize,
         concat!(
             "Offset of field: ",
@@ -2682,7 +2608,7 @@ fn bindgen_test_layout_snd_cea_861_aud_if() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_cea_861_aud_if>())).db4_ca as *const _ as usize },
+        ::core::mem::offset_of!(snd_cea_861_aud_if, db4_ca),
         3usize,
         concat!(
             "Offset of field: ",
@@ -2692,9 +2618,7 @@ fn bindgen_test_layout_snd_cea_861_aud_if() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_cea_861_aud_if>())).db5_dminh_lsv as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_cea_861_aud_if, db5_dminh_lsv),
         4usize,
         concat!(
             "Offset of field: ",
@@ -2756,7 +2680,7 @@ fn bindgen_test_layout_snd_hwdep_info() {
         concat!("Alignment of ", stringify!(snd_hwdep_info))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_info>())).device as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_info, device),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2766,7 +2690,7 @@ fn bindgen_test_layout_snd_hwdep_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_info>())).card as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_info, card),
         4usize,
         concat!(
             "Offset of field: ",
@@ -2776,7 +2700,7 @@ fn bindgen_test_layout_snd_hwdep_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_info>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_info, id),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2786,7 +2710,7 @@ fn bindgen_test_layout_snd_hwdep_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_info>())).name as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_info, name),
         72usize,
         concat!(
             "Offset of field: ",
@@ -2796,7 +2720,7 @@ fn bindgen_test_layout_snd_hwdep_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_info>())).iface as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_info, iface),
         152usize,
         concat!(
             "Offset of field: ",
@@ -2806,7 +2730,7 @@ fn bindgen_test_layout_snd_hwdep_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_info>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_info, reserved),
         156usize,
         concat!(
             "Offset of field: ",
@@ -2839,7 +2763,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_status() {
         concat!("Alignment of ", stringify!(snd_hwdep_dsp_status))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_status>())).version as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_status, version),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2849,7 +2773,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_status>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_status, id),
         4usize,
         concat!(
             "Offset of field: ",
@@ -2859,7 +2783,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_status>())).num_dsps as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_status, num_dsps),
         36usize,
         concat!(
             "Offset of field: ",
@@ -2869,7 +2793,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_status>())).dsp_loaded as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_status, dsp_loaded),
         40usize,
         concat!(
             "Offset of field: ",
@@ -2879,7 +2803,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_status>())).chip_ready as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_status, chip_ready),
         44usize,
         concat!(
             "Offset of field: ",
@@ -2889,7 +2813,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_status>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_status, reserved),
         48usize,
         concat!(
             "Offset of field: ",
@@ -2921,7 +2845,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_image() {
         concat!("Alignment of ", stringify!(snd_hwdep_dsp_image))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_image>())).index as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_image, index),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2931,7 +2855,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_image() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_image>())).name as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_image, name),
         4usize,
         concat!(
             "Offset of field: ",
@@ -2941,7 +2865,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_image() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_image>())).image as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_image, image),
         72usize,
         concat!(
             "Offset of field: ",
@@ -2951,7 +2875,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_image() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_image>())).length as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_image, length),
         80usize,
         concat!(
             "Offset of field: ",
@@ -2961,7 +2885,7 @@ fn bindgen_test_layout_snd_hwdep_dsp_image() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_hwdep_dsp_image>())).driver_data as *const _ as usize },
+        ::core::mem::offset_of!(snd_hwdep_dsp_image, driver_data),
         88usize,
         concat!(
             "Offset of field: ",
@@ -3016,7 +2940,7 @@ fn bindgen_test_layout_snd_pcm_sync_id() {
         concat!("Alignment of ", stringify!(snd_pcm_sync_id))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sync_id>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sync_id, id),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3026,7 +2950,7 @@ fn bindgen_test_layout_snd_pcm_sync_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sync_id>())).id16 as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sync_id, id16),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3036,7 +2960,7 @@ fn bindgen_test_layout_snd_pcm_sync_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sync_id>())).id32 as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sync_id, id32),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3076,7 +3000,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         concat!("Alignment of ", stringify!(snd_pcm_info))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).device as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, device),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3086,7 +3010,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).subdevice as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, subdevice),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3096,7 +3020,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).stream as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, stream),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3106,7 +3030,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).card as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, card),
         12usize,
         concat!(
             "Offset of field: ",
@@ -3116,7 +3040,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, id),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3126,7 +3050,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).name as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, name),
         80usize,
         concat!(
             "Offset of field: ",
@@ -3136,7 +3060,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).subname as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, subname),
         160usize,
         concat!(
             "Offset of field: ",
@@ -3146,7 +3070,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).dev_class as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, dev_class),
         192usize,
         concat!(
             "Offset of field: ",
@@ -3156,7 +3080,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).dev_subclass as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, dev_subclass),
         196usize,
         concat!(
             "Offset of field: ",
@@ -3166,7 +3090,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).subdevices_count as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, subdevices_count),
         200usize,
         concat!(
             "Offset of field: ",
@@ -3176,7 +3100,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).subdevices_avail as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, subdevices_avail),
         204usize,
         concat!(
             "Offset of field: ",
@@ -3186,7 +3110,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).sync as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, sync),
         208usize,
         concat!(
             "Offset of field: ",
@@ -3196,7 +3120,7 @@ fn bindgen_test_layout_snd_pcm_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_info>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_info, reserved),
         224usize,
         concat!(
             "Offset of field: ",
@@ -3228,7 +3152,7 @@ fn bindgen_test_layout_snd_interval() {
         concat!("Alignment of ", stringify!(snd_interval))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_interval>())).min as *const _ as usize },
+        ::core::mem::offset_of!(snd_interval, min),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3238,7 +3162,7 @@ fn bindgen_test_layout_snd_interval() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_interval>())).max as *const _ as usize },
+        ::core::mem::offset_of!(snd_interval, max),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3339,7 +3263,7 @@ fn bindgen_test_layout_snd_mask() {
         concat!("Alignment of ", stringify!(snd_mask))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_mask>())).bits as *const _ as usize },
+        ::core::mem::offset_of!(snd_mask, bits),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3379,7 +3303,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         concat!("Alignment of ", stringify!(snd_pcm_hw_params))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).flags as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, flags),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3389,7 +3313,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).masks as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, masks),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3399,7 +3323,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).mres as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, mres),
         100usize,
         concat!(
             "Offset of field: ",
@@ -3409,7 +3333,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).intervals as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, intervals),
         260usize,
         concat!(
             "Offset of field: ",
@@ -3419,7 +3343,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).ires as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, ires),
         404usize,
         concat!(
             "Offset of field: ",
@@ -3429,7 +3353,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).rmask as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, rmask),
         512usize,
         concat!(
             "Offset of field: ",
@@ -3439,7 +3363,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).cmask as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, cmask),
         516usize,
         concat!(
             "Offset of field: ",
@@ -3449,7 +3373,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).info as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, info),
         520usize,
         concat!(
             "Offset of field: ",
@@ -3459,7 +3383,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).msbits as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, msbits),
         524usize,
         concat!(
             "Offset of field: ",
@@ -3469,7 +3393,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).rate_num as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, rate_num),
         528usize,
         concat!(
             "Offset of field: ",
@@ -3479,7 +3403,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).rate_den as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, rate_den),
         532usize,
         concat!(
             "Offset of field: ",
@@ -3489,7 +3413,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).fifo_size as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, fifo_size),
         536usize,
         concat!(
             "Offset of field: ",
@@ -3499,7 +3423,7 @@ fn bindgen_test_layout_snd_pcm_hw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_hw_params>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_hw_params, reserved),
         544usize,
         concat!(
             "Offset of field: ",
@@ -3543,7 +3467,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         concat!("Alignment of ", stringify!(snd_pcm_sw_params))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).tstamp_mode as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, tstamp_mode),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3553,7 +3477,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).period_step as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, period_step),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3563,7 +3487,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).sleep_min as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, sleep_min),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3573,7 +3497,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).avail_min as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, avail_min),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3583,7 +3507,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).xfer_align as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, xfer_align),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3593,9 +3517,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_sw_params>())).start_threshold as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_sw_params, start_threshold),
         32usize,
         concat!(
             "Offset of field: ",
@@ -3605,9 +3527,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_sw_params>())).stop_threshold as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_sw_params, stop_threshold),
         40usize,
         concat!(
             "Offset of field: ",
@@ -3617,9 +3537,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_sw_params>())).silence_threshold as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_sw_params, silence_threshold),
         48usize,
         concat!(
             "Offset of field: ",
@@ -3629,7 +3547,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).silence_size as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, silence_size),
         56usize,
         concat!(
             "Offset of field: ",
@@ -3639,7 +3557,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).boundary as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, boundary),
         64usize,
         concat!(
             "Offset of field: ",
@@ -3649,7 +3567,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).proto as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, proto),
         72usize,
         concat!(
             "Offset of field: ",
@@ -3659,7 +3577,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).tstamp_type as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, tstamp_type),
         76usize,
         concat!(
             "Offset of field: ",
@@ -3669,7 +3587,7 @@ fn bindgen_test_layout_snd_pcm_sw_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sw_params>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sw_params, reserved),
         80usize,
         concat!(
             "Offset of field: ",
@@ -3700,7 +3618,7 @@ fn bindgen_test_layout_snd_pcm_channel_info() {
         concat!("Alignment of ", stringify!(snd_pcm_channel_info))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_channel_info>())).channel as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_channel_info, channel),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3710,7 +3628,7 @@ fn bindgen_test_layout_snd_pcm_channel_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_channel_info>())).offset as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_channel_info, offset),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3720,7 +3638,7 @@ fn bindgen_test_layout_snd_pcm_channel_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_channel_info>())).first as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_channel_info, first),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3730,7 +3648,7 @@ fn bindgen_test_layout_snd_pcm_channel_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_channel_info>())).step as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_channel_info, step),
         20usize,
         concat!(
             "Offset of field: ",
@@ -3780,7 +3698,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         concat!("Alignment of ", stringify!(snd_pcm_status))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).state as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, state),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3790,7 +3708,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).trigger_tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, trigger_tstamp),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3800,7 +3718,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, tstamp),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3810,7 +3728,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).appl_ptr as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, appl_ptr),
         40usize,
         concat!(
             "Offset of field: ",
@@ -3820,7 +3738,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).hw_ptr as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, hw_ptr),
         48usize,
         concat!(
             "Offset of field: ",
@@ -3830,7 +3748,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).delay as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, delay),
         56usize,
         concat!(
             "Offset of field: ",
@@ -3840,7 +3758,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).avail as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, avail),
         64usize,
         concat!(
             "Offset of field: ",
@@ -3850,7 +3768,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).avail_max as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, avail_max),
         72usize,
         concat!(
             "Offset of field: ",
@@ -3860,7 +3778,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).overrange as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, overrange),
         80usize,
         concat!(
             "Offset of field: ",
@@ -3870,7 +3788,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).suspended_state as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, suspended_state),
         88usize,
         concat!(
             "Offset of field: ",
@@ -3880,9 +3798,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_status>())).audio_tstamp_data as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_status, audio_tstamp_data),
         92usize,
         concat!(
             "Offset of field: ",
@@ -3892,7 +3808,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).audio_tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, audio_tstamp),
         96usize,
         concat!(
             "Offset of field: ",
@@ -3902,7 +3818,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).driver_tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, driver_tstamp),
         112usize,
         concat!(
             "Offset of field: ",
@@ -3912,9 +3828,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_status>())).audio_tstamp_accuracy as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_status, audio_tstamp_accuracy),
         128usize,
         concat!(
             "Offset of field: ",
@@ -3924,7 +3838,7 @@ fn bindgen_test_layout_snd_pcm_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_status>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_status, reserved),
         132usize,
         concat!(
             "Offset of field: ",
@@ -3957,7 +3871,7 @@ fn bindgen_test_layout_snd_pcm_mmap_status() {
         concat!("Alignment of ", stringify!(snd_pcm_mmap_status))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_mmap_status>())).state as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_mmap_status, state),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3967,7 +3881,7 @@ fn bindgen_test_layout_snd_pcm_mmap_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_mmap_status>())).pad1 as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_mmap_status, pad1),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3977,7 +3891,7 @@ fn bindgen_test_layout_snd_pcm_mmap_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_mmap_status>())).hw_ptr as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_mmap_status, hw_ptr),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3987,7 +3901,7 @@ fn bindgen_test_layout_snd_pcm_mmap_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_mmap_status>())).tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_mmap_status, tstamp),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3997,9 +3911,7 @@ fn bindgen_test_layout_snd_pcm_mmap_status() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_mmap_status>())).suspended_state as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_mmap_status, suspended_state),
         32usize,
         concat!(
             "Offset of field: ",
@@ -4009,9 +3921,7 @@ fn bindgen_test_layout_snd_pcm_mmap_status() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_mmap_status>())).audio_tstamp as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_mmap_status, audio_tstamp),
         40usize,
         concat!(
             "Offset of field: ",
@@ -4040,7 +3950,7 @@ fn bindgen_test_layout_snd_pcm_mmap_control() {
         concat!("Alignment of ", stringify!(snd_pcm_mmap_control))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_mmap_control>())).appl_ptr as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_mmap_control, appl_ptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4050,7 +3960,7 @@ fn bindgen_test_layout_snd_pcm_mmap_control() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_mmap_control>())).avail_min as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_mmap_control, avail_min),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4087,9 +3997,7 @@ fn bindgen_test_layout_snd_pcm_sync_ptr__bindgen_ty_1() {
         concat!("Alignment of ", stringify!(snd_pcm_sync_ptr__bindgen_ty_1))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_sync_ptr__bindgen_ty_1>())).status as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_sync_ptr__bindgen_ty_1, status),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4099,9 +4007,7 @@ fn bindgen_test_layout_snd_pcm_sync_ptr__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_sync_ptr__bindgen_ty_1>())).reserved as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_sync_ptr__bindgen_ty_1, reserved),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4131,9 +4037,7 @@ fn bindgen_test_layout_snd_pcm_sync_ptr__bindgen_ty_2() {
         concat!("Alignment of ", stringify!(snd_pcm_sync_ptr__bindgen_ty_2))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_sync_ptr__bindgen_ty_2>())).control as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_sync_ptr__bindgen_ty_2, control),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4143,9 +4047,7 @@ fn bindgen_test_layout_snd_pcm_sync_ptr__bindgen_ty_2() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_pcm_sync_ptr__bindgen_ty_2>())).reserved as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_pcm_sync_ptr__bindgen_ty_2, reserved),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4168,7 +4070,7 @@ fn bindgen_test_layout_snd_pcm_sync_ptr() {
         concat!("Alignment of ", stringify!(snd_pcm_sync_ptr))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sync_ptr>())).flags as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sync_ptr, flags),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4178,7 +4080,7 @@ fn bindgen_test_layout_snd_pcm_sync_ptr() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sync_ptr>())).s as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sync_ptr, s),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4188,7 +4090,7 @@ fn bindgen_test_layout_snd_pcm_sync_ptr() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_pcm_sync_ptr>())).c as *const _ as usize },
+        ::core::mem::offset_of!(snd_pcm_sync_ptr, c),
         72usize,
         concat!(
             "Offset of field: ",
@@ -4218,7 +4120,7 @@ fn bindgen_test_layout_snd_xferi() {
         concat!("Alignment of ", stringify!(snd_xferi))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_xferi>())).result as *const _ as usize },
+        ::core::mem::offset_of!(snd_xferi, result),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4228,7 +4130,7 @@ fn bindgen_test_layout_snd_xferi() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_xferi>())).buf as *const _ as usize },
+        ::core::mem::offset_of!(snd_xferi, buf),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4238,7 +4140,7 @@ fn bindgen_test_layout_snd_xferi() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_xferi>())).frames as *const _ as usize },
+        ::core::mem::offset_of!(snd_xferi, frames),
         16usize,
         concat!(
             "Offset of field: ",
@@ -4268,7 +4170,7 @@ fn bindgen_test_layout_snd_xfern() {
         concat!("Alignment of ", stringify!(snd_xfern))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_xfern>())).result as *const _ as usize },
+        ::core::mem::offset_of!(snd_xfern, result),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4278,7 +4180,7 @@ fn bindgen_test_layout_snd_xfern() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_xfern>())).bufs as *const _ as usize },
+        ::core::mem::offset_of!(snd_xfern, bufs),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4288,7 +4190,7 @@ fn bindgen_test_layout_snd_xfern() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_xfern>())).frames as *const _ as usize },
+        ::core::mem::offset_of!(snd_xfern, frames),
         16usize,
         concat!(
             "Offset of field: ",
@@ -4374,7 +4276,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         concat!("Alignment of ", stringify!(snd_rawmidi_info))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).device as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, device),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4384,7 +4286,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).subdevice as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, subdevice),
         4usize,
         concat!(
             "Offset of field: ",
@@ -4394,7 +4296,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).stream as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, stream),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4404,7 +4306,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).card as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, card),
         12usize,
         concat!(
             "Offset of field: ",
@@ -4414,7 +4316,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).flags as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, flags),
         16usize,
         concat!(
             "Offset of field: ",
@@ -4424,7 +4326,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, id),
         20usize,
         concat!(
             "Offset of field: ",
@@ -4434,7 +4336,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).name as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, name),
         84usize,
         concat!(
             "Offset of field: ",
@@ -4444,7 +4346,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).subname as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, subname),
         164usize,
         concat!(
             "Offset of field: ",
@@ -4454,9 +4356,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_rawmidi_info>())).subdevices_count as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_rawmidi_info, subdevices_count),
         196usize,
         concat!(
             "Offset of field: ",
@@ -4466,9 +4366,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_rawmidi_info>())).subdevices_avail as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_rawmidi_info, subdevices_avail),
         200usize,
         concat!(
             "Offset of field: ",
@@ -4478,7 +4376,7 @@ fn bindgen_test_layout_snd_rawmidi_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_info>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_info, reserved),
         204usize,
         concat!(
             "Offset of field: ",
@@ -4510,7 +4408,7 @@ fn bindgen_test_layout_snd_rawmidi_params() {
         concat!("Alignment of ", stringify!(snd_rawmidi_params))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_params>())).stream as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_params, stream),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4520,7 +4418,7 @@ fn bindgen_test_layout_snd_rawmidi_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_params>())).buffer_size as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_params, buffer_size),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4530,7 +4428,7 @@ fn bindgen_test_layout_snd_rawmidi_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_params>())).avail_min as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_params, avail_min),
         16usize,
         concat!(
             "Offset of field: ",
@@ -4540,7 +4438,7 @@ fn bindgen_test_layout_snd_rawmidi_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_params>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_params, reserved),
         25usize,
         concat!(
             "Offset of field: ",
@@ -4597,7 +4495,7 @@ fn bindgen_test_layout_snd_rawmidi_status() {
         concat!("Alignment of ", stringify!(snd_rawmidi_status))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_status>())).stream as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_status, stream),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4607,7 +4505,7 @@ fn bindgen_test_layout_snd_rawmidi_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_status>())).tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_status, tstamp),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4617,7 +4515,7 @@ fn bindgen_test_layout_snd_rawmidi_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_status>())).avail as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_status, avail),
         24usize,
         concat!(
             "Offset of field: ",
@@ -4627,7 +4525,7 @@ fn bindgen_test_layout_snd_rawmidi_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_status>())).xruns as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_status, xruns),
         32usize,
         concat!(
             "Offset of field: ",
@@ -4637,7 +4535,7 @@ fn bindgen_test_layout_snd_rawmidi_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_rawmidi_status>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_rawmidi_status, reserved),
         40usize,
         concat!(
             "Offset of field: ",
@@ -4682,7 +4580,7 @@ fn bindgen_test_layout_snd_timer_id() {
         concat!("Alignment of ", stringify!(snd_timer_id))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_id>())).dev_class as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_id, dev_class),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4692,7 +4590,7 @@ fn bindgen_test_layout_snd_timer_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_id>())).dev_sclass as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_id, dev_sclass),
         4usize,
         concat!(
             "Offset of field: ",
@@ -4702,7 +4600,7 @@ fn bindgen_test_layout_snd_timer_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_id>())).card as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_id, card),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4712,7 +4610,7 @@ fn bindgen_test_layout_snd_timer_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_id>())).device as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_id, device),
         12usize,
         concat!(
             "Offset of field: ",
@@ -4722,7 +4620,7 @@ fn bindgen_test_layout_snd_timer_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_id>())).subdevice as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_id, subdevice),
         16usize,
         concat!(
             "Offset of field: ",
@@ -4760,7 +4658,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         concat!("Alignment of ", stringify!(snd_timer_ginfo))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).tid as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, tid),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4770,7 +4668,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).flags as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, flags),
         20usize,
         concat!(
             "Offset of field: ",
@@ -4780,7 +4678,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).card as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, card),
         24usize,
         concat!(
             "Offset of field: ",
@@ -4790,7 +4688,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, id),
         28usize,
         concat!(
             "Offset of field: ",
@@ -4800,7 +4698,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).name as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, name),
         92usize,
         concat!(
             "Offset of field: ",
@@ -4810,7 +4708,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).reserved0 as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, reserved0),
         176usize,
         concat!(
             "Offset of field: ",
@@ -4820,7 +4718,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).resolution as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, resolution),
         184usize,
         concat!(
             "Offset of field: ",
@@ -4830,7 +4728,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).resolution_min as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, resolution_min),
         192usize,
         concat!(
             "Offset of field: ",
@@ -4840,7 +4738,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).resolution_max as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, resolution_max),
         200usize,
         concat!(
             "Offset of field: ",
@@ -4850,7 +4748,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).clients as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, clients),
         208usize,
         concat!(
             "Offset of field: ",
@@ -4860,7 +4758,7 @@ fn bindgen_test_layout_snd_timer_ginfo() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_ginfo>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_ginfo, reserved),
         212usize,
         concat!(
             "Offset of field: ",
@@ -4891,7 +4789,7 @@ fn bindgen_test_layout_snd_timer_gparams() {
         concat!("Alignment of ", stringify!(snd_timer_gparams))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_gparams>())).tid as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_gparams, tid),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4901,7 +4799,7 @@ fn bindgen_test_layout_snd_timer_gparams() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_gparams>())).period_num as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_gparams, period_num),
         24usize,
         concat!(
             "Offset of field: ",
@@ -4911,7 +4809,7 @@ fn bindgen_test_layout_snd_timer_gparams() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_gparams>())).period_den as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_gparams, period_den),
         32usize,
         concat!(
             "Offset of field: ",
@@ -4921,7 +4819,7 @@ fn bindgen_test_layout_snd_timer_gparams() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_gparams>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_gparams, reserved),
         40usize,
         concat!(
             "Offset of field: ",
@@ -4953,7 +4851,7 @@ fn bindgen_test_layout_snd_timer_gstatus() {
         concat!("Alignment of ", stringify!(snd_timer_gstatus))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_gstatus>())).tid as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_gstatus, tid),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4963,7 +4861,7 @@ fn bindgen_test_layout_snd_timer_gstatus() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_gstatus>())).resolution as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_gstatus, resolution),
         24usize,
         concat!(
             "Offset of field: ",
@@ -4973,9 +4871,7 @@ fn bindgen_test_layout_snd_timer_gstatus() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_timer_gstatus>())).resolution_num as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_timer_gstatus, resolution_num),
         32usize,
         concat!(
             "Offset of field: ",
@@ -4985,9 +4881,7 @@ fn bindgen_test_layout_snd_timer_gstatus() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_timer_gstatus>())).resolution_den as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_timer_gstatus, resolution_den),
         40usize,
         concat!(
             "Offset of field: ",
@@ -4997,7 +4891,7 @@ fn bindgen_test_layout_snd_timer_gstatus() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_gstatus>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_gstatus, reserved),
         48usize,
         concat!(
             "Offset of field: ",
@@ -5026,7 +4920,7 @@ fn bindgen_test_layout_snd_timer_select() {
         concat!("Alignment of ", stringify!(snd_timer_select))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_select>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_select, id),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5036,7 +4930,7 @@ fn bindgen_test_layout_snd_timer_select() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_select>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_select, reserved),
         20usize,
         concat!(
             "Offset of field: ",
@@ -5070,7 +4964,7 @@ fn bindgen_test_layout_snd_timer_info() {
         concat!("Alignment of ", stringify!(snd_timer_info))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_info>())).flags as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_info, flags),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5080,7 +4974,7 @@ fn bindgen_test_layout_snd_timer_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_info>())).card as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_info, card),
         4usize,
         concat!(
             "Offset of field: ",
@@ -5090,7 +4984,7 @@ fn bindgen_test_layout_snd_timer_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_info>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_info, id),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5100,7 +4994,7 @@ fn bindgen_test_layout_snd_timer_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_info>())).name as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_info, name),
         72usize,
         concat!(
             "Offset of field: ",
@@ -5110,7 +5004,7 @@ fn bindgen_test_layout_snd_timer_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_info>())).reserved0 as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_info, reserved0),
         152usize,
         concat!(
             "Offset of field: ",
@@ -5120,7 +5014,7 @@ fn bindgen_test_layout_snd_timer_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_info>())).resolution as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_info, resolution),
         160usize,
         concat!(
             "Offset of field: ",
@@ -5130,7 +5024,7 @@ fn bindgen_test_layout_snd_timer_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_info>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_info, reserved),
         168usize,
         concat!(
             "Offset of field: ",
@@ -5163,7 +5057,7 @@ fn bindgen_test_layout_snd_timer_params() {
         concat!("Alignment of ", stringify!(snd_timer_params))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_params>())).flags as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_params, flags),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5173,7 +5067,7 @@ fn bindgen_test_layout_snd_timer_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_params>())).ticks as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_params, ticks),
         4usize,
         concat!(
             "Offset of field: ",
@@ -5183,7 +5077,7 @@ fn bindgen_test_layout_snd_timer_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_params>())).queue_size as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_params, queue_size),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5193,7 +5087,7 @@ fn bindgen_test_layout_snd_timer_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_params>())).reserved0 as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_params, reserved0),
         12usize,
         concat!(
             "Offset of field: ",
@@ -5203,7 +5097,7 @@ fn bindgen_test_layout_snd_timer_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_params>())).filter as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_params, filter),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5213,7 +5107,7 @@ fn bindgen_test_layout_snd_timer_params() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_params>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_params, reserved),
         20usize,
         concat!(
             "Offset of field: ",
@@ -5246,7 +5140,7 @@ fn bindgen_test_layout_snd_timer_status() {
         concat!("Alignment of ", stringify!(snd_timer_status))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_status>())).tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_status, tstamp),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5256,7 +5150,7 @@ fn bindgen_test_layout_snd_timer_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_status>())).resolution as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_status, resolution),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5266,7 +5160,7 @@ fn bindgen_test_layout_snd_timer_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_status>())).lost as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_status, lost),
         20usize,
         concat!(
             "Offset of field: ",
@@ -5276,7 +5170,7 @@ fn bindgen_test_layout_snd_timer_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_status>())).overrun as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_status, overrun),
         24usize,
         concat!(
             "Offset of field: ",
@@ -5286,7 +5180,7 @@ fn bindgen_test_layout_snd_timer_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_status>())).queue as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_status, queue),
         28usize,
         concat!(
             "Offset of field: ",
@@ -5296,7 +5190,7 @@ fn bindgen_test_layout_snd_timer_status() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_status>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_status, reserved),
         32usize,
         concat!(
             "Offset of field: ",
@@ -5325,7 +5219,7 @@ fn bindgen_test_layout_snd_timer_read() {
         concat!("Alignment of ", stringify!(snd_timer_read))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_read>())).resolution as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_read, resolution),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5335,7 +5229,7 @@ fn bindgen_test_layout_snd_timer_read() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_read>())).ticks as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_read, ticks),
         4usize,
         concat!(
             "Offset of field: ",
@@ -5381,7 +5275,7 @@ fn bindgen_test_layout_snd_timer_tread() {
         concat!("Alignment of ", stringify!(snd_timer_tread))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_tread>())).event as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_tread, event),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5391,7 +5285,7 @@ fn bindgen_test_layout_snd_timer_tread() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_tread>())).tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_tread, tstamp),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5401,7 +5295,7 @@ fn bindgen_test_layout_snd_timer_tread() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_timer_tread>())).val as *const _ as usize },
+        ::core::mem::offset_of!(snd_timer_tread, val),
         24usize,
         concat!(
             "Offset of field: ",
@@ -5437,7 +5331,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         concat!("Alignment of ", stringify!(snd_ctl_card_info))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).card as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, card),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5447,7 +5341,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).pad as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, pad),
         4usize,
         concat!(
             "Offset of field: ",
@@ -5457,7 +5351,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, id),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5467,7 +5361,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).driver as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, driver),
         24usize,
         concat!(
             "Offset of field: ",
@@ -5477,7 +5371,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).name as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, name),
         40usize,
         concat!(
             "Offset of field: ",
@@ -5487,7 +5381,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).longname as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, longname),
         72usize,
         concat!(
             "Offset of field: ",
@@ -5497,7 +5391,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).reserved_ as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, reserved_),
         152usize,
         concat!(
             "Offset of field: ",
@@ -5507,7 +5401,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).mixername as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, mixername),
         168usize,
         concat!(
             "Offset of field: ",
@@ -5517,7 +5411,7 @@ fn bindgen_test_layout_snd_ctl_card_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_card_info>())).components as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_card_info, components),
         248usize,
         concat!(
             "Offset of field: ",
@@ -5552,7 +5446,7 @@ fn bindgen_test_layout_snd_ctl_elem_id() {
         concat!("Alignment of ", stringify!(snd_ctl_elem_id))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_id>())).numid as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_id, numid),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5562,7 +5456,7 @@ fn bindgen_test_layout_snd_ctl_elem_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_id>())).iface as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_id, iface),
         4usize,
         concat!(
             "Offset of field: ",
@@ -5572,7 +5466,7 @@ fn bindgen_test_layout_snd_ctl_elem_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_id>())).device as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_id, device),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5582,7 +5476,7 @@ fn bindgen_test_layout_snd_ctl_elem_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_id>())).subdevice as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_id, subdevice),
         12usize,
         concat!(
             "Offset of field: ",
@@ -5592,7 +5486,7 @@ fn bindgen_test_layout_snd_ctl_elem_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_id>())).name as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_id, name),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5602,7 +5496,7 @@ fn bindgen_test_layout_snd_ctl_elem_id() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_id>())).index as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_id, index),
         60usize,
         concat!(
             "Offset of field: ",
@@ -5635,7 +5529,7 @@ fn bindgen_test_layout_snd_ctl_elem_list() {
         concat!("Alignment of ", stringify!(snd_ctl_elem_list))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_list>())).offset as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_list, offset),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5645,7 +5539,7 @@ fn bindgen_test_layout_snd_ctl_elem_list() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_list>())).space as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_list, space),
         4usize,
         concat!(
             "Offset of field: ",
@@ -5655,7 +5549,7 @@ fn bindgen_test_layout_snd_ctl_elem_list() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_list>())).used as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_list, used),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5665,7 +5559,7 @@ fn bindgen_test_layout_snd_ctl_elem_list() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_list>())).count as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_list, count),
         12usize,
         concat!(
             "Offset of field: ",
@@ -5675,7 +5569,7 @@ fn bindgen_test_layout_snd_ctl_elem_list() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_list>())).pids as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_list, pids),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5685,7 +5579,7 @@ fn bindgen_test_layout_snd_ctl_elem_list() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_list>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_list, reserved),
         24usize,
         concat!(
             "Offset of field: ",
@@ -5742,10 +5636,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1>())).min
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1, min),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5755,10 +5646,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1>())).max
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1, max),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5768,10 +5656,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1>())).step
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_1, step),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5807,10 +5692,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2>())).min
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2, min),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5820,10 +5702,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2>())).max
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2, max),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5833,10 +5712,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2>())).step
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_2, step),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5874,10 +5750,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3>())).items
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3, items),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5887,10 +5760,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3>())).item
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3, item),
         4usize,
         concat!(
             "Offset of field: ",
@@ -5900,10 +5770,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3>())).name
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3, name),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5913,10 +5780,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3>())).names_ptr
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3, names_ptr),
         72usize,
         concat!(
             "Offset of field: ",
@@ -5926,10 +5790,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3>())).names_length
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1__bindgen_ty_3, names_length),
         80usize,
         concat!(
             "Offset of field: ",
@@ -5952,9 +5813,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1() {
         concat!("Alignment of ", stringify!(snd_ctl_elem_info__bindgen_ty_1))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1>())).integer as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1, integer),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5964,10 +5823,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1>())).integer64 as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1, integer64),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5977,10 +5833,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1>())).enumerated as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1, enumerated),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5990,10 +5843,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_1>())).reserved as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_1, reserved),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6023,9 +5873,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_2() {
         concat!("Alignment of ", stringify!(snd_ctl_elem_info__bindgen_ty_2))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_2>())).d as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_2, d),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6035,9 +5883,7 @@ fn bindgen_test_layout_snd_ctl_elem_info__bindgen_ty_2() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_info__bindgen_ty_2>())).d_ptr as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_info__bindgen_ty_2, d_ptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6060,7 +5906,7 @@ fn bindgen_test_layout_snd_ctl_elem_info() {
         concat!("Alignment of ", stringify!(snd_ctl_elem_info))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_info>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_info, id),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6070,7 +5916,7 @@ fn bindgen_test_layout_snd_ctl_elem_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_info>())).type_ as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_info, type_),
         64usize,
         concat!(
             "Offset of field: ",
@@ -6080,7 +5926,7 @@ fn bindgen_test_layout_snd_ctl_elem_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_info>())).access as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_info, access),
         68usize,
         concat!(
             "Offset of field: ",
@@ -6090,7 +5936,7 @@ fn bindgen_test_layout_snd_ctl_elem_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_info>())).count as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_info, count),
         72usize,
         concat!(
             "Offset of field: ",
@@ -6100,7 +5946,7 @@ fn bindgen_test_layout_snd_ctl_elem_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_info>())).owner as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_info, owner),
         76usize,
         concat!(
             "Offset of field: ",
@@ -6110,7 +5956,7 @@ fn bindgen_test_layout_snd_ctl_elem_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_info>())).value as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_info, value),
         80usize,
         concat!(
             "Offset of field: ",
@@ -6120,7 +5966,7 @@ fn bindgen_test_layout_snd_ctl_elem_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_info>())).dimen as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_info, dimen),
         208usize,
         concat!(
             "Offset of field: ",
@@ -6130,7 +5976,7 @@ fn bindgen_test_layout_snd_ctl_elem_info() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_info>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_info, reserved),
         216usize,
         concat!(
             "Offset of field: ",
@@ -6185,10 +6031,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_1>())).value
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_1, value),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6198,10 +6041,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_1>())).value_ptr
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_1, value_ptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6237,10 +6077,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_2() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_2>())).value
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_2, value),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6250,10 +6087,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_2() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_2>())).value_ptr
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_2, value_ptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6289,10 +6123,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_3() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_3>())).item
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_3, item),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6302,10 +6133,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_3() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_3>())).item_ptr
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_3, item_ptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6341,10 +6169,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_4() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_4>())).data
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_4, data),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6354,10 +6179,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_4() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_4>())).data_ptr
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1__bindgen_ty_4, data_ptr),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6383,10 +6205,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1>())).integer as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1, integer),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6396,10 +6215,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1>())).integer64 as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1, integer64),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6409,10 +6225,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1>())).enumerated as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1, enumerated),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6422,9 +6235,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1>())).bytes as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1, bytes),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6434,9 +6245,7 @@ fn bindgen_test_layout_snd_ctl_elem_value__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_elem_value__bindgen_ty_1>())).iec958 as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_elem_value__bindgen_ty_1, iec958),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6459,7 +6268,7 @@ fn bindgen_test_layout_snd_ctl_elem_value() {
         concat!("Alignment of ", stringify!(snd_ctl_elem_value))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_value>())).id as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_value, id),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6469,7 +6278,7 @@ fn bindgen_test_layout_snd_ctl_elem_value() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_value>())).value as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_value, value),
         72usize,
         concat!(
             "Offset of field: ",
@@ -6479,7 +6288,7 @@ fn bindgen_test_layout_snd_ctl_elem_value() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_value>())).tstamp as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_value, tstamp),
         1096usize,
         concat!(
             "Offset of field: ",
@@ -6489,7 +6298,7 @@ fn bindgen_test_layout_snd_ctl_elem_value() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_elem_value>())).reserved as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_elem_value, reserved),
         1112usize,
         concat!(
             "Offset of field: ",
@@ -6544,7 +6353,7 @@ fn bindgen_test_layout_snd_ctl_tlv() {
         concat!("Alignment of ", stringify!(snd_ctl_tlv))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_tlv>())).numid as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_tlv, numid),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6554,7 +6363,7 @@ fn bindgen_test_layout_snd_ctl_tlv() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_tlv>())).length as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_tlv, length),
         4usize,
         concat!(
             "Offset of field: ",
@@ -6564,7 +6373,7 @@ fn bindgen_test_layout_snd_ctl_tlv() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_tlv>())).tlv as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_tlv, tlv),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6615,10 +6424,7 @@ fn bindgen_test_layout_snd_ctl_event__bindgen_ty_1__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_event__bindgen_ty_1__bindgen_ty_1>())).mask as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_event__bindgen_ty_1__bindgen_ty_1, mask),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6628,10 +6434,7 @@ fn bindgen_test_layout_snd_ctl_event__bindgen_ty_1__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_event__bindgen_ty_1__bindgen_ty_1>())).id as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_event__bindgen_ty_1__bindgen_ty_1, id),
         4usize,
         concat!(
             "Offset of field: ",
@@ -6654,9 +6457,7 @@ fn bindgen_test_layout_snd_ctl_event__bindgen_ty_1() {
         concat!("Alignment of ", stringify!(snd_ctl_event__bindgen_ty_1))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_event__bindgen_ty_1>())).elem as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_event__bindgen_ty_1, elem),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6666,9 +6467,7 @@ fn bindgen_test_layout_snd_ctl_event__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<snd_ctl_event__bindgen_ty_1>())).data8 as *const _ as usize
-        },
+        ::core::mem::offset_of!(snd_ctl_event__bindgen_ty_1, data8),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6691,7 +6490,7 @@ fn bindgen_test_layout_snd_ctl_event() {
         concat!("Alignment of ", stringify!(snd_ctl_event))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_event>())).type_ as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_event, type_),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6701,7 +6500,7 @@ fn bindgen_test_layout_snd_ctl_event() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<snd_ctl_event>())).data as *const _ as usize },
+        ::core::mem::offset_of!(snd_ctl_event, data),
         4usize,
         concat!(
             "Offset of field: ",
@@ -0,0 +1,263 @@
+//! HDMI / DisplayPort ELD (EDID-Like Data) - find out what the connected monitor or receiver can play
+//!
+//! HDMI and DisplayPort cards have a bytes element called "ELD" for each PCM device, which is empty
+//! when nothing is connected. The layout is described in the HD Audio specification, and the
+//! Short Audio Descriptors (SADs) are the same as in CEA-861.
+//!
+//! # Example
+//! ```
+//! use alsa::eld::{Eld, SadFormat};
+//!
+//! for card in alsa::card::Iter::new().map(|c| c.unwrap()) {
+//!     let ctl = alsa::Ctl::from_card(&card, false).unwrap();
+//!     for device in alsa::ctl::DeviceIter::new(&ctl) {
+//!         let Ok(Some(eld)) = Eld::from_ctl(&ctl, device as u32) else { continue };
+//!         let pcm = eld.sads.iter().find(|s| s.format == SadFormat::Lpcm);
+//!         println!("{} on hw:{},{}: max {:?} PCM channels", eld.monitor_name, card.get_index(), device, pcm.map(|s| s.channels));
+//!     }
+//! }
+//! ```
+
+use crate::ctl_int::{self, Ctl, ElemIface, ElemId, ElemType};
+use crate::error::*;
+use ::alloc::string::String;
+use ::alloc::vec::Vec;
+
+const ELD_HEADER_LEN: usize = 4;
+const ELD_FIXED_LEN: usize = 20;
+const ELD_MAX_MNL: usize = 16;
+const SAD_LEN: usize = 3;
+
+const SAD_RATES: [u32; 7] = [32000, 44100, 48000, 88200, 96000, 176400, 192000];
+const SAD_BIT_DEPTHS: [u32; 3] = [16, 20, 24];
+
+/// Audio format of a Short Audio Descriptor, see CEA-861 table "Audio Format Codes"
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SadFormat {
+    Lpcm,
+    Ac3,
+    Mpeg1,
+    Mp3,
+    Mpeg2,
+    Aac,
+    Dts,
+    Atrac,
+    /// One Bit Audio (DSD)
+    OneBitAudio,
+    EAc3,
+    DtsHd,
+    /// MLP / Dolby TrueHD
+    Mlp,
+    Dst,
+    WmaPro,
+    /// Extended format, with the extension type code
+    Extended(u8),
+    /// Reserved format code
+    Reserved(u8),
+}
+
+impl SadFormat {
+    fn from_sad(sad: &[u8]) -> SadFormat {
+        use self::SadFormat::*;
+        match (sad[0] >> 3) & 0xf {
+            1 => Lpcm, 2 => Ac3, 3 => Mpeg1, 4 => Mp3, 5 => Mpeg2, 6 => Aac, 7 => Dts,
+            8 => Atrac, 9 => OneBitAudio, 10 => EAc3, 11 => DtsHd, 12 => Mlp, 13 => Dst, 14 => WmaPro,
+            15 => Extended(sad[2] >> 3),
+            c => Reserved(c),
+        }
+    }
+
+    /// Formats where the third SAD byte is the maximum bit rate
+    fn has_max_bitrate(&self) -> bool {
+        use self::SadFormat::*;
+        matches!(self, Ac3 | Mpeg1 | Mp3 | Mpeg2 | Aac | Dts | Atrac)
+    }
+}
+
+/// A CEA-861 Short Audio Descriptor: one format the sink can play
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sad {
+    pub format: SadFormat,
+    /// The maximum number of channels
+    pub channels: u32,
+    /// Supported sample rates, in Hz
+    pub rates: Vec<u32>,
+    /// Supported sample sizes, in bits (only for `SadFormat::Lpcm`)
+    pub bit_depths: Vec<u32>,
+    /// Maximum bit rate in kbit/s (only for the compressed formats `Ac3` to `Atrac`)
+    pub max_bitrate: Option<u32>,
+    /// The three raw bytes
+    pub raw: [u8; 3],
+}
+
+impl Sad {
+    pub fn parse(raw: &[u8]) -> Result<Sad> {
+        if raw.len() < SAD_LEN { return Err(Error::new("Sad::parse", libc::EINVAL)) }
+        let format = SadFormat::from_sad(raw);
+        let rates = SAD_RATES.iter().enumerate().filter(|(i, _)| raw[1] & (1 << i) != 0).map(|(_, &r)| r).collect();
+        let bit_depths = if format != SadFormat::Lpcm { Vec::new() }
+            else { SAD_BIT_DEPTHS.iter().enumerate().filter(|(i, _)| raw[2] & (1 << i) != 0).map(|(_, &b)| b).collect() };
+        Ok(Sad {
+            channels: (raw[0] & 0x7) as u32 + 1,
+            rates,
+            bit_depths,
+            max_bitrate: if format.has_max_bitrate() { Some(raw[2] as u32 * 8) } else { None },
+            format,
+            raw: [raw[0], raw[1], raw[2]],
+        })
+    }
+}
+
+bitflags! {
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    /// CEA-861 Speaker Allocation, i e which speakers are present
+    pub struct Speakers: u8 {
+        /// Front left and front right
+        const FL_FR = 1 << 0;
+        const LFE = 1 << 1;
+        const FC = 1 << 2;
+        /// Rear left and rear right
+        const RL_RR = 1 << 3;
+        /// Rear center
+        const RC = 1 << 4;
+        /// Front left center and front right center
+        const FLC_FRC = 1 << 5;
+        /// Rear left center and rear right center
+        const RLC_RRC = 1 << 6;
+    }
+}
+
+impl Speakers {
+    /// The number of speakers, counting each pair as two
+    pub fn channels(&self) -> u32 {
+        let pairs = *self & (Speakers::FL_FR | Speakers::RL_RR | Speakers::FLC_FRC | Speakers::RLC_RRC);
+        self.bits().count_ones() + pairs.bits().count_ones()
+    }
+}
+
+/// How the sink is connected
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Connection {
+    Hdmi,
+    DisplayPort,
+    Reserved(u8),
+}
+
+/// Parsed ELD
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Eld {
+    /// ELD version, 2 for CEA-861-D and later
+    pub version: u8,
+    /// CEA EDID timing extension version
+    pub cea_edid_version: u8,
+    pub monitor_name: String,
+    /// Three letter PNP manufacturer id, e g "SAM"
+    pub manufacturer: String,
+    pub product_code: u16,
+    pub port_id: u64,
+    pub connection: Connection,
+    pub hdcp: bool,
+    /// Whether the sink supports ACP, ISRC1 and ISRC2 packets
+    pub supports_ai: bool,
+    /// Audio latency relative to the video, in milliseconds, if known
+    pub audio_sync_delay: Option<u32>,
+    pub speakers: Speakers,
+    pub sads: Vec<Sad>,
+}
+
+fn invalid() -> Error { Error::new("Eld::parse", libc::EINVAL) }
+
+impl Eld {
+    /// Parses the bytes of an "ELD" element.
+    pub fn parse(buf: &[u8]) -> Result<Eld> {
+        if buf.len() < ELD_HEADER_LEN { return Err(invalid()) }
+        let baseline_len = buf[2] as usize * 4;
+        let buf = buf.get(..ELD_HEADER_LEN + baseline_len).ok_or_else(invalid)?;
+        if buf.len() < ELD_FIXED_LEN { return Err(invalid()) }
+
+        let mnl = (buf[4] & 0x1f) as usize;
+        if mnl > ELD_MAX_MNL { return Err(invalid()) }
+        let name = buf.get(ELD_FIXED_LEN..ELD_FIXED_LEN + mnl).ok_or_else(invalid)?;
+        let name = name.split(|&b| b == 0).next().unwrap_or(&[]);
+
+        let sad_count = (buf[5] >> 4) as usize;
+        let sads = buf.get(ELD_FIXED_LEN + mnl..ELD_FIXED_LEN + mnl + sad_count * SAD_LEN).ok_or_else(invalid)?;
+
+        // The manufacturer id is copied from the EDID, where it is big endian
+        let m = u16::from_be_bytes([buf[16], buf[17]]);
+        let manufacturer = [10, 5, 0].iter().map(|s| (b'A' - 1 + ((m >> s) & 0x1f) as u8) as char).collect();
+
+        Ok(Eld {
+            version: buf[0] >> 3,
+            cea_edid_version: buf[4] >> 5,
+            monitor_name: String::from_utf8_lossy(name).into_owned(),
+            manufacturer,
+            product_code: u16::from_le_bytes([buf[18], buf[19]]),
+            port_id: u64::from_le_bytes(buf[8..16].try_into().unwrap()),
+            connection: match (buf[5] >> 2) & 3 { 0 => Connection::Hdmi, 1 => Connection::DisplayPort, c => Connection::Reserved(c) },
+            hdcp: buf[5] & 1 != 0,
+            supports_ai: buf[5] & 2 != 0,
+            audio_sync_delay: if buf[6] == 0 { None } else { Some(buf[6] as u32 * 2) },
+            speakers: Speakers::from_bits_truncate(buf[7]),
+            sads: sads.chunks(SAD_LEN).map(Sad::parse).collect::<Result<_>>()?,
+        })
+    }
+
+    /// Reads and parses the "ELD" element of a PCM device.
+    ///
+    /// Returns None if no monitor is connected, or if the monitor does not support audio.
+    pub fn from_ctl(ctl: &Ctl, device: u32) -> Result<Option<Eld>> {
+        let mut id = ElemId::new(ElemIface::PCM);
+        id.set_device(device);
+        id.set_name(c"ELD");
+        let info = ctl.elem_info(&id)?;
+        if info.get_type() != ElemType::Bytes { return Err(Error::unsupported("Eld::from_ctl")) }
+        if info.get_count() == 0 { return Ok(None) }
+        let mut v = ctl_int::elem_value_new(ElemType::Bytes, info.get_count())?;
+        v.set_id(&id);
+        ctl.elem_read(&mut v)?;
+        let buf = v.get_bytes().unwrap();
+        if buf.len() < ELD_HEADER_LEN || buf[2] == 0 { return Ok(None) }
+        Eld::parse(buf).map(Some)
+    }
+}
+
+#[test]
+fn eld_parse() {
+    let mut buf = ::alloc::vec![
+        0x10, 0x00, 0x08, 0x00, // version 2, baseline length 8 * 4 bytes
+        0x27, 0x24, 0x0a, 0x0b, // CEA version 1, 7 char name, 2 SADs, DP, HDCP, 20 ms, FL/FR + LFE + RL/RR
+        1, 0, 0, 0, 0, 0, 0, 0, // port id
+        0x4c, 0x2d, 0x34, 0x12, // "SAM", product 0x1234
+    ];
+    buf.extend_from_slice(b"MONITOR");
+    buf.extend_from_slice(&[0x0d, 0x7f, 0x07]); // LPCM, 6 channels, all rates, 16/20/24 bits
+    buf.extend_from_slice(&[0x15, 0x07, 0x50]); // AC-3, 6 channels, 32-48 kHz, 640 kbit/s
+    buf.extend_from_slice(&[0, 0, 0]); // padding up to the baseline length
+    let eld = Eld::parse(&buf).unwrap();
+    assert_eq!(eld.version, 2);
+    assert_eq!(eld.cea_edid_version, 1);
+    assert_eq!(eld.monitor_name, "MONITOR");
+    assert_eq!(eld.manufacturer, "SAM");
+    assert_eq!(eld.product_code, 0x1234);
+    assert_eq!(eld.port_id, 1);
+    assert_eq!(eld.connection, Connection::DisplayPort);
+    assert!(!eld.hdcp);
+    assert_eq!(eld.audio_sync_delay, Some(20));
+    assert_eq!(eld.speakers, Speakers::FL_FR | Speakers::LFE | Speakers::RL_RR);
+    assert_eq!(eld.speakers.channels(), 5);
+    assert_eq!(eld.sads.len(), 2);
+    assert_eq!(eld.sads[0].format, SadFormat::Lpcm);
+    assert_eq!(eld.sads[0].channels, 6);
+    assert_eq!(eld.sads[0].rates, &SAD_RATES);
+    assert_eq!(eld.sads[0].bit_depths, &[16, 20, 24]);
+    assert_eq!(eld.sads[0].max_bitrate, None);
+    assert_eq!(eld.sads[1].format, SadFormat::Ac3);
+    assert_eq!(eld.sads[1].rates, &[32000, 44100, 48000]);
+    assert_eq!(eld.sads[1].max_bitrate, Some(640));
+
+    assert!(Eld::parse(&buf[..30]).is_err());
+    buf[5] = 0x44; // 4 SADs do not fit
+    assert!(Eld::parse(&buf).is_err());
+}
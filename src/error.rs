@@ -27,7 +27,12 @@ macro_rules! acheck {
         let r = unsafe { alsa::$f( $($x),* ) };
         if r < 0 { Err(Error::new(stringify!($f), -r as ::libc::c_int)) }
         else { Ok(r) }
-    }}
+    }};
+    ($m: ident :: $f: ident ( $($x: expr),* ) ) => {{
+        let r = unsafe { $m::$f( $($x),* ) };
+        if r < 0 { Err(Error::new(stringify!($f), -r as ::libc::c_int)) }
+        else { Ok(r) }
+    }};
 }
 
 pub fn from_const<'a>(func: &'static str, s: *const c_char) -> Result<&'a str> {
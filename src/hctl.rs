@@ -33,13 +33,17 @@
 */
 
 
-use crate::{alsa, Card};
+use crate::{alsa, Card, Ctl};
 use core::ffi::CStr;
+use ::alloc::boxed::Box;
 use ::alloc::ffi::CString;
+use ::alloc::string::String;
+use ::alloc::vec::Vec;
 use super::error::*;
-use core::ptr;
+use core::{mem, ptr};
 use super::{ctl_int, poll};
-use libc::{c_short, c_uint, c_int, pollfd};
+use super::ctl_int::EventMask;
+use libc::{c_short, c_uint, c_int, c_void, pollfd};
 
 
 /// [snd_hctl_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___h_control.html) wrapper
@@ -49,7 +53,57 @@ pub struct HCtl(*mut alsa::snd_hctl_t);
 unsafe impl Send for HCtl {}
 
 impl Drop for HCtl {
-    fn drop(&mut self) { unsafe { alsa::snd_hctl_close(self.0) }; }
+    fn drop(&mut self) {
+        let cb = unsafe { alsa::snd_hctl_get_callback_private(self.0) } as *mut Callback;
+        // Closing removes all elements, which frees their callbacks
+        unsafe { alsa::snd_hctl_close(self.0) };
+        if !cb.is_null() { drop(unsafe { Box::from_raw(cb) }) }
+    }
+}
+
+type Callback = Box<dyn FnMut(&Elem, EventMask) + Send>;
+
+unsafe extern "C" fn hctl_callback(hctl: *mut alsa::snd_hctl_t, mask: c_uint, elem: *mut alsa::snd_hctl_elem_t) -> c_int {
+    let p = alsa::snd_hctl_get_callback_private(hctl) as *mut Callback;
+    if p.is_null() { return 0 }
+    // Take the callback out while it runs, in case it replaces itself
+    alsa::snd_hctl_set_callback_private(hctl, ptr::null_mut());
+    let mut f = Box::from_raw(p);
+    let h = mem::ManuallyDrop::new(HCtl(hctl));
+    f(&Elem(&h, elem), EventMask(mask));
+    if alsa::snd_hctl_get_callback_private(hctl).is_null() {
+        alsa::snd_hctl_set_callback_private(hctl, Box::into_raw(f) as *mut c_void);
+    }
+    0
+}
+
+unsafe extern "C" fn elem_callback(elem: *mut alsa::snd_hctl_elem_t, mask: c_uint) -> c_int {
+    let p = alsa::snd_hctl_elem_get_callback_private(elem) as *mut Callback;
+    if p.is_null() { return 0 }
+    alsa::snd_hctl_elem_set_callback_private(elem, ptr::null_mut());
+    let mut f = Box::from_raw(p);
+    let h = mem::ManuallyDrop::new(HCtl(alsa::snd_hctl_elem_get_hctl(elem)));
+    let mask = EventMask(mask);
+    f(&Elem(&h, elem), mask);
+    // The element is freed after a remove event, so drop the callback with it
+    if !mask.remove() && alsa::snd_hctl_elem_get_callback_private(elem).is_null() {
+        alsa::snd_hctl_elem_set_callback_private(elem, Box::into_raw(f) as *mut c_void);
+    }
+    0
+}
+
+/// Hands over the snd_hctl_t to someone else who will close it, e g a mixer.
+pub(crate) fn hctl_into_ptr(h: HCtl) -> *mut alsa::snd_hctl_t {
+    // The new owner installs its own callback, so ours would never be freed
+    let cb = unsafe { alsa::snd_hctl_get_callback_private(h.0) } as *mut Callback;
+    unsafe {
+        alsa::snd_hctl_set_callback(h.0, None);
+        alsa::snd_hctl_set_callback_private(h.0, ptr::null_mut());
+    }
+    if !cb.is_null() { drop(unsafe { Box::from_raw(cb) }) }
+    let p = h.0;
+    mem::forget(h);
+    p
 }
 
 impl HCtl {
@@ -73,6 +127,28 @@ impl HCtl {
         HCtl::new(&s, nonblock)
     }
 
+    /// Creates a HCtl on top of an already opened Ctl. You probably want to call `load` afterwards.
+    pub fn from_ctl(ctl: Ctl) -> Result<HCtl> {
+        let mut r = ptr::null_mut();
+        acheck!(snd_hctl_open_ctl(&mut r, ctl_int::ctl_ptr(&ctl)))?;
+        // The HCtl now owns the Ctl and closes it when dropped
+        mem::forget(ctl);
+        Ok(HCtl(r))
+    }
+
+    /// Sets a callback that is called with each element that is added, during `load` or `handle_events`.
+    ///
+    /// This is a good place to call `Elem::set_callback` for the new element.
+    pub fn set_callback<F: FnMut(&Elem, EventMask) + Send + 'static>(&self, f: F) {
+        let old = unsafe { alsa::snd_hctl_get_callback_private(self.0) } as *mut Callback;
+        let f: Callback = Box::new(f);
+        unsafe {
+            alsa::snd_hctl_set_callback_private(self.0, Box::into_raw(Box::new(f)) as *mut c_void);
+            alsa::snd_hctl_set_callback(self.0, Some(hctl_callback));
+        }
+        if !old.is_null() { drop(unsafe { Box::from_raw(old) }) }
+    }
+
     pub fn load(&self) -> Result<()> { acheck!(snd_hctl_load(self.0)).map(|_| ()) }
 
     pub fn elem_iter(&self) -> ElemIter<'_> { ElemIter(self, ptr::null_mut()) }
@@ -134,6 +210,11 @@ impl<'a> Elem<'a> {
         let v = ctl_int::elem_info_new()?;
         acheck!(snd_hctl_elem_info(self.1, ctl_int::elem_info_ptr(&v))).map(|_| v)
     }
+    /// Returns the item names of an element of type `ElemType::Enumerated`.
+    pub fn item_names(&self) -> Result<Vec<String>> {
+        let mut v = self.info()?;
+        ctl_int::elem_info_item_names(&mut v, |i| acheck!(snd_hctl_elem_info(self.1, ctl_int::elem_info_ptr(i))).map(|_| ()))
+    }
     pub fn read(&self) -> Result<ctl_int::ElemValue> {
         let i = self.info()?;
         let v = ctl_int::elem_value_new(i.get_type(), i.get_count())?;
@@ -143,6 +224,27 @@ impl<'a> Elem<'a> {
     pub fn write(&self, v: &ctl_int::ElemValue) -> Result<bool> {
         acheck!(snd_hctl_elem_write(self.1, ctl_int::elem_value_ptr(v))).map(|e| e > 0)
     }
+
+    /// Sets a callback that is called from `HCtl::handle_events` when the element changes.
+    ///
+    /// The last call has `EventMask::remove` set, and comes when the element is removed or the HCtl is closed.
+    pub fn set_callback<F: FnMut(&Elem, EventMask) + Send + 'static>(&self, f: F) {
+        let old = unsafe { alsa::snd_hctl_elem_get_callback_private(self.1) } as *mut Callback;
+        let f: Callback = Box::new(f);
+        unsafe {
+            alsa::snd_hctl_elem_set_callback_private(self.1, Box::into_raw(Box::new(f)) as *mut c_void);
+            alsa::snd_hctl_elem_set_callback(self.1, Some(elem_callback));
+        }
+        if !old.is_null() { drop(unsafe { Box::from_raw(old) }) }
+    }
+
+    /// Reads the TLV data of the element, e g its dB scale. Parse it with `tlv::Tlv::parse`.
+    pub fn tlv_read(&self) -> Result<Vec<u32>> {
+        let mut buf = ::alloc::vec![0 as c_uint; ctl_int::TLV_MAX_WORDS];
+        acheck!(snd_hctl_elem_tlv_read(self.1, buf.as_mut_ptr(), (buf.len() * 4) as c_uint))?;
+        buf.truncate(ctl_int::tlv_len(&buf, "snd_hctl_elem_tlv_read")?);
+        Ok(buf)
+    }
 }
 
 #[test]
@@ -180,3 +282,61 @@ fn print_jacks() {
         }
     }
 }
+
+#[test]
+fn hctl_callbacks() {
+    extern crate std;
+    use std::sync::{Arc, Mutex};
+    use crate::ctl::{ElemId, ElemIface, ElemType, ElemValue};
+    use crate::plugin::ctl_ext::{self, CtlExt, Access};
+
+    struct Volume { vol: i32, events: Vec<u32> }
+
+    impl CtlExt for Volume {
+        fn elem_count(&mut self) -> u32 { 2 }
+        fn elem_list(&mut self, offset: u32) -> Result<ElemId> {
+            let mut id = ElemId::new(ElemIface::Mixer);
+            id.set_name(if offset == 0 { c"Master Playback Volume" } else { c"PCM Playback Volume" });
+            Ok(id)
+        }
+        fn get_attribute(&mut self, _: u32) -> Result<(ElemType, Access, u32)> { Ok((ElemType::Integer, Access::READWRITE, 1)) }
+        fn get_integer_info(&mut self, _: u32) -> Result<(i32, i32, i32)> { Ok((0, 100, 1)) }
+        fn read(&mut self, _: u32, value: &mut ElemValue) -> Result<()> { value.set_integer(0, self.vol); Ok(()) }
+        fn write(&mut self, key: u32, value: &ElemValue) -> Result<bool> {
+            self.vol = value.get_integer(0).unwrap();
+            self.events.push(key);
+            Ok(true)
+        }
+        fn read_event(&mut self, _: &mut ctl_ext::Handle) -> Result<Option<(ElemId, EventMask)>> {
+            if self.events.is_empty() { return Ok(None) }
+            let key = self.events.remove(0);
+            Ok(Some((self.elem_list(key)?, EventMask(1))))
+        }
+    }
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let ctl = ctl_ext::create("rust_hctl_callbacks", true, Volume { vol: 0, events: Vec::new() }).unwrap();
+    let h = HCtl::from_ctl(ctl).unwrap();
+    let log2 = log.clone();
+    h.set_callback(move |elem, mask| {
+        assert!(mask.add());
+        if !elem.get_id().unwrap().get_name().unwrap().starts_with("Master") { return }
+        let log3 = log2.clone();
+        elem.set_callback(move |elem, mask| {
+            let value = if mask.remove() { None } else { elem.read().unwrap().get_integer(0) };
+            log3.lock().unwrap().push((mask, value));
+        });
+    });
+    h.load().unwrap();
+
+    let mut v = ElemValue::new(ElemType::Integer).unwrap();
+    for (i, name) in ["Master Playback Volume", "PCM Playback Volume"].iter().enumerate() {
+        let e = h.elem_iter().find(|e| e.get_id().unwrap().get_name().unwrap() == *name).unwrap();
+        v.set_integer(0, 50 + i as i32);
+        e.write(&v).unwrap();
+    }
+    assert_eq!(h.handle_events().unwrap(), 2);
+    assert_eq!(&*log.lock().unwrap(), &[(EventMask(1), Some(51))]);
+    drop(h);
+    assert_eq!(log.lock().unwrap()[1], (EventMask(!0), None));
+}
@@ -0,0 +1,272 @@
+//! Hardware dependent devices - driver specific I/O and DSP loading
+//!
+//! These devices (`/dev/snd/hwC*D*`) have no common protocol: what can be read, written or
+//! sent through `ioctl` depends on the driver, which is identified by `Info::get_iface`.
+
+use libc::{c_int, c_uint, c_void, size_t, c_short, pollfd};
+use super::ctl_int::{ctl_ptr, Ctl};
+use super::poll;
+use super::error::*;
+use crate::alsa;
+use ::alloc::ffi::CString;
+use core::ptr;
+use core::ffi::CStr;
+use core::marker::PhantomData;
+
+alsa_enum!(
+    /// [SND_HWDEP_IFACE_xxx](http://www.alsa-project.org/alsa-doc/alsa-lib/group___hw_dep.html) constants
+    Iface, ALL_IFACE[27],
+
+    Opl2 = SND_HWDEP_IFACE_OPL2,
+    Opl3 = SND_HWDEP_IFACE_OPL3,
+    Opl4 = SND_HWDEP_IFACE_OPL4,
+    SB16CSP = SND_HWDEP_IFACE_SB16CSP,
+    EMU10K1 = SND_HWDEP_IFACE_EMU10K1,
+    YSS225 = SND_HWDEP_IFACE_YSS225,
+    ICS2115 = SND_HWDEP_IFACE_ICS2115,
+    SScape = SND_HWDEP_IFACE_SSCAPE,
+    VX = SND_HWDEP_IFACE_VX,
+    MixArt = SND_HWDEP_IFACE_MIXART,
+    USX2Y = SND_HWDEP_IFACE_USX2Y,
+    EmuxWavetable = SND_HWDEP_IFACE_EMUX_WAVETABLE,
+    Bluetooth = SND_HWDEP_IFACE_BLUETOOTH,
+    USX2YPcm = SND_HWDEP_IFACE_USX2Y_PCM,
+    PCXHR = SND_HWDEP_IFACE_PCXHR,
+    SBRC = SND_HWDEP_IFACE_SB_RC,
+    HDA = SND_HWDEP_IFACE_HDA,
+    UsbStream = SND_HWDEP_IFACE_USB_STREAM,
+    FwDice = SND_HWDEP_IFACE_FW_DICE,
+    FwFireworks = SND_HWDEP_IFACE_FW_FIREWORKS,
+    FwBebob = SND_HWDEP_IFACE_FW_BEBOB,
+    FwOxfw = SND_HWDEP_IFACE_FW_OXFW,
+    FwDigi00x = SND_HWDEP_IFACE_FW_DIGI00X,
+    FwTascam = SND_HWDEP_IFACE_FW_TASCAM,
+    Line6 = SND_HWDEP_IFACE_LINE6,
+    FwMotu = SND_HWDEP_IFACE_FW_MOTU,
+    FwFireface = SND_HWDEP_IFACE_FW_FIREFACE,
+);
+
+/// Iterator over [Hwdep](http://www.alsa-project.org/alsa-doc/alsa-lib/group___hw_dep.html) devices of a card
+#[derive(Debug)]
+pub struct Iter<'a>(&'a Ctl, c_int);
+
+impl<'a> Iter<'a> {
+    pub fn new(c: &'a Ctl) -> Iter<'a> { Iter(c, -1) }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Result<Info>;
+    fn next(&mut self) -> Option<Result<Info>> {
+        match acheck!(snd_ctl_hwdep_next_device(ctl_ptr(self.0), &mut self.1)) {
+            Err(e) if e.errno() == libc::ENOTTY => return None,
+            Err(e) => return Some(Err(e)),
+            Ok(_) if self.1 == -1 => return None,
+            _ => {},
+        }
+        Some(Info::new().and_then(|i| {
+            unsafe { alsa::snd_hwdep_info_set_device(i.0, self.1 as c_uint) };
+            acheck!(snd_ctl_hwdep_info(ctl_ptr(self.0), i.0)).map(|_| i)
+        }))
+    }
+}
+
+/// [snd_hwdep_info_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___hw_dep.html) wrapper
+#[derive(Debug)]
+pub struct Info(*mut alsa::snd_hwdep_info_t);
+
+impl Drop for Info {
+    fn drop(&mut self) { unsafe { alsa::snd_hwdep_info_free(self.0) }; }
+}
+
+impl Info {
+    fn new() -> Result<Info> {
+        let mut p = ptr::null_mut();
+        acheck!(snd_hwdep_info_malloc(&mut p)).map(|_| Info(p))
+    }
+
+    pub fn get_device(&self) -> u32 { unsafe { alsa::snd_hwdep_info_get_device(self.0) as u32 }}
+    pub fn get_card(&self) -> i32 { unsafe { alsa::snd_hwdep_info_get_card(self.0) as i32 }}
+    pub fn get_id(&self) -> Result<&str> {
+        from_const("snd_hwdep_info_get_id", unsafe { alsa::snd_hwdep_info_get_id(self.0) })
+    }
+    pub fn get_name(&self) -> Result<&str> {
+        from_const("snd_hwdep_info_get_name", unsafe { alsa::snd_hwdep_info_get_name(self.0) })
+    }
+    pub fn get_iface(&self) -> Result<Iface> {
+        Iface::from_c_int(unsafe { alsa::snd_hwdep_info_get_iface(self.0) } as c_int, "snd_hwdep_info_get_iface")
+    }
+}
+
+/// [snd_hwdep_dsp_status_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___hw_dep.html) wrapper
+#[derive(Debug)]
+pub struct DspStatus(*mut alsa::snd_hwdep_dsp_status_t);
+
+impl Drop for DspStatus {
+    fn drop(&mut self) { unsafe { alsa::snd_hwdep_dsp_status_free(self.0) }; }
+}
+
+impl DspStatus {
+    fn new() -> Result<DspStatus> {
+        let mut p = ptr::null_mut();
+        acheck!(snd_hwdep_dsp_status_malloc(&mut p)).map(|_| DspStatus(p))
+    }
+
+    pub fn get_version(&self) -> u32 { unsafe { alsa::snd_hwdep_dsp_status_get_version(self.0) as u32 }}
+    pub fn get_id(&self) -> Result<&str> {
+        from_const("snd_hwdep_dsp_status_get_id", unsafe { alsa::snd_hwdep_dsp_status_get_id(self.0) })
+    }
+    pub fn get_num_dsps(&self) -> u32 { unsafe { alsa::snd_hwdep_dsp_status_get_num_dsps(self.0) as u32 }}
+    /// Bit mask of the DSPs that have been loaded
+    pub fn get_dsp_loaded(&self) -> u32 { unsafe { alsa::snd_hwdep_dsp_status_get_dsp_loaded(self.0) as u32 }}
+    pub fn get_chip_ready(&self) -> bool { unsafe { alsa::snd_hwdep_dsp_status_get_chip_ready(self.0) != 0 }}
+}
+
+/// [snd_hwdep_dsp_image_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___hw_dep.html) wrapper
+///
+/// Borrows the image data until it has been loaded with `Hwdep::dsp_load`.
+#[derive(Debug)]
+pub struct DspImage<'a>(*mut alsa::snd_hwdep_dsp_image_t, PhantomData<&'a [u8]>);
+
+impl<'a> Drop for DspImage<'a> {
+    fn drop(&mut self) { unsafe { alsa::snd_hwdep_dsp_image_free(self.0) }; }
+}
+
+impl<'a> DspImage<'a> {
+    /// Creates an image, to be loaded into DSP number `index`.
+    pub fn new(index: u32, name: &str, image: &'a [u8]) -> Result<DspImage<'a>> {
+        let mut p = ptr::null_mut();
+        let r = acheck!(snd_hwdep_dsp_image_malloc(&mut p)).map(|_| DspImage(p, PhantomData))?;
+        let name = CString::new(name).unwrap();
+        unsafe {
+            alsa::snd_hwdep_dsp_image_set_index(r.0, index as c_uint);
+            alsa::snd_hwdep_dsp_image_set_name(r.0, name.as_ptr());
+            // alsa-lib never writes to the image, the pointer is mut by mistake
+            alsa::snd_hwdep_dsp_image_set_image(r.0, image.as_ptr() as *mut c_void);
+            alsa::snd_hwdep_dsp_image_set_length(r.0, image.len());
+        }
+        Ok(r)
+    }
+
+    pub fn get_index(&self) -> u32 { unsafe { alsa::snd_hwdep_dsp_image_get_index(self.0) as u32 }}
+    pub fn get_name(&self) -> Result<&str> {
+        from_const("snd_hwdep_dsp_image_get_name", unsafe { alsa::snd_hwdep_dsp_image_get_name(self.0) })
+    }
+    pub fn get_length(&self) -> usize { unsafe { alsa::snd_hwdep_dsp_image_get_length(self.0) }}
+}
+
+/// [snd_hwdep_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___hw_dep.html) wrapper
+#[derive(Debug)]
+pub struct Hwdep(*mut alsa::snd_hwdep_t);
+
+unsafe impl Send for Hwdep {}
+
+impl Drop for Hwdep {
+    fn drop(&mut self) { unsafe { alsa::snd_hwdep_close(self.0) }; }
+}
+
+impl Hwdep {
+    /// Wrapper around open that takes a &str instead of a &CStr
+    pub fn new(name: &str, nonblock: bool) -> Result<Self> {
+        Self::open(&CString::new(name).unwrap(), nonblock)
+    }
+
+    /// Opens the device (e g "hw:0,0") for both reading and writing.
+    pub fn open(name: &CStr, nonblock: bool) -> Result<Hwdep> {
+        let mut h = ptr::null_mut();
+        // SND_HWDEP_OPEN_DUPLEX and SND_HWDEP_OPEN_NONBLOCK are the fcntl flags
+        let mode = libc::O_RDWR | if nonblock { libc::O_NONBLOCK } else { 0 };
+        acheck!(snd_hwdep_open(&mut h, name.as_ptr(), mode)).map(|_| Hwdep(h))
+    }
+
+    pub fn set_nonblock(&self, nonblock: bool) -> Result<()> {
+        acheck!(snd_hwdep_nonblock(self.0, if nonblock { 1 } else { 0 })).map(|_| ())
+    }
+
+    pub fn info(&self) -> Result<Info> {
+        Info::new().and_then(|i| acheck!(snd_hwdep_info(self.0, i.0)).map(|_| i))
+    }
+
+    pub fn dsp_status(&self) -> Result<DspStatus> {
+        DspStatus::new().and_then(|s| acheck!(snd_hwdep_dsp_status(self.0, s.0)).map(|_| s))
+    }
+
+    pub fn dsp_load(&self, image: &DspImage) -> Result<()> {
+        acheck!(snd_hwdep_dsp_load(self.0, image.0)).map(|_| ())
+    }
+
+    /// Sends a driver specific request to the device.
+    ///
+    /// # Safety
+    ///
+    /// `arg` must be valid for whatever the driver reads or writes for `request`.
+    pub unsafe fn ioctl(&self, request: c_uint, arg: *mut c_void) -> Result<c_int> {
+        acheck!(snd_hwdep_ioctl(self.0, request, arg))
+    }
+
+    pub fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let r = unsafe { alsa::snd_hwdep_read(self.0, buf.as_mut_ptr() as *mut c_void, buf.len() as size_t) };
+        from_code("snd_hwdep_read", r as c_int).map(|_| r as usize)
+    }
+
+    pub fn write(&self, buf: &[u8]) -> Result<usize> {
+        let r = unsafe { alsa::snd_hwdep_write(self.0, buf.as_ptr() as *const c_void, buf.len() as size_t) };
+        from_code("snd_hwdep_write", r as c_int).map(|_| r as usize)
+    }
+
+    pub fn io(&self) -> IO<'_> { IO(self) }
+}
+
+impl poll::Descriptors for Hwdep {
+    fn count(&self) -> usize {
+        unsafe { alsa::snd_hwdep_poll_descriptors_count(self.0) as usize }
+    }
+    fn fill(&self, p: &mut [pollfd]) -> Result<usize> {
+        let z = unsafe { alsa::snd_hwdep_poll_descriptors(self.0, p.as_mut_ptr(), p.len() as c_uint) };
+        from_code("snd_hwdep_poll_descriptors", z).map(|_| z as usize)
+    }
+    fn revents(&self, p: &[pollfd]) -> Result<poll::Flags> {
+        let mut r = 0;
+        let z = unsafe { alsa::snd_hwdep_poll_descriptors_revents(self.0, p.as_ptr() as *mut pollfd, p.len() as c_uint, &mut r) };
+        from_code("snd_hwdep_poll_descriptors_revents", z).map(|_| poll::Flags::from_bits_truncate(r as c_short))
+    }
+}
+
+/// Implements `std::io::Read` and `std::io::Write` for `Hwdep`
+#[derive(Debug)]
+pub struct IO<'a>(&'a Hwdep);
+
+#[cfg(feature = "std")]
+impl<'a> std::io::Read for IO<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf).map_err(|e| std::io::Error::from_raw_os_error(e.errno()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> std::io::Write for IO<'a> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf).map_err(|e| std::io::Error::from_raw_os_error(e.errno()))
+    }
+    fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+}
+
+#[test]
+fn print_hwdeps() {
+    extern crate std;
+
+    for a in super::card::Iter::new().map(|a| a.unwrap()) {
+        for b in Iter::new(&Ctl::from_card(&a, false).unwrap()).map(|b| b.unwrap()) {
+            std::println!("Hwdep {:?} (hw:{},{}) {} - {}", b.get_iface(), a.get_index(), b.get_device(),
+                 a.get_name().unwrap(), b.get_name().unwrap())
+        }
+    }
+}
+
+#[test]
+fn dsp_image() {
+    let data = [1u8, 2, 3, 4];
+    let image = DspImage::new(1, "firmware", &data).unwrap();
+    assert_eq!(image.get_index(), 1);
+    assert_eq!(image.get_name().unwrap(), "firmware");
+    assert_eq!(image.get_length(), 4);
+}
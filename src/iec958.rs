@@ -0,0 +1,275 @@
+//! IEC958 (S/PDIF, AES/EBU) channel status, as stored in elements of type `ElemType::IEC958`.
+//!
+//! The bit layout follows the IEC958_AESx_xxx definitions in alsa-lib's asoundef.h.
+
+use super::error::*;
+
+const AES0_PROFESSIONAL: u8 = 1 << 0;
+const AES0_NONAUDIO: u8 = 1 << 1;
+const AES0_PRO_EMPHASIS: u8 = 7 << 2;
+const AES0_PRO_EMPHASIS_NOTID: u8 = 0 << 2;
+const AES0_PRO_EMPHASIS_NONE: u8 = 1 << 2;
+const AES0_PRO_EMPHASIS_5015: u8 = 3 << 2;
+const AES0_PRO_EMPHASIS_CCITT: u8 = 7 << 2;
+const AES0_PRO_FS: u8 = 3 << 6;
+const AES0_CON_NOT_COPYRIGHT: u8 = 1 << 2;
+const AES0_CON_EMPHASIS: u8 = 7 << 3;
+const AES0_CON_EMPHASIS_5015: u8 = 1 << 3;
+const AES1_CON_CATEGORY: u8 = 0x7f;
+const AES1_CON_ORIGINAL: u8 = 1 << 7;
+const AES2_PRO_SBITS: u8 = 7;
+const AES2_PRO_SBITS_20: u8 = 2;
+const AES2_PRO_SBITS_24: u8 = 4;
+const AES2_PRO_WORDLEN: u8 = 7 << 3;
+const AES3_CON_FS: u8 = 0x0f;
+const AES4_CON_MAX_WORDLEN_24: u8 = 1 << 0;
+const AES4_CON_WORDLEN: u8 = 7 << 1;
+
+// Sample rate codes, (rate, AES0_PRO_FS value)
+const PRO_RATES: [(u32, u8); 3] = [(44100, 1 << 6), (48000, 2 << 6), (32000, 3 << 6)];
+
+// Sample rate codes, (rate, AES3_CON_FS value)
+const CON_RATES: [(u32, u8); 10] = [(44100, 0), (48000, 2), (32000, 3), (22050, 4), (24000, 6),
+    (88200, 8), (768000, 9), (96000, 10), (176400, 12), (192000, 14)];
+
+// Word length codes, (bits if max is 20, bits if max is 24, AES4_CON_WORDLEN value)
+const CON_WORDLENS: [(u32, u32, u8); 5] = [(16, 20, 1 << 1), (18, 22, 2 << 1), (19, 23, 4 << 1), (20, 24, 5 << 1), (17, 21, 6 << 1)];
+
+// Word length codes, (bits if max is 20, bits if max is 24, AES2_PRO_WORDLEN value)
+const PRO_WORDLENS: [(u32, u32, u8); 4] = [(18, 22, 2 << 3), (19, 23, 4 << 3), (20, 24, 5 << 3), (16, 20, 6 << 3)];
+
+/// Pre-emphasis of the audio
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Iec958Emphasis {
+    /// Not indicated (professional mode only)
+    NotIndicated,
+    None,
+    /// 50/15 µs
+    Us50_15,
+    /// CCITT J.17 (professional mode only)
+    CcittJ17,
+}
+
+/// The 24 bytes of IEC958 channel status
+///
+/// The first bit decides between consumer (S/PDIF) and professional (AES/EBU) format, and
+/// the other fields are located differently in the two formats.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Iec958Status(pub [u8; 24]);
+
+impl Default for Iec958Status {
+    fn default() -> Self { Iec958Status::consumer() }
+}
+
+impl Iec958Status {
+    /// "General" category, used by most PCs
+    pub const CATEGORY_GENERAL: u8 = 0x00;
+    /// CD player (IEC 908)
+    pub const CATEGORY_CD: u8 = 0x01;
+    /// PCM encoder/decoder
+    pub const CATEGORY_PCM_CODER: u8 = 0x02;
+    /// Digital audio tape
+    pub const CATEGORY_DAT: u8 = 0x03;
+    pub const CATEGORY_MIXER: u8 = 0x12;
+    pub const CATEGORY_DVD: u8 = 0x19;
+    pub const CATEGORY_MINI_DISC: u8 = 0x49;
+
+    /// Consumer format, PCM audio, 48 kHz, copyright asserted, category "General".
+    pub fn consumer() -> Self {
+        let mut s = Iec958Status([0; 24]);
+        s.0[3] = 2;
+        s
+    }
+
+    /// Professional format, PCM audio, 48 kHz, no emphasis.
+    pub fn professional() -> Self {
+        let mut s = Iec958Status([0; 24]);
+        s.0[0] = AES0_PROFESSIONAL | AES0_PRO_EMPHASIS_NONE | (2 << 6);
+        s
+    }
+
+    pub fn is_professional(&self) -> bool { self.0[0] & AES0_PROFESSIONAL != 0 }
+
+    /// True if the stream is not linear PCM, e g AC-3 or DTS passthrough.
+    pub fn is_non_audio(&self) -> bool { self.0[0] & AES0_NONAUDIO != 0 }
+
+    pub fn set_non_audio(&mut self, v: bool) {
+        if v { self.0[0] |= AES0_NONAUDIO } else { self.0[0] &= !AES0_NONAUDIO }
+    }
+
+    /// Returns the sample rate in Hz, or None if not indicated.
+    pub fn rate(&self) -> Option<u32> {
+        if self.is_professional() {
+            PRO_RATES.iter().find(|r| r.1 == self.0[0] & AES0_PRO_FS).map(|r| r.0)
+        } else {
+            CON_RATES.iter().find(|r| r.1 == self.0[3] & AES3_CON_FS).map(|r| r.0)
+        }
+    }
+
+    /// Fails with EINVAL if the rate cannot be represented in the current format.
+    pub fn set_rate(&mut self, rate: u32) -> Result<()> {
+        if self.is_professional() {
+            let r = PRO_RATES.iter().find(|r| r.0 == rate).ok_or_else(|| Error::new("Iec958Status::set_rate", libc::EINVAL))?;
+            self.0[0] = (self.0[0] & !AES0_PRO_FS) | r.1;
+        } else {
+            let r = CON_RATES.iter().find(|r| r.0 == rate).ok_or_else(|| Error::new("Iec958Status::set_rate", libc::EINVAL))?;
+            self.0[3] = (self.0[3] & !AES3_CON_FS) | r.1;
+        }
+        Ok(())
+    }
+
+    /// Returns the sample word length in bits, or None if not indicated.
+    pub fn word_length(&self) -> Option<u32> {
+        if self.is_professional() {
+            let max24 = match self.0[2] & AES2_PRO_SBITS { AES2_PRO_SBITS_20 => false, AES2_PRO_SBITS_24 => true, _ => return None };
+            PRO_WORDLENS.iter().find(|w| w.2 == self.0[2] & AES2_PRO_WORDLEN).map(|w| if max24 { w.1 } else { w.0 })
+        } else {
+            let max24 = self.0[4] & AES4_CON_MAX_WORDLEN_24 != 0;
+            CON_WORDLENS.iter().find(|w| w.2 == self.0[4] & AES4_CON_WORDLEN).map(|w| if max24 { w.1 } else { w.0 })
+        }
+    }
+
+    /// Fails with EINVAL if the word length cannot be represented in the current format.
+    pub fn set_word_length(&mut self, bits: u32) -> Result<()> {
+        let (table, wordlen_mask, max_mask): (&[(u32, u32, u8)], u8, u8) = if self.is_professional() {
+            (&PRO_WORDLENS, AES2_PRO_WORDLEN, AES2_PRO_SBITS)
+        } else {
+            (&CON_WORDLENS, AES4_CON_WORDLEN, AES4_CON_MAX_WORDLEN_24)
+        };
+        let (w, max24) = table.iter().find(|w| w.0 == bits).map(|w| (w, false))
+            .or_else(|| table.iter().find(|w| w.1 == bits).map(|w| (w, true)))
+            .ok_or_else(|| Error::new("Iec958Status::set_word_length", libc::EINVAL))?;
+        let max = match (self.is_professional(), max24) {
+            (true, true) => AES2_PRO_SBITS_24,
+            (true, false) => AES2_PRO_SBITS_20,
+            (false, true) => AES4_CON_MAX_WORDLEN_24,
+            (false, false) => 0,
+        };
+        let idx = if self.is_professional() { 2 } else { 4 };
+        self.0[idx] = (self.0[idx] & !(wordlen_mask | max_mask)) | w.2 | max;
+        Ok(())
+    }
+
+    /// Returns whether the content is copyrighted. Professional format has no copyright bit, so this returns None.
+    pub fn copyright(&self) -> Option<bool> {
+        if self.is_professional() { None } else { Some(self.0[0] & AES0_CON_NOT_COPYRIGHT == 0) }
+    }
+
+    pub fn set_copyright(&mut self, v: bool) -> Result<()> {
+        if self.is_professional() { return Err(Error::new("Iec958Status::set_copyright", libc::EINVAL)) }
+        if v { self.0[0] &= !AES0_CON_NOT_COPYRIGHT } else { self.0[0] |= AES0_CON_NOT_COPYRIGHT }
+        Ok(())
+    }
+
+    /// Returns None for emphasis values that are reserved.
+    pub fn emphasis(&self) -> Option<Iec958Emphasis> {
+        if self.is_professional() {
+            match self.0[0] & AES0_PRO_EMPHASIS {
+                AES0_PRO_EMPHASIS_NOTID => Some(Iec958Emphasis::NotIndicated),
+                AES0_PRO_EMPHASIS_NONE => Some(Iec958Emphasis::None),
+                AES0_PRO_EMPHASIS_5015 => Some(Iec958Emphasis::Us50_15),
+                AES0_PRO_EMPHASIS_CCITT => Some(Iec958Emphasis::CcittJ17),
+                _ => None,
+            }
+        } else {
+            match self.0[0] & AES0_CON_EMPHASIS {
+                0 => Some(Iec958Emphasis::None),
+                AES0_CON_EMPHASIS_5015 => Some(Iec958Emphasis::Us50_15),
+                _ => None,
+            }
+        }
+    }
+
+    /// Fails with EINVAL if the emphasis cannot be represented in the current format.
+    pub fn set_emphasis(&mut self, e: Iec958Emphasis) -> Result<()> {
+        let (mask, v) = match (self.is_professional(), e) {
+            (true, Iec958Emphasis::NotIndicated) => (AES0_PRO_EMPHASIS, AES0_PRO_EMPHASIS_NOTID),
+            (true, Iec958Emphasis::None) => (AES0_PRO_EMPHASIS, AES0_PRO_EMPHASIS_NONE),
+            (true, Iec958Emphasis::Us50_15) => (AES0_PRO_EMPHASIS, AES0_PRO_EMPHASIS_5015),
+            (true, Iec958Emphasis::CcittJ17) => (AES0_PRO_EMPHASIS, AES0_PRO_EMPHASIS_CCITT),
+            (false, Iec958Emphasis::None) => (AES0_CON_EMPHASIS, 0),
+            (false, Iec958Emphasis::Us50_15) => (AES0_CON_EMPHASIS, AES0_CON_EMPHASIS_5015),
+            (false, _) => return Err(Error::new("Iec958Status::set_emphasis", libc::EINVAL)),
+        };
+        self.0[0] = (self.0[0] & !mask) | v;
+        Ok(())
+    }
+
+    /// Returns the category code (see the CATEGORY_xxx constants), or None in professional format.
+    pub fn category(&self) -> Option<u8> {
+        if self.is_professional() { None } else { Some(self.0[1] & AES1_CON_CATEGORY) }
+    }
+
+    pub fn set_category(&mut self, category: u8) -> Result<()> {
+        if self.is_professional() || category & !AES1_CON_CATEGORY != 0 {
+            return Err(Error::new("Iec958Status::set_category", libc::EINVAL))
+        }
+        self.0[1] = (self.0[1] & !AES1_CON_CATEGORY) | category;
+        Ok(())
+    }
+
+    /// Returns the "L" bit, which together with the category tells whether this is an original or a copy.
+    pub fn original(&self) -> Option<bool> {
+        if self.is_professional() { None } else { Some(self.0[1] & AES1_CON_ORIGINAL != 0) }
+    }
+}
+
+#[test]
+fn iec958_consumer() {
+    let mut s = Iec958Status::consumer();
+    assert!(!s.is_professional());
+    assert_eq!(s.rate(), Some(48000));
+    assert_eq!(s.word_length(), None);
+    assert_eq!(s.copyright(), Some(true));
+    assert_eq!(s.emphasis(), Some(Iec958Emphasis::None));
+    assert_eq!(s.category(), Some(Iec958Status::CATEGORY_GENERAL));
+
+    s.set_rate(44100).unwrap();
+    s.set_word_length(24).unwrap();
+    s.set_copyright(false).unwrap();
+    s.set_non_audio(true);
+    s.set_category(Iec958Status::CATEGORY_PCM_CODER).unwrap();
+    assert_eq!(&s.0[..5], &[0x06, 0x02, 0x00, 0x00, 0x0b]);
+    assert_eq!(s.rate(), Some(44100));
+    assert_eq!(s.word_length(), Some(24));
+    assert!(s.is_non_audio());
+
+    s.set_word_length(16).unwrap();
+    assert_eq!(s.0[4], 0x02);
+    assert!(s.set_rate(12345).is_err());
+    assert!(s.set_word_length(32).is_err());
+    assert!(s.set_emphasis(Iec958Emphasis::CcittJ17).is_err());
+    assert!(s.set_category(0x80).is_err());
+}
+
+#[test]
+fn iec958_professional() {
+    let mut s = Iec958Status::professional();
+    assert!(s.is_professional());
+    assert_eq!(s.rate(), Some(48000));
+    assert_eq!(s.emphasis(), Some(Iec958Emphasis::None));
+    assert_eq!(s.copyright(), None);
+    assert_eq!(s.category(), None);
+
+    s.set_rate(32000).unwrap();
+    s.set_emphasis(Iec958Emphasis::CcittJ17).unwrap();
+    s.set_word_length(24).unwrap();
+    assert_eq!(&s.0[..3], &[0xdd, 0x00, 0x2c]);
+    assert_eq!(s.word_length(), Some(24));
+    s.set_word_length(16).unwrap();
+    assert_eq!(s.0[2], 0x32);
+    assert!(s.set_rate(96000).is_err());
+    assert!(s.set_copyright(true).is_err());
+}
+
+#[test]
+fn iec958_elem_value() {
+    use crate::ctl::{ElemType, ElemValue};
+    let mut v = ElemValue::new(ElemType::IEC958).unwrap();
+    assert_eq!(v.get_iec958(), Some(Iec958Status([0; 24])));
+    let mut s = Iec958Status::consumer();
+    s.set_rate(96000).unwrap();
+    v.set_iec958(&s).unwrap();
+    assert_eq!(v.get_iec958().unwrap().rate(), Some(96000));
+    assert_eq!(ElemValue::new(ElemType::Bytes).unwrap().get_iec958(), None);
+}
@@ -0,0 +1,269 @@
+//! Jack detection - find out when headphones, microphones or HDMI sinks are plugged in
+//!
+//! Drivers expose jacks as boolean elements of interface `ElemIface::Card`, named e g
+//! "Headphone Jack", "Mic Jack" or "HDMI/DP,pcm=3 Jack". Jacks that cannot detect anything are
+//! named "... Phantom Jack", and are always reported as plugged in.
+//!
+//! # Example
+//! Print the jacks of all cards, then wait for changes
+//!
+//! ```no_run
+//! use alsa::jack::JackMonitor;
+//! use alsa::poll::Descriptors;
+//!
+//! let mut monitors = vec!();
+//! for card in alsa::card::Iter::new().map(|c| c.unwrap()) {
+//!     let m = JackMonitor::from_card(&card).unwrap();
+//!     for j in m.jacks() { println!("{}: {} is {}", card.get_index(), j.name, if j.plugged { "plugged in" } else { "unplugged" }) }
+//!     monitors.push(m);
+//! }
+//! loop {
+//!     let mut fds = vec!();
+//!     for m in &monitors { fds.extend(m.get().unwrap()); }
+//!     alsa::poll::poll(&mut fds, -1).unwrap();
+//!     for m in &mut monitors {
+//!         for ev in m.read().unwrap() { println!("{:?}", ev) }
+//!     }
+//! }
+//! ```
+
+use crate::{Card, poll};
+use crate::ctl_int::{ElemId, ElemIface, ElemType};
+use crate::hctl::{Elem, HCtl};
+use crate::error::*;
+use ::alloc::string::{String, ToString};
+use ::alloc::sync::Arc;
+use ::alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use libc::pollfd;
+
+/// A jack, and whether something is plugged into it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Jack {
+    /// The element name without the " Jack" or " Phantom Jack" suffix, e g "Headphone" or "HDMI/DP,pcm=3".
+    pub name: String,
+    /// Distinguishes jacks with the same name, e g a front and a rear "Mic".
+    pub index: u32,
+    /// The jack cannot detect anything, so `plugged` is always true.
+    pub phantom: bool,
+    pub plugged: bool,
+    /// The id of the underlying element.
+    pub id: ElemId,
+}
+
+impl Jack {
+    fn from_elem(e: &Elem) -> Option<Jack> {
+        let id = e.get_id().ok()?;
+        if id.get_interface() != ElemIface::Card { return None }
+        let full = id.get_name().ok()?;
+        let (name, phantom) = match full.strip_suffix(" Phantom Jack") {
+            Some(name) => (name, true),
+            None => (full.strip_suffix(" Jack")?, false),
+        };
+        if e.info().ok()?.get_type() != ElemType::Boolean { return None }
+        let plugged = e.read().ok()?.get_boolean(0)?;
+        Some(Jack { name: name.to_string(), index: id.get_index(), phantom, plugged, id })
+    }
+}
+
+/// A change reported by `JackMonitor::read`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JackEvent {
+    /// Something was plugged into or unplugged from the jack.
+    Changed(Jack),
+    /// The driver added a jack.
+    Added(Jack),
+    /// The driver removed a jack. Its state is as of the last call to `read`.
+    Removed(Jack),
+}
+
+// Set by the element callbacks, and cleared by `read`
+const CHANGED: u8 = 1;
+const REMOVED: u8 = 2;
+
+fn watch(e: &Elem) -> Arc<AtomicU8> {
+    let flags = Arc::new(AtomicU8::new(0));
+    let f = flags.clone();
+    e.set_callback(move |_, mask| {
+        f.fetch_or(if mask.remove() { REMOVED } else if mask.value() { CHANGED } else { 0 }, Ordering::Relaxed);
+    });
+    flags
+}
+
+/// Keeps track of the jacks of a card
+///
+/// Poll it using the `poll::Descriptors` trait, then call `read` to find out what changed.
+#[derive(Debug)]
+pub struct JackMonitor {
+    hctl: HCtl,
+    jacks: Vec<Jack>,
+    /// The change flags of each jack, in the same order as `jacks`
+    flags: Vec<Arc<AtomicU8>>,
+    /// Set by the HCtl callback when elements are added
+    added: Arc<AtomicBool>,
+}
+
+impl JackMonitor {
+    /// Opens the control device of a card in non-blocking mode.
+    pub fn from_card(c: &Card) -> Result<JackMonitor> {
+        JackMonitor::from_hctl(HCtl::from_card(c, true)?)
+    }
+
+    /// Monitors an already opened `HCtl`, which should be in non-blocking mode.
+    ///
+    /// The `HCtl` is loaded if that has not already been done. The monitor sets its own callbacks on the
+    /// `HCtl` and on the jack elements, replacing any that were set before.
+    pub fn from_hctl(hctl: HCtl) -> Result<JackMonitor> {
+        if hctl.elem_iter().next().is_none() { hctl.load()? }
+        let added = Arc::new(AtomicBool::new(false));
+        let a = added.clone();
+        hctl.set_callback(move |_, mask| if mask.add() { a.store(true, Ordering::Relaxed) });
+        let mut m = JackMonitor { hctl, jacks: Vec::new(), flags: Vec::new(), added };
+        m.find_new();
+        Ok(m)
+    }
+
+    /// Starts watching jack elements that are not in `jacks` yet, and returns them.
+    fn find_new(&mut self) -> Vec<Jack> {
+        let mut r = Vec::new();
+        for e in self.hctl.elem_iter() {
+            let Some(j) = Jack::from_elem(&e) else { continue };
+            if self.jacks.iter().any(|k| k.id == j.id) { continue }
+            self.flags.push(watch(&e));
+            self.jacks.push(j.clone());
+            r.push(j);
+        }
+        r
+    }
+
+    /// All jacks of the card, with their state as of the last call to `read`.
+    pub fn jacks(&self) -> &[Jack] { &self.jacks }
+
+    /// Returns whether the first jack with this name (e g "Headphone") is plugged in, or None if there is no such jack.
+    pub fn is_plugged(&self, name: &str) -> Option<bool> {
+        self.jacks.iter().find(|j| j.name == name).map(|j| j.plugged)
+    }
+
+    /// Handles pending events, and returns what happened to the jacks since the last call.
+    ///
+    /// Only the jacks the driver sent events for are read. A jack that was plugged in and unplugged
+    /// again in between is not returned.
+    pub fn read(&mut self) -> Result<Vec<JackEvent>> {
+        self.hctl.handle_events()?;
+        let mut r = Vec::new();
+        let mut i = 0;
+        while i < self.jacks.len() {
+            let flags = self.flags[i].swap(0, Ordering::Relaxed);
+            if flags & REMOVED != 0 {
+                self.flags.remove(i);
+                r.push(JackEvent::Removed(self.jacks.remove(i)));
+                continue;
+            }
+            let j = &mut self.jacks[i];
+            i += 1;
+            if flags & CHANGED == 0 { continue }
+            let Some(e) = self.hctl.find_elem(&j.id) else { continue };
+            let plugged = e.read()?.get_boolean(0).unwrap_or(j.plugged);
+            if plugged != j.plugged {
+                j.plugged = plugged;
+                r.push(JackEvent::Changed(j.clone()));
+            }
+        }
+        if self.added.swap(false, Ordering::Relaxed) {
+            r.extend(self.find_new().into_iter().map(JackEvent::Added));
+        }
+        Ok(r)
+    }
+
+    /// Setting callbacks on the `HCtl` or its elements stops the monitor from seeing changes.
+    pub fn hctl(&self) -> &HCtl { &self.hctl }
+}
+
+impl poll::Descriptors for JackMonitor {
+    fn count(&self) -> usize { self.hctl.count() }
+    fn fill(&self, p: &mut [pollfd]) -> Result<usize> { self.hctl.fill(p) }
+    fn revents(&self, p: &[pollfd]) -> Result<poll::Flags> { self.hctl.revents(p) }
+}
+
+#[test]
+fn jack_monitor() {
+    extern crate std;
+    use std::sync::Mutex;
+    use crate::ctl_int::{ElemValue, EventMask};
+    use crate::plugin::ctl_ext::{self, Access, CtlExt, Handle};
+
+    const NAMES: [&core::ffi::CStr; 5] = [c"Headphone Jack", c"Mic Jack", c"Line Phantom Jack", c"Headphone Playback Switch",
+        c"HDMI/DP,pcm=3 Jack"];
+
+    // Plugged state and presence of each element, and pending events
+    #[derive(Default)]
+    struct State { plugged: [bool; 5], present: [bool; 5], events: Vec<(u32, EventMask)> }
+    struct Jacks(Arc<Mutex<State>>);
+
+    impl Jacks {
+        fn id(key: u32) -> ElemId {
+            let mut id = ElemId::new(if key == 3 { ElemIface::Mixer } else { ElemIface::Card });
+            id.set_name(NAMES[key as usize]);
+            id
+        }
+        fn keys(&self) -> Vec<u32> {
+            let s = self.0.lock().unwrap();
+            (0..NAMES.len() as u32).filter(|&k| s.present[k as usize]).collect()
+        }
+    }
+
+    impl CtlExt for Jacks {
+        fn elem_count(&mut self) -> u32 { self.keys().len() as u32 }
+        fn elem_list(&mut self, offset: u32) -> Result<ElemId> { Ok(Self::id(self.keys()[offset as usize])) }
+        fn find_elem(&mut self, id: &ElemId) -> Option<u32> {
+            self.keys().into_iter().find(|&k| id.get_name().ok() == NAMES[k as usize].to_str().ok())
+        }
+        fn get_attribute(&mut self, key: u32) -> Result<(ElemType, Access, u32)> {
+            Ok((ElemType::Boolean, if key == 3 { Access::READWRITE } else { Access::READ }, 1))
+        }
+        fn read(&mut self, key: u32, value: &mut ElemValue) -> Result<()> {
+            value.set_boolean(0, self.0.lock().unwrap().plugged[key as usize]);
+            Ok(())
+        }
+        fn read_event(&mut self, _: &mut Handle) -> Result<Option<(ElemId, EventMask)>> {
+            let mut s = self.0.lock().unwrap();
+            if s.events.is_empty() { return Ok(None) }
+            let (key, mask) = s.events.remove(0);
+            Ok(Some((Self::id(key), mask)))
+        }
+    }
+
+    let state = Arc::new(Mutex::new(State { plugged: [false, true, true, true, true], present: [true, true, true, true, false], events: Vec::new() }));
+    let ctl = ctl_ext::create("rust_jacks", true, Jacks(state.clone())).unwrap();
+    let mut m = JackMonitor::from_hctl(HCtl::from_ctl(ctl).unwrap()).unwrap();
+    let names: Vec<_> = m.jacks().iter().map(|j| (j.name.as_str(), j.phantom, j.plugged)).collect();
+    assert_eq!(names, [("Headphone", false, false), ("Line", true, true), ("Mic", false, true)]);
+    assert!(m.read().unwrap().is_empty());
+
+    // The Mic is unplugged without an event, so it is not read
+    {
+        let mut s = state.lock().unwrap();
+        s.plugged[..2].copy_from_slice(&[true, false]);
+        s.events.push((0, EventMask(1)));
+    }
+    let changed = m.read().unwrap();
+    assert_eq!(changed.len(), 1);
+    let JackEvent::Changed(j) = &changed[0] else { panic!("{:?}", changed) };
+    assert_eq!((j.name.as_str(), j.plugged), ("Headphone", true));
+    assert_eq!(m.is_plugged("Headphone"), Some(true));
+    assert_eq!(m.is_plugged("Mic"), Some(true));
+    assert_eq!(m.is_plugged("Speaker"), None);
+
+    {
+        let mut s = state.lock().unwrap();
+        s.present[1] = false;
+        s.present[4] = true;
+        s.events.extend([(1, EventMask(!0)), (4, EventMask(1 << 2))]);
+    }
+    let events = m.read().unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(matches!(&events[0], JackEvent::Removed(j) if j.name == "Mic"));
+    assert!(matches!(&events[1], JackEvent::Added(j) if j.name == "HDMI/DP,pcm=3" && j.plugged));
+    let names: Vec<_> = m.jacks().iter().map(|j| j.name.as_str()).collect();
+    assert_eq!(names, ["Headphone", "Line", "HDMI/DP,pcm=3"]);
+}
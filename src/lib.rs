@@ -45,13 +45,13 @@ impl $name {
     pub fn all() -> &'static [$name] { &$static_name[..] }
 
     #[allow(dead_code)]
-    fn from_c_int(c: ::libc::c_int, s: &'static str) -> Result<$name> {
+    pub(crate) fn from_c_int(c: ::libc::c_int, s: &'static str) -> Result<$name> {
         Self::all().iter().find(|&&x| c == x as ::libc::c_int).map(|&x| x)
             .ok_or_else(|| Error::unsupported(s))
     }
 
     #[allow(dead_code)]
-    fn to_c_int(&self) -> ::libc::c_int {
+    pub(crate) fn to_c_int(&self) -> ::libc::c_int {
         return *self as ::libc::c_int;
     }
 }
@@ -102,9 +102,11 @@ pub mod card;
 pub use crate::card::Card as Card;
 
 mod ctl_int;
+mod iec958;
 pub mod ctl {
     //! Control device API
-    pub use super::ctl_int::{Ctl, CardInfo, DeviceIter, ElemIface, ElemId, ElemList, ElemType, ElemValue, ElemInfo};
+    pub use super::ctl_int::{Ctl, CardInfo, DeviceIter, ElemIface, ElemId, ElemList, ElemType, ElemValue, ElemInfo, ElemSpec, Event, EventMask};
+    pub use super::iec958::{Iec958Status, Iec958Emphasis};
 }
 
 pub use crate::ctl::Ctl as Ctl;
@@ -112,6 +114,10 @@ pub use crate::ctl::Ctl as Ctl;
 pub mod hctl;
 pub use crate::hctl::HCtl as HCtl;
 
+pub mod jack;
+
+pub mod tlv;
+
 pub mod pcm;
 pub use crate::pcm::PCM as PCM;
 
@@ -123,6 +129,15 @@ pub use crate::rawmidi::Rawmidi;
 pub mod ump;
 pub use crate::ump::Ump;
 
+pub mod hwdep;
+pub use crate::hwdep::Hwdep;
+
+pub mod ucm;
+
+pub mod eld;
+
+pub mod state;
+
 pub mod device_name;
 
 pub mod poll;
@@ -131,6 +146,9 @@ pub use crate::poll::Descriptors as PollDescriptors;
 pub mod mixer;
 pub use crate::mixer::Mixer as Mixer;
 
+pub mod timer;
+pub use crate::timer::Timer as Timer;
+
 pub mod seq;
 pub use crate::seq::Seq as Seq;
 
@@ -141,3 +159,5 @@ pub use crate::io::Output;
 mod chmap;
 
 pub mod direct;
+
+pub mod plugin;
@@ -1,11 +1,15 @@
 //! Mixer API - Simple Mixer API for mixer control
 //!
 use core::ffi::CStr;
+use ::alloc::boxed::Box;
 use ::alloc::ffi::CString;
 use ::alloc::string::String;
 use core::{ptr, mem, fmt, ops};
-use libc::{c_long, c_int, c_uint, c_short, pollfd};
+use libc::{c_long, c_int, c_uint, c_short, c_void, pollfd};
 use crate::poll;
+use crate::ctl_int::EventMask;
+use crate::hctl::{self, HCtl};
+use crate::tlv;
 
 use crate::alsa;
 use super::Round;
@@ -48,6 +52,30 @@ impl Mixer {
         acheck!(snd_mixer_attach(self.0, name.as_ptr())).map(|_| ())
     }
 
+    /// Attaches an opened but not yet loaded HCtl, e g one created with `HCtl::from_ctl`.
+    ///
+    /// The mixer loads the HCtl itself, so this fails with EBUSY if `HCtl::load` has already been called.
+    /// The mixer takes over the HCtl, including its event handling, so any callback set with
+    /// `HCtl::set_callback` is dropped.
+    pub fn attach_hctl(&mut self, hctl: HCtl) -> Result<()> {
+        if hctl.elem_iter().next().is_some() { return Err(Error::new("snd_mixer_attach_hctl", libc::EBUSY)) }
+        // alsa-lib closes the hctl if attaching fails, except when out of memory, where it is leaked
+        acheck!(snd_mixer_attach_hctl(self.0, hctl::hctl_into_ptr(hctl))).map(|_| ())
+    }
+
+    /// Sets a callback that is called with each simple element that is added, during `load` or `handle_events`.
+    ///
+    /// Removal of an element is reported to the element's own callback, see `Selem::set_callback`.
+    pub fn set_callback<F: FnMut(&Selem, EventMask) + Send + 'static>(&self, f: F) {
+        let old = unsafe { alsa::snd_mixer_get_callback_private(self.0) } as *mut Callback;
+        let f: Callback = Box::new(f);
+        unsafe {
+            alsa::snd_mixer_set_callback_private(self.0, Box::into_raw(Box::new(f)) as *mut c_void);
+            alsa::snd_mixer_set_callback(self.0, Some(mixer_callback));
+        }
+        if !old.is_null() { drop(unsafe { Box::from_raw(old) }) }
+    }
+
     pub fn load(&mut self) -> Result<()> {
         acheck!(snd_mixer_load(self.0)).map(|_| ())
     }
@@ -70,8 +98,46 @@ impl Mixer {
 /// Closes mixer and frees used resources
 impl Drop for Mixer {
     fn drop(&mut self) {
+        let cb = unsafe { alsa::snd_mixer_get_callback_private(self.0) } as *mut Callback;
+        // Closing removes all elements, which frees their callbacks
         unsafe { alsa::snd_mixer_close(self.0) };
+        if !cb.is_null() { drop(unsafe { Box::from_raw(cb) }) }
+    }
+}
+
+type Callback = Box<dyn FnMut(&Selem, EventMask) + Send>;
+
+// There is no snd_mixer_elem_get_mixer, so keep the mixer together with the element's callback
+struct ElemCallback(*mut alsa::snd_mixer_t, Callback);
+
+unsafe extern "C" fn mixer_callback(mixer: *mut alsa::snd_mixer_t, mask: c_uint, elem: *mut alsa::snd_mixer_elem_t) -> c_int {
+    let p = alsa::snd_mixer_get_callback_private(mixer) as *mut Callback;
+    if p.is_null() { return 0 }
+    let m = mem::ManuallyDrop::new(Mixer(mixer));
+    let Some(selem) = Selem::new(Elem { handle: elem, _mixer: &m }) else { return 0 };
+    // Take the callback out while it runs, in case it replaces itself
+    alsa::snd_mixer_set_callback_private(mixer, ptr::null_mut());
+    let mut f = Box::from_raw(p);
+    f(&selem, EventMask(mask));
+    if alsa::snd_mixer_get_callback_private(mixer).is_null() {
+        alsa::snd_mixer_set_callback_private(mixer, Box::into_raw(f) as *mut c_void);
     }
+    0
+}
+
+unsafe extern "C" fn elem_callback(elem: *mut alsa::snd_mixer_elem_t, mask: c_uint) -> c_int {
+    let p = alsa::snd_mixer_elem_get_callback_private(elem) as *mut ElemCallback;
+    if p.is_null() { return 0 }
+    alsa::snd_mixer_elem_set_callback_private(elem, ptr::null_mut());
+    let mut f = Box::from_raw(p);
+    let m = mem::ManuallyDrop::new(Mixer(f.0));
+    let mask = EventMask(mask);
+    (f.1)(&Selem(Elem { handle: elem, _mixer: &m }), mask);
+    // The element is freed after a remove event, so drop the callback with it
+    if !mask.remove() && alsa::snd_mixer_elem_get_callback_private(elem).is_null() {
+        alsa::snd_mixer_elem_set_callback_private(elem, Box::into_raw(f) as *mut c_void);
+    }
+    0
 }
 
 
@@ -127,6 +193,43 @@ impl ops::SubAssign for MilliBel {
     fn sub_assign(&mut self, rhs: Self) { self.0 -= rhs.0 }
 }
 
+/// Volume ranges up to this size are mapped linearly in dB, larger ones with a cubic curve.
+const MAX_LINEAR_DB_SCALE: i64 = 24 * 100;
+
+fn round_dir(x: f64, dir: Round) -> i64 {
+    let i = x as i64;
+    match dir {
+        Round::Floor if (i as f64) > x => i - 1,
+        Round::Ceil if (i as f64) < x => i + 1,
+        _ => i,
+    }
+}
+
+/// Maps a dB value to 0.0 - 1.0, in the same way as volume_mapping.c in alsa-utils (used by alsamixer).
+fn db_to_normalized(value: MilliBel, min: MilliBel, max: MilliBel) -> f64 {
+    if max.0 - min.0 <= MAX_LINEAR_DB_SCALE {
+        return (value.0 - min.0) as f64 / (max.0 - min.0) as f64
+    }
+    let normalized = tlv::exp10((value.0 - max.0) as f64 / 6000.0);
+    if min == tlv::DB_GAIN_MUTE { return normalized }
+    let min_norm = tlv::exp10((min.0 - max.0) as f64 / 6000.0);
+    (normalized - min_norm) / (1.0 - min_norm)
+}
+
+/// The inverse of `db_to_normalized`.
+fn normalized_to_db(volume: f64, min: MilliBel, max: MilliBel, dir: Round) -> MilliBel {
+    let volume = volume.clamp(0.0, 1.0);
+    if max.0 - min.0 <= MAX_LINEAR_DB_SCALE {
+        return MilliBel(round_dir(volume * (max.0 - min.0) as f64, dir) + min.0)
+    }
+    let volume = if min == tlv::DB_GAIN_MUTE { volume } else {
+        let min_norm = tlv::exp10((min.0 - max.0) as f64 / 6000.0);
+        volume * (1.0 - min_norm) + min_norm
+    };
+    if volume <= 0.0 { return min }
+    MilliBel(round_dir(6000.0 * tlv::log10(volume), dir) + max.0)
+}
+
 /// Wraps [snd_mixer_elem_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___mixer.html)
 #[derive(Copy, Clone, Debug)]
 pub struct Elem<'a>{
@@ -187,7 +290,7 @@ impl SelemId {
     /// Convert SelemId into ``*mut snd_mixer_selem_id_t` that the alsa call needs.
     /// See [snd_mixer_selem_id_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___simple_mixer.html)
     #[inline]
-    fn as_ptr(&self) -> *mut alsa::snd_mixer_selem_id_t {
+    pub(crate) fn as_ptr(&self) -> *mut alsa::snd_mixer_selem_id_t {
         self.0.as_ptr() as *const _ as *mut alsa::snd_mixer_selem_id_t
     }
 
@@ -233,6 +336,23 @@ impl<'a> Selem<'a> {
         id
     }
 
+    /// Sets a callback that is called from `Mixer::handle_events` when the element changes.
+    ///
+    /// Changes made through this Mixer are not reported, since alsa-lib only reports values
+    /// that differ from the ones it already knows about.
+    ///
+    /// The last call has `EventMask::remove` set, and comes when the element is removed (e g
+    /// when a USB device is unplugged) or the Mixer is closed.
+    pub fn set_callback<F: FnMut(&Selem, EventMask) + Send + 'static>(&self, f: F) {
+        let old = unsafe { alsa::snd_mixer_elem_get_callback_private(self.handle) } as *mut ElemCallback;
+        let f = ElemCallback(self._mixer.0, Box::new(f));
+        unsafe {
+            alsa::snd_mixer_elem_set_callback_private(self.handle, Box::into_raw(Box::new(f)) as *mut c_void);
+            alsa::snd_mixer_elem_set_callback(self.handle, Some(elem_callback));
+        }
+        if !old.is_null() { drop(unsafe { Box::from_raw(old) }) }
+    }
+
     pub fn has_capture_volume(&self) -> bool {
         unsafe { alsa::snd_mixer_selem_has_capture_volume(self.handle) > 0 }
     }
@@ -405,6 +525,54 @@ impl<'a> Selem<'a> {
         acheck!(snd_mixer_selem_set_capture_volume_all(self.handle, value as c_long)).map(|_| ())
     }
 
+    fn get_volume_normalized(&self, channel: SelemChannelId, playback: bool) -> Result<f64> {
+        let (dbmin, dbmax) = if playback { self.get_playback_db_range() } else { self.get_capture_db_range() };
+        if dbmin >= dbmax {
+            // No dB information, so use the raw volume
+            let (min, max) = if playback { self.get_playback_volume_range() } else { self.get_capture_volume_range() };
+            if min >= max { return Ok(0.0) }
+            let v = if playback { self.get_playback_volume(channel)? } else { self.get_capture_volume(channel)? };
+            return Ok((v - min) as f64 / (max - min) as f64)
+        }
+        let db = if playback { self.get_playback_vol_db(channel)? } else { self.get_capture_vol_db(channel)? };
+        Ok(db_to_normalized(db, dbmin, dbmax))
+    }
+
+    fn set_volume_normalized(&self, channel: SelemChannelId, volume: f64, dir: Round, playback: bool) -> Result<()> {
+        let (dbmin, dbmax) = if playback { self.get_playback_db_range() } else { self.get_capture_db_range() };
+        if dbmin >= dbmax {
+            let (min, max) = if playback { self.get_playback_volume_range() } else { self.get_capture_volume_range() };
+            let v = round_dir(volume.clamp(0.0, 1.0) * (max - min) as f64, dir) + min;
+            return if playback { self.set_playback_volume(channel, v) } else { self.set_capture_volume(channel, v) }
+        }
+        let db = normalized_to_db(volume, dbmin, dbmax, dir);
+        if playback { self.set_playback_db(channel, db, dir) } else { self.set_capture_db(channel, db, dir) }
+    }
+
+    /// Returns the playback volume in the range 0.0 - 1.0, mapped the same way as alsamixer does.
+    ///
+    /// If the element has a dB range larger than 24 dB, the mapping is a cubic curve, which is
+    /// closer to how loud it sounds. Otherwise the mapping is linear in dB, or in raw volume if
+    /// the element has no dB information.
+    pub fn get_playback_volume_normalized(&self, channel: SelemChannelId) -> Result<f64> {
+        self.get_volume_normalized(channel, true)
+    }
+
+    /// Sets the playback volume from a value in the range 0.0 - 1.0, see `get_playback_volume_normalized`.
+    pub fn set_playback_volume_normalized(&self, channel: SelemChannelId, volume: f64, dir: Round) -> Result<()> {
+        self.set_volume_normalized(channel, volume, dir, true)
+    }
+
+    /// Returns the capture volume in the range 0.0 - 1.0, see `get_playback_volume_normalized`.
+    pub fn get_capture_volume_normalized(&self, channel: SelemChannelId) -> Result<f64> {
+        self.get_volume_normalized(channel, false)
+    }
+
+    /// Sets the capture volume from a value in the range 0.0 - 1.0, see `get_playback_volume_normalized`.
+    pub fn set_capture_volume_normalized(&self, channel: SelemChannelId, volume: f64, dir: Round) -> Result<()> {
+        self.set_volume_normalized(channel, volume, dir, false)
+    }
+
     pub fn set_playback_switch(&self, channel: SelemChannelId, value: i32) -> Result<()> {
         acheck!(snd_mixer_selem_set_playback_switch(self.handle, channel as i32, value)).map(|_| ())
     }
@@ -669,6 +837,28 @@ fn get_and_set_capture_volume() {
 }
 
 
+#[test]
+fn volume_mapping() {
+    let (min, max) = (MilliBel(-6000), MilliBel(0));
+    assert_eq!(db_to_normalized(min, min, max), 0.0);
+    assert_eq!(db_to_normalized(max, min, max), 1.0);
+    let n = db_to_normalized(MilliBel(-2000), min, max);
+    assert!((n - 0.4046).abs() < 0.0001);
+    assert_eq!(normalized_to_db(n, min, max, Round::Ceil), MilliBel(-2000));
+    assert_eq!(normalized_to_db(0.4, min, max, Round::Floor), MilliBel(-2024));
+    assert_eq!(normalized_to_db(0.4, min, max, Round::Ceil), MilliBel(-2023));
+    assert_eq!(normalized_to_db(-1.0, min, max, Round::Floor), min);
+    assert_eq!(normalized_to_db(2.0, min, max, Round::Floor), max);
+
+    // A muted minimum maps 0.0 to mute
+    assert_eq!(normalized_to_db(0.0, tlv::DB_GAIN_MUTE, max, Round::Ceil), tlv::DB_GAIN_MUTE);
+    assert!((db_to_normalized(MilliBel(-2000), tlv::DB_GAIN_MUTE, max) - 0.4642).abs() < 0.0001);
+
+    // Small ranges are linear
+    assert_eq!(db_to_normalized(MilliBel(-500), MilliBel(-1000), max), 0.5);
+    assert_eq!(normalized_to_db(0.25, MilliBel(-1000), max, Round::Floor), MilliBel(-750));
+}
+
 #[test]
 fn print_sizeof() {
     extern crate std;
@@ -677,3 +867,63 @@ fn print_sizeof() {
     assert!(selemid <= SELEM_ID_SIZE);
     std::println!("Selem id: {}", selemid);
 }
+
+#[test]
+fn mixer_callbacks() {
+    extern crate std;
+    use std::sync::{Arc, Mutex};
+    use ::alloc::vec::Vec;
+    use crate::ctl::{ElemId, ElemIface, ElemType, ElemValue};
+    use crate::plugin::ctl_ext::{self, CtlExt, Access};
+
+    // Volumes and pending events, shared with the test so it can change volumes behind the mixer's back
+    type State = Arc<Mutex<([i32; 2], Vec<u32>)>>;
+    struct Volumes(State);
+
+    impl CtlExt for Volumes {
+        fn elem_count(&mut self) -> u32 { 2 }
+        fn elem_list(&mut self, offset: u32) -> Result<ElemId> {
+            let mut id = ElemId::new(ElemIface::Mixer);
+            id.set_name(if offset == 0 { c"Master Playback Volume" } else { c"PCM Playback Volume" });
+            Ok(id)
+        }
+        fn get_attribute(&mut self, _: u32) -> Result<(ElemType, Access, u32)> { Ok((ElemType::Integer, Access::READWRITE, 1)) }
+        fn get_integer_info(&mut self, _: u32) -> Result<(i32, i32, i32)> { Ok((0, 100, 1)) }
+        fn read(&mut self, key: u32, value: &mut ElemValue) -> Result<()> {
+            value.set_integer(0, self.0.lock().unwrap().0[key as usize]);
+            Ok(())
+        }
+        fn read_event(&mut self, _: &mut ctl_ext::Handle) -> Result<Option<(ElemId, EventMask)>> {
+            let key = { let mut s = self.0.lock().unwrap(); if s.1.is_empty() { return Ok(None) }; s.1.remove(0) };
+            Ok(Some((self.elem_list(key)?, EventMask(1))))
+        }
+    }
+
+    let state: State = Arc::new(Mutex::new(([10, 20], Vec::new())));
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let ctl = ctl_ext::create("rust_mixer_callbacks", true, Volumes(state.clone())).unwrap();
+    let mut mixer = Mixer::open(false).unwrap();
+    let loaded = HCtl::from_ctl(ctl_ext::create("rust_mixer_loaded", true, Volumes(state.clone())).unwrap()).unwrap();
+    loaded.load().unwrap();
+    assert_eq!(mixer.attach_hctl(loaded).unwrap_err().errno(), libc::EBUSY);
+    mixer.attach_hctl(HCtl::from_ctl(ctl).unwrap()).unwrap();
+    Selem::register(&mut mixer).unwrap();
+    let log2 = log.clone();
+    mixer.set_callback(move |selem, mask| {
+        assert!(mask.add());
+        if selem.get_id().get_name().unwrap() != "Master" { return }
+        let log3 = log2.clone();
+        selem.set_callback(move |selem, mask| {
+            let vol = if mask.remove() { None } else { selem.get_playback_volume(SelemChannelId::mono()).ok() };
+            log3.lock().unwrap().push((mask, vol));
+        });
+    });
+    mixer.load().unwrap();
+    log.lock().unwrap().clear();
+
+    *state.lock().unwrap() = ([70, 80], ::alloc::vec![0, 1]);
+    assert_eq!(mixer.handle_events().unwrap(), 2);
+    assert_eq!(&*log.lock().unwrap(), &[(EventMask(1), Some(70))]);
+    drop(mixer);
+    assert_eq!(log.lock().unwrap()[1], (EventMask(!0), None));
+}
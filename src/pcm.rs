@@ -1,5 +1,7 @@
 //! Audio playback and capture
 //!
+//! To negotiate parameters with fallbacks, rather than setting them one by one as below, see `Config`.
+//!
 //! # Example
 //! Playback a sine wave through the "default" device.
 //!
@@ -52,11 +54,17 @@ use core::ffi::CStr;
 use core::str::FromStr;
 use ::alloc::ffi::CString;
 use ::alloc::format;
+use ::alloc::vec::Vec;
 use core::{fmt, ptr, cell};
+use core::ops::RangeInclusive;
 use super::error::*;
-use super::{Direction, Output, poll, ValueOr, chmap};
+use super::{Direction, Output, poll, ValueOr, chmap, config};
 
 pub use super::chmap::{Chmap, ChmapPosition, ChmapType, ChmapsQuery};
+pub use self::negotiate::{Config, ConfigBuilder, NegotiatedConfig, Compromise};
+
+pub mod convert;
+mod negotiate;
 
 /// [snd_pcm_sframes_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___p_c_m.html)
 pub type Frames = alsa::snd_pcm_sframes_t;
@@ -162,6 +170,20 @@ impl PCM {
         acheck!(snd_pcm_open(&mut r, name.as_ptr(), stream, flags)).map(|_| PCM(r, cell::Cell::new(false)))
     }
 
+    /// Like open, but looks up the name in `lconf` instead of the global configuration.
+    ///
+    /// This makes it possible to define a device (e g a `plug` or `softvol` device) in code.
+    pub fn open_lconf(name: &CStr, dir: Direction, nonblock: bool, lconf: &config::Config) -> Result<PCM> {
+        let mut r = ptr::null_mut();
+        let stream = match dir {
+            Direction::Capture => alsa::SND_PCM_STREAM_CAPTURE,
+            Direction::Playback => alsa::SND_PCM_STREAM_PLAYBACK
+        };
+        let flags = if nonblock { alsa::SND_PCM_NONBLOCK as i32 } else { 0 };
+        acheck!(snd_pcm_open_lconf(&mut r, name.as_ptr(), stream, flags, config::config_ptr(lconf)))
+            .map(|_| PCM(r, cell::Cell::new(false)))
+    }
+
     pub fn start(&self) -> Result<()> { acheck!(snd_pcm_start(self.0)).map(|_| ()) }
     pub fn drop(&self) -> Result<()> { acheck!(snd_pcm_drop(self.0)).map(|_| ()) }
     pub fn pause(&self, pause: bool) -> Result<()> {
@@ -353,6 +375,8 @@ impl Drop for PCM {
     fn drop(&mut self) { unsafe { alsa::snd_pcm_close(self.0) }; }
 }
 
+pub(crate) fn pcm_from_ptr(p: *mut alsa::snd_pcm_t) -> PCM { PCM(p, cell::Cell::new(false)) }
+
 
 impl poll::Descriptors for PCM {
     fn count(&self) -> usize {
@@ -439,6 +463,37 @@ impl<'a, S: Copy> IO<'a, S> {
         acheck!(snd_pcm_readn((self.0).0, bufs.as_mut_ptr() as *mut *mut c_void, frames)).map(|r| r as usize)
     }
 
+    /// Checks that the PCM is set up for non-interleaved access with one buffer per channel,
+    /// and returns the number of frames in each buffer.
+    fn planar_frames(&self, func: &'static str, mut lens: impl ExactSizeIterator<Item=usize>) -> Result<usize> {
+        let hwp = self.0.hw_params_current()?;
+        if hwp.get_access()? != Access::RWNonInterleaved { return Err(Error::unsupported(func)) }
+        let channels = hwp.get_channels()? as usize;
+        if lens.len() != channels { return Err(Error::new(func, libc::EINVAL)) }
+        let len = lens.next().unwrap_or(0);
+        if lens.any(|l| l != len) { return Err(Error::new(func, libc::EINVAL)) }
+        let sample_bytes = self.0.frames_to_bytes(1) as usize / channels.max(1);
+        Ok(len * size_of::<S>() / sample_bytes.max(1))
+    }
+
+    /// Safe version of `writen`, with one slice per channel. On success, returns number of frames written.
+    ///
+    /// The PCM must be set up with `Access::RWNonInterleaved`, and all slices must have the same length.
+    pub fn writen_slices(&self, bufs: &[&[S]]) -> Result<usize> {
+        let frames = self.planar_frames("writen_slices", bufs.iter().map(|b| b.len()))?;
+        let ptrs: Vec<*const S> = bufs.iter().map(|b| b.as_ptr()).collect();
+        unsafe { self.writen(&ptrs, frames) }
+    }
+
+    /// Safe version of `readn`, with one slice per channel. On success, returns number of frames read.
+    ///
+    /// The PCM must be set up with `Access::RWNonInterleaved`, and all slices must have the same length.
+    pub fn readn_slices(&self, bufs: &mut [&mut [S]]) -> Result<usize> {
+        let frames = self.planar_frames("readn_slices", bufs.iter().map(|b| b.len()))?;
+        let mut ptrs: Vec<*mut S> = bufs.iter_mut().map(|b| b.as_mut_ptr()).collect();
+        unsafe { self.readn(&mut ptrs, frames) }
+    }
+
     /// Wrapper around snd_pcm_mmap_begin and snd_pcm_mmap_commit.
     ///
     /// You can read/write into the sound card's buffer during the call to the closure.
@@ -799,6 +854,82 @@ impl IoFormat for f64 {
     const FORMAT: Format = Format::Float64BE;
 }
 
+macro_rules! packed_sample {
+    ($(#[$attr:meta])* $name:ident, $format:ident, $bits:expr, $signed:expr, $little:expr) => {
+        $(#[$attr])*
+        #[allow(non_camel_case_types)]
+        #[repr(C)]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+        pub struct $name(pub [u8; 3]);
+
+        impl $name {
+            const MIN: i32 = if $signed { -(1 << ($bits - 1)) } else { 0 };
+            const MAX: i32 = if $signed { (1 << ($bits - 1)) - 1 } else { (1 << $bits) - 1 };
+
+            /// Returns the sample value, sign extended if the format is signed.
+            pub const fn get(self) -> i32 {
+                let b = self.0;
+                let raw = if $little { u32::from_le_bytes([b[0], b[1], b[2], 0]) } else { u32::from_be_bytes([0, b[0], b[1], b[2]]) };
+                if $signed { ((raw << (32 - $bits)) as i32) >> (32 - $bits) }
+                else { (raw & ((1 << $bits) - 1)) as i32 }
+            }
+        }
+
+        impl IoFormat for $name { const FORMAT: Format = Format::$format; }
+
+        impl From<$name> for i32 {
+            fn from(s: $name) -> i32 { s.get() }
+        }
+
+        impl TryFrom<i32> for $name {
+            type Error = Error;
+            /// Fails with ERANGE if the value does not fit.
+            fn try_from(v: i32) -> Result<$name> {
+                if v < $name::MIN || v > $name::MAX { return Err(Error::new(stringify!($name), libc::ERANGE)) }
+                let b = v.to_le_bytes();
+                Ok($name(if $little { [b[0], b[1], b[2]] } else { [b[2], b[1], b[0]] }))
+            }
+        }
+    }
+}
+
+packed_sample!(
+    /// A sample of format `Format::S243LE`, signed 24 bit in 3 bytes
+    S24_3LE, S243LE, 24, true, true);
+packed_sample!(
+    /// A sample of format `Format::S243BE`, signed 24 bit in 3 bytes
+    S24_3BE, S243BE, 24, true, false);
+packed_sample!(
+    /// A sample of format `Format::U243LE`, unsigned 24 bit in 3 bytes
+    U24_3LE, U243LE, 24, false, true);
+packed_sample!(
+    /// A sample of format `Format::U243BE`, unsigned 24 bit in 3 bytes
+    U24_3BE, U243BE, 24, false, false);
+packed_sample!(
+    /// A sample of format `Format::S203LE`, signed 20 bit in 3 bytes
+    S20_3LE, S203LE, 20, true, true);
+packed_sample!(
+    /// A sample of format `Format::S203BE`, signed 20 bit in 3 bytes
+    S20_3BE, S203BE, 20, true, false);
+packed_sample!(
+    /// A sample of format `Format::U203LE`, unsigned 20 bit in 3 bytes
+    U20_3LE, U203LE, 20, false, true);
+packed_sample!(
+    /// A sample of format `Format::U203BE`, unsigned 20 bit in 3 bytes
+    U20_3BE, U203BE, 20, false, false);
+packed_sample!(
+    /// A sample of format `Format::S183LE`, signed 18 bit in 3 bytes
+    S18_3LE, S183LE, 18, true, true);
+packed_sample!(
+    /// A sample of format `Format::S183BE`, signed 18 bit in 3 bytes
+    S18_3BE, S183BE, 18, true, false);
+packed_sample!(
+    /// A sample of format `Format::U183LE`, unsigned 18 bit in 3 bytes
+    U18_3LE, U183LE, 18, false, true);
+packed_sample!(
+    /// A sample of format `Format::U183BE`, unsigned 18 bit in 3 bytes
+    U18_3BE, U183BE, 18, false, false);
+
 
 alsa_enum!(
     /// [SND_PCM_ACCESS_xxx](http://www.alsa-project.org/alsa-doc/alsa-lib/group___p_c_m.html) constants
@@ -811,6 +942,16 @@ alsa_enum!(
     RWNonInterleaved = SND_PCM_ACCESS_RW_NONINTERLEAVED,
 );
 
+alsa_enum!(
+    /// [SND_PCM_SUBFORMAT_xxx](http://www.alsa-project.org/alsa-doc/alsa-lib/group___p_c_m.html) constants
+    Subformat, ALL_SUBFORMATS[4],
+
+    Std = SND_PCM_SUBFORMAT_STD,
+    MsbitsMax = SND_PCM_SUBFORMAT_MSBITS_MAX,
+    Msbits20 = SND_PCM_SUBFORMAT_MSBITS_20,
+    Msbits24 = SND_PCM_SUBFORMAT_MSBITS_24,
+);
+
 alsa_enum!(
     /// [SND_PCM_TSTAMP_TYPE_xxx](http://www.alsa-project.org/alsa-doc/alsa-lib/group___p_c_m.html) constants
     TstampType, ALL_TSTAMP_TYPES[3],
@@ -937,6 +1078,38 @@ impl<'a> HwParams<'a> {
             .and_then(|_| Access::from_c_int(v as c_int, "snd_pcm_hw_params_get_access"))
     }
 
+    /// Returns all formats in the configuration space.
+    pub fn get_format_mask(&self) -> Result<Vec<Format>> {
+        let mut m = ptr::null_mut();
+        acheck!(snd_pcm_format_mask_malloc(&mut m))?;
+        unsafe { alsa::snd_pcm_hw_params_get_format_mask(self.0, m) };
+        let r = Format::all().iter().copied()
+            .filter(|&v| v != Format::Unknown && unsafe { alsa::snd_pcm_format_mask_test(m, v as c_int) } != 0).collect();
+        unsafe { alsa::snd_pcm_format_mask_free(m) };
+        Ok(r)
+    }
+
+    /// Returns all access types in the configuration space.
+    pub fn get_access_mask(&self) -> Result<Vec<Access>> {
+        let mut m = ptr::null_mut();
+        acheck!(snd_pcm_access_mask_malloc(&mut m))?;
+        let r = acheck!(snd_pcm_hw_params_get_access_mask(self.0, m)).map(|_| Access::all().iter().copied()
+            .filter(|&v| unsafe { alsa::snd_pcm_access_mask_test(m, v as c_uint) } != 0).collect());
+        unsafe { alsa::snd_pcm_access_mask_free(m) };
+        r
+    }
+
+    /// Returns all subformats in the configuration space.
+    pub fn get_subformat_mask(&self) -> Result<Vec<Subformat>> {
+        let mut m = ptr::null_mut();
+        acheck!(snd_pcm_subformat_mask_malloc(&mut m))?;
+        unsafe { alsa::snd_pcm_hw_params_get_subformat_mask(self.0, m) };
+        let r = Subformat::all().iter().copied()
+            .filter(|&v| unsafe { alsa::snd_pcm_subformat_mask_test(m, v as c_int) } != 0).collect();
+        unsafe { alsa::snd_pcm_subformat_mask_free(m) };
+        Ok(r)
+    }
+
     pub fn set_period_size_near(&self, v: Frames, dir: ValueOr) -> Result<Frames> {
         let mut d = dir as c_int;
         let mut r = v as alsa::snd_pcm_uframes_t;
@@ -1177,6 +1350,44 @@ impl<'a> fmt::Debug for HwParams<'a> {
     }
 }
 
+/// Sample rates that `Capabilities::probe` tests for.
+pub const STANDARD_RATES: [u32; 16] = [8000, 11025, 16000, 22050, 32000, 44100, 48000, 64000, 88200, 96000,
+    176400, 192000, 352800, 384000, 705600, 768000];
+
+/// Everything a PCM supports, before any hardware parameters are chosen
+///
+/// Note that the values are not independent: e g, a device might support 8 channels only at 48000 Hz.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    pub formats: Vec<Format>,
+    pub accesses: Vec<Access>,
+    pub subformats: Vec<Subformat>,
+    pub channels: RangeInclusive<u32>,
+    pub rate: RangeInclusive<u32>,
+    /// The `STANDARD_RATES` that are supported. Might be empty, e g for devices with a fixed odd rate.
+    pub rates: Vec<u32>,
+    pub period_size: RangeInclusive<Frames>,
+    pub buffer_size: RangeInclusive<Frames>,
+}
+
+impl Capabilities {
+    /// Queries the full configuration space of a PCM, which must not have its hardware parameters set.
+    pub fn probe(pcm: &PCM) -> Result<Capabilities> {
+        let hwp = HwParams::any(pcm)?;
+        let rate = hwp.get_rate_min()?..=hwp.get_rate_max()?;
+        Ok(Capabilities {
+            formats: hwp.get_format_mask()?,
+            accesses: hwp.get_access_mask()?,
+            subformats: hwp.get_subformat_mask()?,
+            channels: hwp.get_channels_min()?..=hwp.get_channels_max()?,
+            rates: STANDARD_RATES.iter().copied().filter(|r| rate.contains(r) && hwp.test_rate(*r).is_ok()).collect(),
+            rate,
+            period_size: hwp.get_period_size_min()?..=hwp.get_period_size_max()?,
+            buffer_size: hwp.get_buffer_size_min()?..=hwp.get_buffer_size_max()?,
+        })
+    }
+}
+
 /// [snd_pcm_sw_params_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___p_c_m___s_w___params.html) wrapper
 pub struct SwParams<'a>(*mut alsa::snd_pcm_sw_params_t, &'a PCM);
 
@@ -1454,3 +1665,86 @@ fn format_display_from_str() {
         assert_eq!(format, format.to_string().parse().unwrap());
     }
 }
+
+#[test]
+fn open_lconf_null() {
+    let c = config::Config::load_string("pcm.mynull { type null }").unwrap();
+    let pcm = PCM::open_lconf(c"mynull", Direction::Playback, false, &c).unwrap();
+    let hwp = HwParams::any(&pcm).unwrap();
+    hwp.set_channels(2).unwrap();
+    hwp.set_rate(48000, ValueOr::Nearest).unwrap();
+    hwp.set_format(Format::s16()).unwrap();
+    hwp.set_access(Access::RWInterleaved).unwrap();
+    pcm.hw_params(&hwp).unwrap();
+    let io = pcm.io_i16().unwrap();
+    assert_eq!(io.writei(&[0i16; 512]).unwrap(), 256);
+    assert!(PCM::open_lconf(c"default", Direction::Playback, false, &c).is_err());
+}
+
+/// Opens a null playback device, set up for 2 channels at 48000 Hz.
+#[cfg(test)]
+fn open_null(format: Format, access: Access) -> PCM {
+    let c = config::Config::load_string("pcm.mynull { type null }").unwrap();
+    let pcm = PCM::open_lconf(c"mynull", Direction::Playback, false, &c).unwrap();
+    {
+        let hwp = HwParams::any(&pcm).unwrap();
+        hwp.set_channels(2).unwrap();
+        hwp.set_rate(48000, ValueOr::Nearest).unwrap();
+        hwp.set_format(format).unwrap();
+        hwp.set_access(access).unwrap();
+        pcm.hw_params(&hwp).unwrap();
+    }
+    pcm
+}
+
+#[test]
+fn capabilities_null() {
+    let pcm = open_null(Format::s16(), Access::RWInterleaved);
+    pcm.hw_free().unwrap();
+    let caps = Capabilities::probe(&pcm).unwrap();
+    let hwp = HwParams::any(&pcm).unwrap();
+    for &f in Format::all().iter().filter(|&&f| f != Format::Unknown) {
+        assert_eq!(caps.formats.contains(&f), hwp.test_format(f).is_ok());
+    }
+    assert_eq!(caps.accesses.len(), 5);
+    assert_eq!(caps.subformats, [Subformat::Std]);
+    assert_eq!(*caps.channels.start(), 1);
+    assert_eq!(caps.rates, STANDARD_RATES);
+    assert_eq!(caps, Capabilities::probe(&pcm).unwrap());
+}
+
+#[test]
+fn io_slices_null() {
+    let pcm = open_null(Format::s16(), Access::RWNonInterleaved);
+    let io = pcm.io_i16().unwrap();
+    let (left, right) = ([0i16; 256], [0i16; 256]);
+    assert_eq!(io.writen_slices(&[&left, &right]).unwrap(), 256);
+    assert_eq!(io.writen_slices(&[&left[..100], &right[..100]]).unwrap(), 100);
+    assert!(io.writen_slices(&[&left]).is_err());
+    assert!(io.writen_slices(&[&left, &right[..100]]).is_err());
+    assert!(io.writei(&left).is_err());
+    ::core::mem::drop(io);
+
+    let bytes = pcm.io_bytes();
+    let b = [0u8; 64];
+    assert_eq!(bytes.writen_slices(&[&b, &b]).unwrap(), 32);
+}
+
+#[test]
+fn packed_samples() {
+    assert_eq!(S24_3LE::try_from(-2).unwrap(), S24_3LE([0xfe, 0xff, 0xff]));
+    assert_eq!(S24_3BE::try_from(0x123456).unwrap(), S24_3BE([0x12, 0x34, 0x56]));
+    assert_eq!(i32::from(S24_3BE([0x80, 0, 0])), -0x800000);
+    assert_eq!(i32::from(U24_3LE([0xff, 0xff, 0xff])), 0xffffff);
+    assert_eq!(i32::from(S20_3LE([0xff, 0xff, 0x07])), 0x7ffff);
+    assert_eq!(i32::from(S20_3LE([0x00, 0x00, 0xf8])), -0x80000);
+    assert!(S20_3LE::try_from(0x80000).is_err());
+    assert!(U18_3BE::try_from(-1).is_err());
+    for v in [0, 1, -1, 0x1ffff, -0x20000] { assert_eq!(i32::from(S18_3BE::try_from(v).unwrap()), v) }
+    assert_eq!(core::mem::size_of::<[U20_3BE; 4]>(), 12);
+
+    let pcm = open_null(Format::S243LE, Access::RWInterleaved);
+    assert!(pcm.io_checked::<S24_3BE>().is_err());
+    let io = pcm.io_checked::<S24_3LE>().unwrap();
+    assert_eq!(io.writei(&[S24_3LE::default(); 512]).unwrap(), 256);
+}
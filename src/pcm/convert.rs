@@ -0,0 +1,277 @@
+//! Sample format conversion - render in `f32` or `i32`, play in whatever linear format the device accepts
+//!
+//! Integer samples are full scale 32-bit, i e `i32::MAX` corresponds to `1.0`, regardless of how many bits
+//! the device format has. Conversion is done sample by sample, so it works for interleaved and
+//! non-interleaved buffers alike. Out of range values are clipped when converting to an integer format;
+//! float formats get the value as is.
+//!
+//! # Example
+//! ```no_run
+//! use alsa::{Direction, ValueOr};
+//! use alsa::pcm::{PCM, HwParams, Format, Access};
+//! use alsa::pcm::convert::{self, Dither};
+//!
+//! let pcm = PCM::new("hw:0", Direction::Playback, false).unwrap();
+//! let hwp = HwParams::any(&pcm).unwrap();
+//! hwp.set_channels(2).unwrap();
+//! hwp.set_rate(48000, ValueOr::Nearest).unwrap();
+//! let format = [Format::float(), Format::s32(), Format::s24_3(), Format::s16()].into_iter()
+//!     .find(|&f| hwp.set_format(f).is_ok()).unwrap();
+//! hwp.set_access(Access::RWInterleaved).unwrap();
+//! pcm.hw_params(&hwp).unwrap();
+//!
+//! let rendered = [0.25f32; 2048];
+//! let mut buf = vec![0u8; rendered.len() * convert::sample_bytes(format).unwrap()];
+//! convert::encode_f32(&rendered, format, &mut buf, Some(&mut Dither::default())).unwrap();
+//! pcm.io_bytes().writei(&buf).unwrap();
+//! ```
+
+use crate::error::*;
+use super::Format;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Kind { Signed, Unsigned, Float }
+
+/// How a sample of a linear format is stored
+#[derive(Debug, Copy, Clone)]
+struct Layout {
+    kind: Kind,
+    /// Significant bits, stored in the least significant bits of the container
+    bits: u32,
+    /// Container size
+    bytes: usize,
+    little: bool,
+}
+
+fn layout(f: Format) -> Option<Layout> {
+    use self::Kind::*;
+    let l = |kind, bits, bytes, little| Some(Layout { kind, bits, bytes, little });
+    match f {
+        Format::S8 => l(Signed, 8, 1, true),
+        Format::U8 => l(Unsigned, 8, 1, true),
+        Format::S16LE => l(Signed, 16, 2, true),
+        Format::S16BE => l(Signed, 16, 2, false),
+        Format::U16LE => l(Unsigned, 16, 2, true),
+        Format::U16BE => l(Unsigned, 16, 2, false),
+        Format::S24LE => l(Signed, 24, 4, true),
+        Format::S24BE => l(Signed, 24, 4, false),
+        Format::U24LE => l(Unsigned, 24, 4, true),
+        Format::U24BE => l(Unsigned, 24, 4, false),
+        Format::S32LE => l(Signed, 32, 4, true),
+        Format::S32BE => l(Signed, 32, 4, false),
+        Format::U32LE => l(Unsigned, 32, 4, true),
+        Format::U32BE => l(Unsigned, 32, 4, false),
+        Format::FloatLE => l(Float, 32, 4, true),
+        Format::FloatBE => l(Float, 32, 4, false),
+        Format::Float64LE => l(Float, 64, 8, true),
+        Format::Float64BE => l(Float, 64, 8, false),
+        Format::S20LE => l(Signed, 20, 4, true),
+        Format::S20BE => l(Signed, 20, 4, false),
+        Format::U20LE => l(Unsigned, 20, 4, true),
+        Format::U20BE => l(Unsigned, 20, 4, false),
+        Format::S243LE => l(Signed, 24, 3, true),
+        Format::S243BE => l(Signed, 24, 3, false),
+        Format::U243LE => l(Unsigned, 24, 3, true),
+        Format::U243BE => l(Unsigned, 24, 3, false),
+        Format::S203LE => l(Signed, 20, 3, true),
+        Format::S203BE => l(Signed, 20, 3, false),
+        Format::U203LE => l(Unsigned, 20, 3, true),
+        Format::U203BE => l(Unsigned, 20, 3, false),
+        Format::S183LE => l(Signed, 18, 3, true),
+        Format::S183BE => l(Signed, 18, 3, false),
+        Format::U183LE => l(Unsigned, 18, 3, true),
+        Format::U183BE => l(Unsigned, 18, 3, false),
+        _ => None,
+    }
+}
+
+/// Returns whether this module can convert to and from the format.
+pub fn is_supported(f: Format) -> bool { layout(f).is_some() }
+
+/// Returns the number of bytes of one sample, or None if the format is not supported.
+pub fn sample_bytes(f: Format) -> Option<usize> { layout(f).map(|l| l.bytes) }
+
+/// TPDF (triangular probability density function) dither
+///
+/// Adds noise of up to one least significant bit of the target format, which turns the distortion caused by
+/// truncating to fewer bits into a constant noise floor. Only used when encoding to an integer format of less than 32 bits.
+#[derive(Debug, Clone)]
+pub struct Dither(u32);
+
+impl Dither {
+    pub fn new(seed: u32) -> Dither { Dither(if seed == 0 { 0x9e37_79b9 } else { seed }) }
+
+    // xorshift32
+    fn next(&mut self) -> u32 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.0 = x;
+        x
+    }
+
+    /// Triangular noise in (-2^shift, 2^shift)
+    fn noise(&mut self, shift: u32) -> i64 {
+        let mask = (1u64 << shift) - 1;
+        (self.next() as u64 & mask) as i64 + (self.next() as u64 & mask) as i64 - mask as i64
+    }
+}
+
+impl Default for Dither {
+    fn default() -> Dither { Dither::new(0) }
+}
+
+fn read_raw(b: &[u8], l: &Layout) -> u64 {
+    let mut v = 0u64;
+    if l.little { for &x in b.iter().rev() { v = (v << 8) | x as u64 } }
+    else { for &x in b { v = (v << 8) | x as u64 } }
+    v
+}
+
+fn write_raw(b: &mut [u8], l: &Layout, mut v: u64) {
+    if l.little { for x in b.iter_mut() { *x = v as u8; v >>= 8 } }
+    else { for x in b.iter_mut().rev() { *x = v as u8; v >>= 8 } }
+}
+
+fn round(x: f64) -> i64 { if x >= 0.0 { (x + 0.5) as i64 } else { (x - 0.5) as i64 } }
+
+fn f64_to_i32(x: f64) -> i32 { round(x * 2147483648.0).clamp(i32::MIN as i64, i32::MAX as i64) as i32 }
+
+fn i32_to_f64(x: i32) -> f64 { x as f64 / 2147483648.0 }
+
+fn decode(b: &[u8], l: &Layout) -> i32 {
+    let raw = read_raw(b, l);
+    match l.kind {
+        Kind::Float if l.bits == 32 => f64_to_i32(f32::from_bits(raw as u32) as f64),
+        Kind::Float => f64_to_i32(f64::from_bits(raw)),
+        _ => {
+            let mut v = raw as u32;
+            // Flipping the top bit turns offset binary into two's complement
+            if l.kind == Kind::Unsigned { v ^= 1 << (l.bits - 1) }
+            // Left-justify, which also drops any bits above the significant ones
+            (v << (32 - l.bits)) as i32
+        }
+    }
+}
+
+fn encode(x: i32, b: &mut [u8], l: &Layout, dither: &mut Option<&mut Dither>) {
+    match l.kind {
+        Kind::Float if l.bits == 32 => write_raw(b, l, (i32_to_f64(x) as f32).to_bits() as u64),
+        Kind::Float => write_raw(b, l, i32_to_f64(x).to_bits()),
+        _ => {
+            let shift = 32 - l.bits;
+            let mut v = x as i64;
+            if shift > 0 {
+                let d = dither.as_mut().map(|d| d.noise(shift)).unwrap_or(0);
+                v = (v + (1 << (shift - 1)) + d) >> shift;
+                v = v.clamp(-(1 << (l.bits - 1)), (1 << (l.bits - 1)) - 1);
+            }
+            let mut raw = v as u64;
+            if l.kind == Kind::Unsigned {
+                raw = (raw ^ (1 << (l.bits - 1))) & ((1u64 << l.bits) - 1);
+            }
+            write_raw(b, l, raw);
+        }
+    }
+}
+
+fn check(f: Format, func: &'static str, samples: usize, bytes: usize) -> Result<Layout> {
+    let l = layout(f).ok_or_else(|| Error::unsupported(func))?;
+    if bytes < samples * l.bytes { return Err(Error::new(func, libc::EINVAL)) }
+    Ok(l)
+}
+
+/// Converts full scale `i32` samples to `format`. `dst` must be large enough to hold them all.
+pub fn encode_i32(src: &[i32], format: Format, dst: &mut [u8], mut dither: Option<&mut Dither>) -> Result<()> {
+    let l = check(format, "encode_i32", src.len(), dst.len())?;
+    for (&x, b) in src.iter().zip(dst.chunks_exact_mut(l.bytes)) { encode(x, b, &l, &mut dither) }
+    Ok(())
+}
+
+/// Converts samples in `format` to full scale `i32`, and returns the number of samples converted.
+///
+/// `dst` must be large enough to hold all the whole samples in `src`.
+pub fn decode_i32(src: &[u8], format: Format, dst: &mut [i32]) -> Result<usize> {
+    let l = layout(format).ok_or_else(|| Error::unsupported("decode_i32"))?;
+    let n = src.len() / l.bytes;
+    if dst.len() < n { return Err(Error::new("decode_i32", libc::EINVAL)) }
+    for (b, x) in src.chunks_exact(l.bytes).zip(dst.iter_mut()) { *x = decode(b, &l) }
+    Ok(n)
+}
+
+/// Converts `f32` samples in the range -1.0 to 1.0 to `format`. `dst` must be large enough to hold them all.
+///
+/// Samples outside that range are clipped for integer formats, and written unchanged for float formats.
+pub fn encode_f32(src: &[f32], format: Format, dst: &mut [u8], mut dither: Option<&mut Dither>) -> Result<()> {
+    let l = check(format, "encode_f32", src.len(), dst.len())?;
+    for (&x, b) in src.iter().zip(dst.chunks_exact_mut(l.bytes)) {
+        match l.kind {
+            // Do not lose precision by going through i32
+            Kind::Float if l.bits == 32 => write_raw(b, &l, x.to_bits() as u64),
+            Kind::Float => write_raw(b, &l, (x as f64).to_bits()),
+            _ => encode(f64_to_i32(x as f64), b, &l, &mut dither),
+        }
+    }
+    Ok(())
+}
+
+/// Converts samples in `format` to `f32` in the range -1.0 to 1.0, and returns the number of samples converted.
+///
+/// `dst` must be large enough to hold all the whole samples in `src`.
+pub fn decode_f32(src: &[u8], format: Format, dst: &mut [f32]) -> Result<usize> {
+    let l = layout(format).ok_or_else(|| Error::unsupported("decode_f32"))?;
+    let n = src.len() / l.bytes;
+    if dst.len() < n { return Err(Error::new("decode_f32", libc::EINVAL)) }
+    for (b, x) in src.chunks_exact(l.bytes).zip(dst.iter_mut()) {
+        *x = match l.kind {
+            Kind::Float if l.bits == 32 => f32::from_bits(read_raw(b, &l) as u32),
+            Kind::Float => f64::from_bits(read_raw(b, &l)) as f32,
+            _ => i32_to_f64(decode(b, &l)) as f32,
+        }
+    }
+    Ok(n)
+}
+
+#[test]
+fn convert_layouts() {
+    let enc = |x: f32, f: Format| { let mut b = [0u8; 8]; encode_f32(&[x], f, &mut b, None).unwrap(); b };
+    assert_eq!(enc(0.5, Format::S16LE)[..2], [0x00, 0x40]);
+    assert_eq!(enc(0.5, Format::S16BE)[..2], [0x40, 0x00]);
+    assert_eq!(enc(0.0, Format::U8)[..1], [0x80]);
+    assert_eq!(enc(-1.0, Format::S243BE)[..3], [0x80, 0, 0]);
+    assert_eq!(enc(-1.0, Format::S24LE)[..4], [0, 0, 0x80, 0xff]);
+    assert_eq!(enc(-1.0, Format::U24LE)[..4], [0, 0, 0, 0]);
+    assert_eq!(enc(2.0, Format::S203LE)[..3], [0xff, 0xff, 0x07]);
+    assert_eq!(enc(1.0, Format::S32LE)[..4], i32::MAX.to_le_bytes());
+    assert_eq!(enc(0.75, Format::FloatBE)[..4], 0.75f32.to_be_bytes());
+    assert_eq!(enc(-0.25, Format::Float64LE), (-0.25f64).to_le_bytes());
+    assert!(encode_f32(&[0.0; 2], Format::S16LE, &mut [0u8; 3], None).is_err());
+    assert!(encode_f32(&[0.0], Format::MuLaw, &mut [0u8; 4], None).is_err());
+
+    let mut d = [0f32; 2];
+    assert_eq!(decode_f32(&[0x00, 0xc0, 0x00, 0x40, 0x12], Format::S16LE, &mut d).unwrap(), 2);
+    assert_eq!(d, [-0.5, 0.5]);
+}
+
+#[test]
+fn convert_roundtrip() {
+    let src: [i32; 6] = [0, 1 << 24, -(1 << 24), i32::MAX & !0xffffff, i32::MIN, 0x12000000];
+    for &f in Format::all().iter().filter(|&&f| is_supported(f)) {
+        let mut buf = [0u8; 48];
+        let mut dst = [0i32; 6];
+        encode_i32(&src, f, &mut buf, None).unwrap();
+        assert_eq!(decode_i32(&buf[..src.len() * sample_bytes(f).unwrap()], f, &mut dst).unwrap(), 6);
+        assert_eq!(dst, src, "{}", f);
+    }
+
+    // Dither adds at most one LSB, and averages out
+    let zeros = [0f32; 1000];
+    let mut buf = [0u8; 2000];
+    encode_f32(&zeros, Format::S16LE, &mut buf, Some(&mut Dither::default())).unwrap();
+    let mut out = [0i32; 1000];
+    decode_i32(&buf, Format::S16LE, &mut out).unwrap();
+    assert!(out.iter().all(|&x| (x >> 16).abs() <= 1));
+    assert!(out.iter().any(|&x| x != 0));
+    assert!(out.iter().map(|&x| (x >> 16) as i64).sum::<i64>().abs() < 100);
+}
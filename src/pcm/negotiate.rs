@@ -0,0 +1,242 @@
+use crate::error::*;
+use crate::{Direction, ValueOr};
+use super::{Access, Format, Frames, HwParams, PCM};
+use core::time::Duration;
+use ::alloc::vec::Vec;
+
+/// A setting where the device could not give what was asked for first
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Compromise {
+    Access { wanted: Access, got: Access },
+    Format { wanted: Format, got: Format },
+    Channels { wanted: u32, got: u32 },
+    Rate { wanted: u32, got: u32 },
+    PeriodSize { wanted: Frames, got: Frames },
+    BufferSize { wanted: Frames, got: Frames },
+}
+
+/// Hardware and software parameters to negotiate with a PCM
+///
+/// Created with `Config::builder()`. Formats, access types, channels and rates are tried in the order
+/// they were added. If none of the channel counts or rates are supported, the one nearest to the first
+/// choice is used. Latency is set to the nearest value the device supports.
+///
+/// # Example
+/// ```no_run
+/// use alsa::{Direction, pcm::{PCM, Config, Format}};
+/// use std::time::Duration;
+///
+/// let pcm = PCM::new("default", Direction::Playback, false).unwrap();
+/// let c = Config::builder().format(Format::float()).format(Format::s16())
+///     .channels(&[2]).rates(&[48000, 44100]).latency(Duration::from_millis(40)).build();
+/// let n = c.apply(&pcm).unwrap();
+/// for x in &n.compromises { println!("Compromise: {:?}", x) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    formats: Vec<Format>,
+    accesses: Vec<Access>,
+    channels: Vec<u32>,
+    rates: Vec<u32>,
+    latency: Option<Duration>,
+    periods: u32,
+    period_size: Option<Frames>,
+    buffer_size: Option<Frames>,
+    start_threshold: Option<Frames>,
+}
+
+/// Builder for [`Config`].
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder(Config);
+
+impl ConfigBuilder {
+    /// Adds a format to try, after the ones already added. If no format is added, any format is accepted.
+    pub fn format(mut self, f: Format) -> Self { self.0.formats.push(f); self }
+    pub fn formats(mut self, f: &[Format]) -> Self { self.0.formats.extend_from_slice(f); self }
+
+    /// Adds an access type to try, after the ones already added. Defaults to `Access::RWInterleaved`.
+    pub fn access(mut self, a: Access) -> Self { self.0.accesses.push(a); self }
+
+    /// Adds channel counts to try, after the ones already added.
+    pub fn channels(mut self, v: &[u32]) -> Self { self.0.channels.extend_from_slice(v); self }
+
+    /// Adds a rate to try, after the ones already added.
+    pub fn rate(mut self, v: u32) -> Self { self.0.rates.push(v); self }
+    pub fn rates(mut self, v: &[u32]) -> Self { self.0.rates.extend_from_slice(v); self }
+
+    /// Sets the buffer time. The period time is the buffer time divided by the number of periods.
+    pub fn latency(mut self, v: Duration) -> Self { self.0.latency = Some(v); self }
+
+    /// Sets how many periods a buffer set with `latency` is divided into. Defaults to 4.
+    pub fn periods(mut self, v: u32) -> Self { self.0.periods = v; self }
+
+    /// Sets the period size, overriding the period time given by `latency`.
+    pub fn period_size(mut self, v: Frames) -> Self { self.0.period_size = Some(v); self }
+
+    /// Sets the buffer size, overriding `latency`.
+    pub fn buffer_size(mut self, v: Frames) -> Self { self.0.buffer_size = Some(v); self }
+
+    /// Defaults to the buffer size for playback, so that the stream starts when the buffer is full,
+    /// and to one frame for capture.
+    pub fn start_threshold(mut self, v: Frames) -> Self { self.0.start_threshold = Some(v); self }
+
+    pub fn build(self) -> Config { self.0 }
+}
+
+/// The parameters a PCM was set up with by `Config::apply`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegotiatedConfig {
+    pub access: Access,
+    pub format: Format,
+    pub channels: u32,
+    pub rate: u32,
+    pub period_size: Frames,
+    pub buffer_size: Frames,
+    /// Every setting that differs from the first choice. Empty if the device gave everything asked for.
+    pub compromises: Vec<Compromise>,
+}
+
+impl NegotiatedConfig {
+    /// The duration of the buffer.
+    pub fn latency(&self) -> Duration {
+        Duration::from_micros(self.buffer_size as u64 * 1_000_000 / self.rate as u64)
+    }
+}
+
+fn frames(t: Duration, rate: u32) -> Frames {
+    ((t.as_micros() * rate as u128 + 500_000) / 1_000_000) as Frames
+}
+
+/// Tries each value in order, and returns the first one that could be set.
+fn first_of<T: Copy>(list: &[T], func: &'static str, mut set: impl FnMut(T) -> Result<()>) -> Result<T> {
+    list.iter().copied().find(|&v| set(v).is_ok()).ok_or_else(|| Error::new(func, libc::EINVAL))
+}
+
+impl Config {
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder(Config {
+            formats: Vec::new(), accesses: Vec::new(), channels: Vec::new(), rates: Vec::new(), latency: None,
+            periods: 4, period_size: None, buffer_size: None, start_threshold: None
+        })
+    }
+
+    /// Negotiates and commits hardware and software parameters.
+    ///
+    /// Fails if none of the formats or access types are supported, or if the device rejects the parameters.
+    pub fn apply(&self, pcm: &PCM) -> Result<NegotiatedConfig> {
+        let mut compromises = Vec::new();
+        let hwp = HwParams::any(pcm)?;
+
+        let accesses = if self.accesses.is_empty() { &[Access::RWInterleaved][..] } else { &self.accesses };
+        let access = first_of(accesses, "snd_pcm_hw_params_set_access", |a| hwp.set_access(a))?;
+        if access != accesses[0] { compromises.push(Compromise::Access { wanted: accesses[0], got: access }) }
+
+        let formats = if self.formats.is_empty() { hwp.get_format_mask()? } else { self.formats.clone() };
+        let format = first_of(&formats, "snd_pcm_hw_params_set_format", |f| hwp.set_format(f))?;
+        if !self.formats.is_empty() && format != formats[0] { compromises.push(Compromise::Format { wanted: formats[0], got: format }) }
+
+        if let Some(&wanted) = self.channels.first() {
+            let got = match first_of(&self.channels, "snd_pcm_hw_params_set_channels", |c| hwp.set_channels(c)) {
+                Ok(c) => c,
+                Err(_) => hwp.set_channels_near(wanted)?,
+            };
+            if got != wanted { compromises.push(Compromise::Channels { wanted, got }) }
+        }
+        if let Some(&wanted) = self.rates.first() {
+            let got = match first_of(&self.rates, "snd_pcm_hw_params_set_rate", |r| hwp.set_rate(r, ValueOr::Nearest)) {
+                Ok(r) => r,
+                Err(_) => hwp.set_rate_near(wanted, ValueOr::Nearest)?,
+            };
+            if got != wanted { compromises.push(Compromise::Rate { wanted, got }) }
+        }
+
+        // Like aplay, set the period before the buffer
+        let period_time = self.latency.map(|l| l / self.periods.max(1));
+        if let Some(p) = self.period_size {
+            hwp.set_period_size_near(p, ValueOr::Nearest)?;
+        } else if let Some(t) = period_time {
+            hwp.set_period_time_near(t.as_micros() as u32, ValueOr::Nearest)?;
+        }
+        if let Some(b) = self.buffer_size {
+            hwp.set_buffer_size_near(b)?;
+        } else if let Some(t) = self.latency {
+            hwp.set_buffer_time_near(t.as_micros() as u32, ValueOr::Nearest)?;
+        }
+        pcm.hw_params(&hwp)?;
+
+        let hwp = pcm.hw_params_current()?;
+        let (channels, rate) = (hwp.get_channels()?, hwp.get_rate()?);
+        let (period_size, buffer_size) = (hwp.get_period_size()?, hwp.get_buffer_size()?);
+        let wanted_period = self.period_size.or(period_time.map(|t| frames(t, rate)));
+        let wanted_buffer = self.buffer_size.or(self.latency.map(|t| frames(t, rate)));
+        if let Some(wanted) = wanted_period.filter(|&w| w != period_size) {
+            compromises.push(Compromise::PeriodSize { wanted, got: period_size })
+        }
+        if let Some(wanted) = wanted_buffer.filter(|&w| w != buffer_size) {
+            compromises.push(Compromise::BufferSize { wanted, got: buffer_size })
+        }
+
+        let swp = pcm.sw_params_current()?;
+        let threshold = match self.start_threshold {
+            Some(t) => t,
+            None if pcm.info()?.get_stream() == Direction::Playback => buffer_size,
+            None => 1,
+        };
+        swp.set_start_threshold(threshold)?;
+        swp.set_avail_min(period_size)?;
+        pcm.sw_params(&swp)?;
+
+        Ok(NegotiatedConfig { access, format, channels, rate, period_size, buffer_size, compromises })
+    }
+}
+
+#[test]
+fn negotiate_fallbacks() {
+    use crate::plugin::ioplug::{self, Areas, Handle, HwParam, IoPlug};
+
+    struct Sink { pos: Frames }
+
+    impl IoPlug for Sink {
+        fn hw_constraints(&mut self, io: &mut Handle) -> Result<()> {
+            io.set_access(&[Access::RWInterleaved, Access::MMapInterleaved])?;
+            io.set_formats(&[Format::s16()])?;
+            io.set_param_list(HwParam::Channels, &[2])?;
+            io.set_param_minmax(HwParam::Rate, 8000, 48000)?;
+            io.set_param_minmax(HwParam::PeriodBytes, 256, 65536)?;
+            io.set_param_minmax(HwParam::Periods, 2, 64)
+        }
+        fn start(&mut self, _: &mut Handle) -> Result<()> { Ok(()) }
+        fn stop(&mut self, _: &mut Handle) -> Result<()> { Ok(()) }
+        fn pointer(&mut self, io: &mut Handle) -> Result<Frames> { Ok(self.pos % io.get_buffer_size()) }
+        fn transfer(&mut self, _: &mut Handle, areas: &mut Areas) -> Result<Frames> {
+            self.pos += areas.frames();
+            Ok(areas.frames())
+        }
+    }
+
+    let pcm = ioplug::create("rust_negotiate", Direction::Playback, false, Sink { pos: 0 }).unwrap();
+    let c = Config::builder().format(Format::float()).format(Format::s16()).channels(&[2]).rate(96000)
+        .latency(Duration::from_millis(100)).build();
+    let n = c.apply(&pcm).unwrap();
+    assert_eq!((n.access, n.format, n.channels, n.rate), (Access::RWInterleaved, Format::s16(), 2, 48000));
+    assert_eq!((n.period_size, n.buffer_size), (1200, 4800));
+    assert_eq!(n.latency(), Duration::from_millis(100));
+    assert_eq!(n.compromises, [Compromise::Format { wanted: Format::float(), got: Format::s16() },
+        Compromise::Rate { wanted: 96000, got: 48000 }]);
+    assert_eq!(pcm.sw_params_current().unwrap().get_start_threshold().unwrap(), 4800);
+    pcm.hw_free().unwrap();
+
+    let c = Config::builder().access(Access::MMapNonInterleaved).access(Access::MMapInterleaved).channels(&[6]).build();
+    let n = c.apply(&pcm).unwrap();
+    assert_eq!(n.compromises, [Compromise::Access { wanted: Access::MMapNonInterleaved, got: Access::MMapInterleaved },
+        Compromise::Channels { wanted: 6, got: 2 }]);
+    pcm.hw_free().unwrap();
+
+    // The first supported choice is used, even if another one is nearer to the first choice
+    let n = Config::builder().channels(&[6, 1, 2]).rates(&[96000, 22050, 44100]).build().apply(&pcm).unwrap();
+    assert_eq!((n.channels, n.rate), (2, 22050));
+    assert_eq!(n.compromises, [Compromise::Channels { wanted: 6, got: 2 }, Compromise::Rate { wanted: 96000, got: 22050 }]);
+    pcm.hw_free().unwrap();
+
+    assert!(Config::builder().format(Format::U8).build().apply(&pcm).is_err());
+}
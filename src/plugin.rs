@@ -0,0 +1,137 @@
+//! Write ALSA plugins in Rust.
+//!
+//! alsa-lib loads external plugins from shared libraries named `libasound_module_<type>_<name>.so`,
+//! usually installed in `/usr/lib/alsa-lib`. Build your plugin as a `cdylib` and use the export macro
+//! in the respective module to create the entry point alsa-lib looks for.
+
+pub mod ioplug;
+pub mod extplug;
+pub mod ctl_ext;
+
+mod ffi;
+
+use crate::Direction;
+use crate::alsa;
+use crate::pcm::{Format, Frames, IoFormat};
+use super::error::*;
+use core::marker::PhantomData;
+use core::{mem, slice};
+use libc::{c_int, c_uint};
+
+fn stream_to_dir(stream: alsa::snd_pcm_stream_t) -> Direction {
+    if stream == alsa::SND_PCM_STREAM_CAPTURE { Direction::Capture } else { Direction::Playback }
+}
+
+fn dir_to_stream(dir: Direction) -> alsa::snd_pcm_stream_t {
+    match dir {
+        Direction::Capture => alsa::SND_PCM_STREAM_CAPTURE,
+        Direction::Playback => alsa::SND_PCM_STREAM_PLAYBACK,
+    }
+}
+
+/// Converts an error from a plugin callback to what alsa-lib expects: a negative error code.
+fn err_code(e: Error) -> c_int {
+    let c = e.errno().abs();
+    if c == 0 { -libc::EIO } else { -c }
+}
+
+/// A buffer of audio samples, as handed to the transfer callback of a plugin
+///
+/// Depending on the access type, the samples are either interleaved or non-interleaved, so
+/// either `interleaved` or `channel` returns the samples.
+#[derive(Debug)]
+pub struct Areas<'a> {
+    areas: *const alsa::snd_pcm_channel_area_t,
+    channels: usize,
+    format: Option<Format>,
+    /// Physical sample width, in bits
+    width: usize,
+    offset: usize,
+    frames: Frames,
+    _buf: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> Areas<'a> {
+    unsafe fn new(areas: *const alsa::snd_pcm_channel_area_t, channels: c_uint, format: alsa::snd_pcm_format_t,
+        offset: alsa::snd_pcm_uframes_t, frames: alsa::snd_pcm_uframes_t) -> Areas<'a> {
+        let width = alsa::snd_pcm_format_physical_width(format);
+        Areas {
+            areas, channels: channels as usize, format: Format::from_c_int(format as c_int, "").ok(),
+            width: if width > 0 { width as usize } else { 0 }, offset: offset as usize, frames: frames as Frames,
+            _buf: PhantomData,
+        }
+    }
+
+    /// The number of frames to transfer
+    pub fn frames(&self) -> Frames { self.frames }
+
+    pub fn channels(&self) -> usize { self.channels }
+
+    pub fn format(&self) -> Option<Format> { self.format }
+
+    fn area(&self, ch: usize) -> &alsa::snd_pcm_channel_area_t { unsafe { &*self.areas.add(ch) } }
+
+    fn buf(&self, a: &alsa::snd_pcm_channel_area_t) -> (*mut u8, usize) {
+        let step = a.step as usize;
+        (unsafe { (a.addr as *mut u8).add((a.first as usize + self.offset * step) / 8) }, self.frames as usize * step / 8)
+    }
+
+    fn interleaved_buf(&self) -> Option<(*mut u8, usize)> {
+        if self.width == 0 || self.channels == 0 { return None }
+        let a0 = self.area(0);
+        for ch in 0..self.channels {
+            let a = self.area(ch);
+            if a.addr != a0.addr || a.first as usize != a0.first as usize + ch * self.width ||
+                a.step as usize != self.channels * self.width { return None }
+        }
+        Some(self.buf(a0))
+    }
+
+    fn channel_buf(&self, ch: usize) -> Option<(*mut u8, usize)> {
+        if self.width == 0 || ch >= self.channels { return None }
+        let a = self.area(ch);
+        if a.step as usize != self.width { return None }
+        Some(self.buf(a))
+    }
+
+    fn typed<S: IoFormat>(&self, b: Option<(*mut u8, usize)>) -> Option<(*mut S, usize)> {
+        if self.format != Some(S::FORMAT) || mem::size_of::<S>() * 8 != self.width { return None }
+        b.filter(|&(p, _)| p as usize % mem::align_of::<S>() == 0).map(|(p, len)| (p as *mut S, len / mem::size_of::<S>()))
+    }
+
+    /// All samples, if the buffer is interleaved.
+    pub fn interleaved(&self) -> Option<&[u8]> {
+        self.interleaved_buf().map(|(p, len)| unsafe { slice::from_raw_parts(p, len) })
+    }
+
+    pub fn interleaved_mut(&mut self) -> Option<&mut [u8]> {
+        self.interleaved_buf().map(|(p, len)| unsafe { slice::from_raw_parts_mut(p, len) })
+    }
+
+    /// The samples of one channel, if the buffer is non-interleaved.
+    pub fn channel(&self, ch: usize) -> Option<&[u8]> {
+        self.channel_buf(ch).map(|(p, len)| unsafe { slice::from_raw_parts(p, len) })
+    }
+
+    pub fn channel_mut(&mut self, ch: usize) -> Option<&mut [u8]> {
+        self.channel_buf(ch).map(|(p, len)| unsafe { slice::from_raw_parts_mut(p, len) })
+    }
+
+    /// Like `interleaved`, but returns None unless the samples are of type `S`.
+    pub fn interleaved_as<S: IoFormat>(&self) -> Option<&[S]> {
+        self.typed::<S>(self.interleaved_buf()).map(|(p, len)| unsafe { slice::from_raw_parts(p, len) })
+    }
+
+    pub fn interleaved_as_mut<S: IoFormat>(&mut self) -> Option<&mut [S]> {
+        self.typed::<S>(self.interleaved_buf()).map(|(p, len)| unsafe { slice::from_raw_parts_mut(p, len) })
+    }
+
+    /// Like `channel`, but returns None unless the samples are of type `S`.
+    pub fn channel_as<S: IoFormat>(&self, ch: usize) -> Option<&[S]> {
+        self.typed::<S>(self.channel_buf(ch)).map(|(p, len)| unsafe { slice::from_raw_parts(p, len) })
+    }
+
+    pub fn channel_as_mut<S: IoFormat>(&mut self, ch: usize) -> Option<&mut [S]> {
+        self.typed::<S>(self.channel_buf(ch)).map(|(p, len)| unsafe { slice::from_raw_parts_mut(p, len) })
+    }
+}
@@ -0,0 +1,524 @@
+//! Control plugins - implement virtual mixer controls in Rust
+//!
+//! Implement the `CtlExt` trait, then either create a `Ctl` directly with `create`, or use
+//! `export_ctl_ext!` in a `cdylib` so that any ALSA application (e g `alsamixer`) can open the plugin by name.
+//! See the [External control plugin SDK](https://www.alsa-project.org/alsa-doc/alsa-lib/ctl_external_plugins.html)
+//! for how alsa-lib calls the plugin.
+//!
+//! Elements are identified by a key, which is their offset in the element list unless `find_elem` is overridden.
+//!
+//! # Example
+//!
+//! ```
+//! use alsa::ctl::{ElemId, ElemIface, ElemType, ElemValue};
+//! use alsa::plugin::ctl_ext::{CtlExt, Handle, Access};
+//!
+//! /// A stereo volume control, for a sink that does not have its own mixer.
+//! struct Volume { vol: [i32; 2] }
+//!
+//! impl CtlExt for Volume {
+//!     fn setup(&mut self, ext: &mut Handle) -> alsa::Result<()> {
+//!         ext.set_name("Remote sink");
+//!         Ok(())
+//!     }
+//!     fn elem_count(&mut self) -> u32 { 1 }
+//!     fn elem_list(&mut self, _offset: u32) -> alsa::Result<ElemId> {
+//!         let mut id = ElemId::new(ElemIface::Mixer);
+//!         id.set_name(c"Master Playback Volume");
+//!         Ok(id)
+//!     }
+//!     fn get_attribute(&mut self, _key: u32) -> alsa::Result<(ElemType, Access, u32)> {
+//!         Ok((ElemType::Integer, Access::READWRITE, 2))
+//!     }
+//!     fn get_integer_info(&mut self, _key: u32) -> alsa::Result<(i32, i32, i32)> { Ok((0, 100, 1)) }
+//!     fn read(&mut self, _key: u32, value: &mut ElemValue) -> alsa::Result<()> {
+//!         for (i, &v) in self.vol.iter().enumerate() { value.set_integer(i as u32, v); }
+//!         Ok(())
+//!     }
+//!     fn write(&mut self, _key: u32, value: &ElemValue) -> alsa::Result<bool> {
+//!         let old = self.vol;
+//!         for (i, v) in self.vol.iter_mut().enumerate() { *v = value.get_integer(i as u32).unwrap(); }
+//!         Ok(old != self.vol)
+//!     }
+//! }
+//!
+//! // Built as "libasound_module_ctl_volume.so", this makes control devices of type "volume" available,
+//! // e g by adding `ctl.remote { type volume }` to your .asoundrc.
+//! alsa::export_ctl_ext!(volume, |_name, _conf| Ok(Volume { vol: [100, 100] }));
+//! ```
+
+use super::{ffi, err_code};
+use crate::alsa;
+use crate::config::Node;
+use crate::ctl_int::{self, Ctl, ElemId, ElemType, ElemValue, EventMask};
+use crate::iec958::Iec958Status;
+use crate::error::*;
+use ::alloc::boxed::Box;
+use ::alloc::ffi::CString;
+use core::ffi::CStr;
+use core::marker::PhantomData;
+use core::{cmp, mem, ptr};
+use libc::{c_char, c_int, c_long, c_uint, c_void, size_t};
+
+bitflags! {
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    /// [SND_CTL_EXT_ACCESS_xxx](https://www.alsa-project.org/alsa-doc/alsa-lib/group___c_t_l___plugin___s_d_k.html) constants
+    pub struct Access: u32 {
+        const READ = 1<<0;
+        const WRITE = 1<<1;
+        const READWRITE = 3<<0;
+        const VOLATILE = 1<<2;
+        const INACTIVE = 1<<8;
+    }
+}
+
+/// Callbacks of a control plugin
+///
+/// The plugin is dropped when the control device is closed.
+pub trait CtlExt: Send + 'static {
+    /// Sets up card information and the poll descriptor. Called before the control device is created.
+    fn setup(&mut self, _ext: &mut Handle) -> Result<()> { Ok(()) }
+
+    /// Returns the number of elements.
+    fn elem_count(&mut self) -> u32;
+
+    /// Returns the id of the element at `offset` in the element list. The numid is filled in by alsa-lib.
+    fn elem_list(&mut self, offset: u32) -> Result<ElemId>;
+
+    /// Returns the key of the element with this id, or None if there is no such element.
+    ///
+    /// The default implementation returns the element's offset in the element list.
+    fn find_elem(&mut self, id: &ElemId) -> Option<u32> {
+        // alsa-lib numbers the elements from one, in list order
+        let numid = id.get_numid();
+        if numid > 0 { return if numid <= self.elem_count() { Some(numid - 1) } else { None } }
+        (0..self.elem_count()).find(|&i| self.elem_list(i).map(|e| {
+            e.get_interface() == id.get_interface() && e.get_name().ok() == id.get_name().ok() &&
+            e.get_index() == id.get_index() && e.get_device() == id.get_device() && e.get_subdevice() == id.get_subdevice()
+        }).unwrap_or(false))
+    }
+
+    /// Returns type, access and number of values of an element.
+    fn get_attribute(&mut self, key: u32) -> Result<(ElemType, Access, u32)>;
+
+    /// Returns minimum, maximum and step for an element of type `ElemType::Integer`.
+    fn get_integer_info(&mut self, _key: u32) -> Result<(i32, i32, i32)> { Err(Error::unsupported("get_integer_info")) }
+
+    /// Returns minimum, maximum and step for an element of type `ElemType::Integer64`.
+    fn get_integer64_info(&mut self, _key: u32) -> Result<(i64, i64, i64)> { Err(Error::unsupported("get_integer64_info")) }
+
+    /// Returns the number of items of an element of type `ElemType::Enumerated`.
+    fn get_enumerated_info(&mut self, _key: u32) -> Result<u32> { Err(Error::unsupported("get_enumerated_info")) }
+
+    fn get_enumerated_name(&mut self, _key: u32, _item: u32) -> Result<&str> { Err(Error::unsupported("get_enumerated_name")) }
+
+    /// Fills in the current values of an element.
+    ///
+    /// `value` is of the type returned by `get_attribute`.
+    fn read(&mut self, key: u32, value: &mut ElemValue) -> Result<()>;
+
+    /// Changes the values of an element, and returns whether they changed.
+    fn write(&mut self, _key: u32, _value: &ElemValue) -> Result<bool> { Err(Error::unsupported("write")) }
+
+    fn subscribe_events(&mut self, _ext: &mut Handle, _subscribe: bool) {}
+
+    /// Returns the next pending event, if any.
+    ///
+    /// Called when the poll descriptor is readable, if the application subscribed to events.
+    fn read_event(&mut self, _ext: &mut Handle) -> Result<Option<(ElemId, EventMask)>> { Ok(None) }
+}
+
+/// [snd_ctl_ext_t](https://www.alsa-project.org/alsa-doc/alsa-lib/group___c_t_l___plugin___s_d_k.html) wrapper
+///
+/// Gives plugin callbacks access to the state of the control device.
+#[derive(Debug)]
+pub struct Handle<'a>(*mut ffi::snd_ctl_ext_t, PhantomData<&'a mut ()>);
+
+fn set_str(dst: &mut [c_char], s: &str) {
+    let len = cmp::min(s.len(), dst.len() - 1);
+    for (d, &b) in dst.iter_mut().zip(&s.as_bytes()[..len]) { *d = b as c_char }
+    dst[len] = 0;
+}
+
+impl<'a> Handle<'a> {
+    pub fn get_nonblock(&self) -> bool { unsafe { (*self.0).nonblock != 0 } }
+    pub fn get_subscribed(&self) -> bool { unsafe { (*self.0).subscribed != 0 } }
+
+    /// Sets the card number reported in `CardInfo`.
+    pub fn set_card(&mut self, card: i32) { unsafe { (*self.0).card_idx = card } }
+    pub fn set_id(&mut self, s: &str) { set_str(unsafe { &mut (*self.0).id }, s) }
+    pub fn set_driver(&mut self, s: &str) { set_str(unsafe { &mut (*self.0).driver }, s) }
+    pub fn set_name(&mut self, s: &str) { set_str(unsafe { &mut (*self.0).name }, s) }
+    pub fn set_longname(&mut self, s: &str) { set_str(unsafe { &mut (*self.0).longname }, s) }
+    pub fn set_mixername(&mut self, s: &str) { set_str(unsafe { &mut (*self.0).mixername }, s) }
+
+    /// Sets the file descriptor applications poll for events. It should become readable when
+    /// `read_event` has an event to return.
+    pub fn set_poll_fd(&mut self, fd: c_int) { unsafe { (*self.0).poll_fd = fd } }
+}
+
+// The ext struct given to alsa-lib, which must stay at the same address, is part of this allocation.
+struct Inner<P> {
+    ext: ffi::snd_ctl_ext_t,
+    callback: ffi::snd_ctl_ext_callback_t,
+    plugin: P,
+}
+
+unsafe fn from_ext<'a, P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t) -> (&'a mut P, Handle<'a>) {
+    let inner = (*ext).private_data as *mut Inner<P>;
+    (&mut (*inner).plugin, Handle(ext, PhantomData))
+}
+
+unsafe extern "C" fn cb_close<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t) {
+    drop(Box::from_raw((*ext).private_data as *mut Inner<P>));
+}
+
+unsafe extern "C" fn cb_elem_count<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t) -> c_int {
+    from_ext::<P>(ext).0.elem_count() as c_int
+}
+
+unsafe extern "C" fn cb_elem_list<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, offset: c_uint, id: *mut alsa::snd_ctl_elem_id_t) -> c_int {
+    match from_ext::<P>(ext).0.elem_list(offset as u32) {
+        Ok(e) => { alsa::snd_ctl_elem_id_copy(id, ctl_int::elem_id_ptr(&e)); 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+unsafe extern "C" fn cb_find_elem<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, id: *const alsa::snd_ctl_elem_id_t) -> ffi::snd_ctl_ext_key_t {
+    let e = match ctl_int::elem_id_new() { Ok(e) => e, Err(_) => return ffi::SND_CTL_EXT_KEY_NOT_FOUND };
+    alsa::snd_ctl_elem_id_copy(ctl_int::elem_id_ptr(&e), id);
+    from_ext::<P>(ext).0.find_elem(&e).map_or(ffi::SND_CTL_EXT_KEY_NOT_FOUND, |k| k as ffi::snd_ctl_ext_key_t)
+}
+
+unsafe extern "C" fn cb_get_attribute<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t,
+    type_: *mut c_int, acc: *mut c_uint, count: *mut c_uint) -> c_int {
+    match from_ext::<P>(ext).0.get_attribute(key as u32) {
+        Ok((t, _, c)) if c > max_count(t) => -libc::EINVAL,
+        Ok((t, a, c)) => { *type_ = t as c_int; *acc = a.bits() as c_uint; *count = c as c_uint; 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+unsafe extern "C" fn cb_get_integer_info<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t,
+    imin: *mut c_long, imax: *mut c_long, istep: *mut c_long) -> c_int {
+    match from_ext::<P>(ext).0.get_integer_info(key as u32) {
+        Ok((min, max, step)) => { *imin = min as c_long; *imax = max as c_long; *istep = step as c_long; 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+unsafe extern "C" fn cb_get_integer64_info<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t,
+    imin: *mut i64, imax: *mut i64, istep: *mut i64) -> c_int {
+    match from_ext::<P>(ext).0.get_integer64_info(key as u32) {
+        Ok((min, max, step)) => { *imin = min; *imax = max; *istep = step; 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+unsafe extern "C" fn cb_get_enumerated_info<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t,
+    items: *mut c_uint) -> c_int {
+    match from_ext::<P>(ext).0.get_enumerated_info(key as u32) {
+        Ok(n) => { *items = n as c_uint; 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+unsafe extern "C" fn cb_get_enumerated_name<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t,
+    item: c_uint, name: *mut c_char, name_max_len: size_t) -> c_int {
+    if name_max_len == 0 { return -libc::EINVAL }
+    match from_ext::<P>(ext).0.get_enumerated_name(key as u32, item as u32) {
+        Ok(s) => { set_str(core::slice::from_raw_parts_mut(name, name_max_len), s); 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+/// How many values alsa-lib has room for in the `snd_ctl_elem_value_t` passed to the read and write callbacks.
+fn max_count(t: ElemType) -> u32 {
+    match t {
+        ElemType::Boolean | ElemType::Integer | ElemType::Enumerated => 128,
+        ElemType::Integer64 => 64,
+        ElemType::Bytes => 512,
+        _ => 1,
+    }
+}
+
+/// Reads an element into a temporary ElemValue, then hands the values to `f`.
+unsafe fn read_value<P: CtlExt, F: FnMut(&ElemValue, u32)>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, mut f: F) -> c_int {
+    let p = from_ext::<P>(ext).0;
+    let r = p.get_attribute(key as u32).and_then(|(t, _, count)| {
+        let mut v = ElemValue::new(t)?;
+        p.read(key as u32, &mut v)?;
+        for i in 0..count.min(max_count(t)) { f(&v, i) };
+        Ok(())
+    });
+    r.map_or_else(err_code, |_| 0)
+}
+
+/// Creates a temporary ElemValue, lets `f` fill it in, and writes it to the element.
+unsafe fn write_value<P: CtlExt, F: FnMut(&mut ElemValue, u32)>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, mut f: F) -> c_int {
+    let p = from_ext::<P>(ext).0;
+    let r = p.get_attribute(key as u32).and_then(|(t, _, count)| {
+        let mut v = ElemValue::new(t)?;
+        for i in 0..count.min(max_count(t)) { f(&mut v, i) };
+        p.write(key as u32, &v)
+    });
+    r.map_or_else(err_code, |changed| changed as c_int)
+}
+
+// Boolean elements are read and written through the integer callbacks.
+unsafe extern "C" fn cb_read_integer<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, value: *mut c_long) -> c_int {
+    read_value::<P, _>(ext, key, |v, i| *value.add(i as usize) =
+        v.get_integer(i).map(|x| x as c_long).or_else(|| v.get_boolean(i).map(|x| x as c_long)).unwrap_or(0))
+}
+
+unsafe extern "C" fn cb_read_integer64<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, value: *mut i64) -> c_int {
+    read_value::<P, _>(ext, key, |v, i| *value.add(i as usize) = v.get_integer64(i).unwrap_or(0))
+}
+
+unsafe extern "C" fn cb_read_enumerated<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, items: *mut c_uint) -> c_int {
+    read_value::<P, _>(ext, key, |v, i| *items.add(i as usize) = v.get_enumerated(i).unwrap_or(0) as c_uint)
+}
+
+unsafe extern "C" fn cb_read_bytes<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, data: *mut u8, max_bytes: size_t) -> c_int {
+    read_value::<P, _>(ext, key, |v, i| if (i as usize) < max_bytes { *data.add(i as usize) = v.get_byte(i).unwrap_or(0) })
+}
+
+unsafe extern "C" fn cb_read_iec958<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, iec958: *mut alsa::snd_aes_iec958_t) -> c_int {
+    read_value::<P, _>(ext, key, |v, _| if let Some(s) = v.get_iec958() { (*iec958).status = s.0 })
+}
+
+unsafe extern "C" fn cb_write_integer<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, value: *mut c_long) -> c_int {
+    write_value::<P, _>(ext, key, |v, i| {
+        let x = *value.add(i as usize);
+        if v.set_integer(i, x as i32).is_none() { v.set_boolean(i, x != 0); }
+    })
+}
+
+unsafe extern "C" fn cb_write_integer64<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, value: *mut i64) -> c_int {
+    write_value::<P, _>(ext, key, |v, i| { v.set_integer64(i, *value.add(i as usize)); })
+}
+
+unsafe extern "C" fn cb_write_enumerated<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, items: *mut c_uint) -> c_int {
+    write_value::<P, _>(ext, key, |v, i| { v.set_enumerated(i, *items.add(i as usize) as u32); })
+}
+
+unsafe extern "C" fn cb_write_bytes<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, data: *mut u8, max_bytes: size_t) -> c_int {
+    write_value::<P, _>(ext, key, |v, i| if (i as usize) < max_bytes { v.set_byte(i, *data.add(i as usize)); })
+}
+
+unsafe extern "C" fn cb_write_iec958<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, key: ffi::snd_ctl_ext_key_t, iec958: *mut alsa::snd_aes_iec958_t) -> c_int {
+    write_value::<P, _>(ext, key, |v, _| { v.set_iec958(&Iec958Status((*iec958).status)); })
+}
+
+unsafe extern "C" fn cb_subscribe_events<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, subscribe: c_int) {
+    let (p, mut h) = from_ext::<P>(ext);
+    p.subscribe_events(&mut h, subscribe != 0)
+}
+
+unsafe extern "C" fn cb_read_event<P: CtlExt>(ext: *mut ffi::snd_ctl_ext_t, id: *mut alsa::snd_ctl_elem_id_t,
+    event_mask: *mut c_uint) -> c_int {
+    let (p, mut h) = from_ext::<P>(ext);
+    match p.read_event(&mut h) {
+        Ok(Some((e, mask))) => {
+            alsa::snd_ctl_elem_id_copy(id, ctl_int::elem_id_ptr(&e));
+            *event_mask = mask.0 as c_uint;
+            1
+        },
+        Ok(None) => -libc::EAGAIN,
+        Err(e) => err_code(e),
+    }
+}
+
+fn create_raw<P: CtlExt>(name: &CStr, mode: c_int, plugin: P) -> Result<*mut alsa::snd_ctl_t> {
+    let callback = ffi::snd_ctl_ext_callback_t {
+        close: Some(cb_close::<P>),
+        elem_count: Some(cb_elem_count::<P>),
+        elem_list: Some(cb_elem_list::<P>),
+        find_elem: Some(cb_find_elem::<P>),
+        free_key: None,
+        get_attribute: Some(cb_get_attribute::<P>),
+        get_integer_info: Some(cb_get_integer_info::<P>),
+        get_integer64_info: Some(cb_get_integer64_info::<P>),
+        get_enumerated_info: Some(cb_get_enumerated_info::<P>),
+        get_enumerated_name: Some(cb_get_enumerated_name::<P>),
+        read_integer: Some(cb_read_integer::<P>),
+        read_integer64: Some(cb_read_integer64::<P>),
+        read_enumerated: Some(cb_read_enumerated::<P>),
+        read_bytes: Some(cb_read_bytes::<P>),
+        read_iec958: Some(cb_read_iec958::<P>),
+        write_integer: Some(cb_write_integer::<P>),
+        write_integer64: Some(cb_write_integer64::<P>),
+        write_enumerated: Some(cb_write_enumerated::<P>),
+        write_bytes: Some(cb_write_bytes::<P>),
+        write_iec958: Some(cb_write_iec958::<P>),
+        subscribe_events: Some(cb_subscribe_events::<P>),
+        read_event: Some(cb_read_event::<P>),
+        poll_descriptors_count: None,
+        poll_descriptors: None,
+        poll_revents: None,
+    };
+    let inner = Box::into_raw(Box::new(Inner { ext: unsafe { mem::zeroed() }, callback, plugin }));
+    let ext = unsafe {
+        let ext = ptr::addr_of_mut!((*inner).ext);
+        (*ext).version = ffi::SND_CTL_EXT_VERSION;
+        (*ext).poll_fd = -1;
+        (*ext).callback = ptr::addr_of!((*inner).callback);
+        (*ext).private_data = inner as *mut c_void;
+        ext
+    };
+    let (p, mut h) = unsafe { from_ext::<P>(ext) };
+    let s = name.to_str().unwrap_or("");
+    h.set_id(s);
+    h.set_driver(s);
+    h.set_name(s);
+    h.set_longname(s);
+    h.set_mixername(s);
+    if let Err(e) = p.setup(&mut h).and_then(|_| acheck!(ffi::snd_ctl_ext_create(ext, name.as_ptr(), mode))) {
+        drop(unsafe { Box::from_raw(inner) });
+        return Err(e);
+    }
+    Ok(unsafe { (*ext).handle })
+}
+
+/// Creates a new control device, whose elements are implemented by `plugin`.
+pub fn create<P: CtlExt>(name: &str, nonblock: bool, plugin: P) -> Result<Ctl> {
+    let mode = if nonblock { 1 } else { 0 }; // FIXME: alsa::SND_CTL_NONBLOCK does not exist in alsa-sys
+    create_raw(&CString::new(name).unwrap(), mode, plugin).map(ctl_int::ctl_from_ptr)
+}
+
+#[doc(hidden)]
+/// Called from the entry point generated by `export_ctl_ext!`.
+pub unsafe fn open_entry<P, F>(handlep: *mut *mut c_void, name: *const c_char, _root: *mut c_void, conf: *mut c_void,
+    mode: c_int, f: F) -> c_int
+where P: CtlExt, F: FnOnce(&str, &Node) -> Result<P> {
+    let r = from_const("_snd_ctl_open", name).and_then(|n| {
+        let p = f(n, Node::from_ptr(conf as *mut alsa::snd_config_t))?;
+        create_raw(CStr::from_ptr(name), mode, p)
+    });
+    match r {
+        Ok(ctl) => { *handlep = ctl as *mut c_void; 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+/// Exports a control plugin from a `cdylib`.
+///
+/// `export_ctl_ext!(foo, open)` creates the `_snd_ctl_foo_open` entry point which alsa-lib
+/// looks for in `libasound_module_ctl_foo.so`, when opening a control device of type `foo`.
+///
+/// `open` is called as `open(name: &str, conf: &config::Node) -> Result<impl CtlExt>`,
+/// where `conf` is the control device's definition in the configuration tree.
+#[macro_export]
+macro_rules! export_ctl_ext {
+    ($name: ident, $open: expr) => {
+        const _: () = {
+            #[export_name = concat!("_snd_ctl_", stringify!($name), "_open")]
+            pub unsafe extern "C" fn open(handlep: *mut *mut ::core::ffi::c_void, name: *const ::core::ffi::c_char,
+                root: *mut ::core::ffi::c_void, conf: *mut ::core::ffi::c_void,
+                mode: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                $crate::plugin::ctl_ext::open_entry(handlep, name, root, conf, mode, $open)
+            }
+
+            // alsa-lib checks that this symbol exists, to verify the plugin API version
+            #[export_name = concat!("__snd_ctl_", stringify!($name), "_open_dlsym_control_001")]
+            pub static DLSYM_VERSION: u8 = 0;
+        };
+    }
+}
+
+#[test]
+fn ctl_ext_elems() {
+    use ::alloc::vec::Vec;
+    use crate::ctl::ElemIface;
+
+    struct Mixer { vol: [i32; 2], mute: bool, events: Vec<u32> }
+
+    impl CtlExt for Mixer {
+        fn setup(&mut self, ext: &mut Handle) -> Result<()> {
+            ext.set_name("Rust mixer");
+            Ok(())
+        }
+        fn elem_count(&mut self) -> u32 { 2 }
+        fn elem_list(&mut self, offset: u32) -> Result<ElemId> {
+            let mut id = ElemId::new(ElemIface::Mixer);
+            id.set_name(if offset == 0 { c"Master Playback Volume" } else { c"Master Playback Switch" });
+            Ok(id)
+        }
+        fn get_attribute(&mut self, key: u32) -> Result<(ElemType, Access, u32)> {
+            Ok(if key == 0 { (ElemType::Integer, Access::READWRITE, 2) } else { (ElemType::Boolean, Access::READWRITE, 1) })
+        }
+        fn get_integer_info(&mut self, _: u32) -> Result<(i32, i32, i32)> { Ok((0, 100, 1)) }
+        fn read(&mut self, key: u32, value: &mut ElemValue) -> Result<()> {
+            if key == 0 {
+                value.set_integer(0, self.vol[0]);
+                value.set_integer(1, self.vol[1]);
+            } else { value.set_boolean(0, !self.mute); }
+            Ok(())
+        }
+        fn write(&mut self, key: u32, value: &ElemValue) -> Result<bool> {
+            if key != 0 { return Err(Error::unsupported("write")) }
+            let old = self.vol;
+            self.vol = [value.get_integer(0).unwrap(), value.get_integer(1).unwrap()];
+            if old != self.vol { self.events.push(key) };
+            Ok(old != self.vol)
+        }
+        fn read_event(&mut self, ext: &mut Handle) -> Result<Option<(ElemId, EventMask)>> {
+            assert!(ext.get_subscribed());
+            if self.events.is_empty() { return Ok(None) }
+            let key = self.events.remove(0);
+            Ok(Some((self.elem_list(key)?, EventMask(1))))
+        }
+    }
+
+    let ctl = create("rust_ctl_ext", false, Mixer { vol: [30, 40], mute: false, events: Vec::new() }).unwrap();
+    assert_eq!(ctl.card_info().unwrap().get_name().unwrap(), "Rust mixer");
+    let list = ctl.elem_list().unwrap();
+    assert_eq!(list.get_used(), 2);
+    assert_eq!(list.get_name(1).unwrap(), "Master Playback Switch");
+    assert_eq!(list.get_numid(1).unwrap(), 2);
+
+    let mut v = ElemValue::new(ElemType::Integer).unwrap();
+    v.set_id(&list.get_id(0).unwrap());
+    ctl.elem_read(&mut v).unwrap();
+    assert_eq!((v.get_integer(0), v.get_integer(1)), (Some(30), Some(40)));
+
+    let mut s = ElemValue::new(ElemType::Boolean).unwrap();
+    s.set_id(&list.get_id(1).unwrap());
+    ctl.elem_read(&mut s).unwrap();
+    assert_eq!(s.get_boolean(0), Some(true));
+    assert!(ctl.elem_write(&s).is_err());
+
+    ctl.subscribe_events(true).unwrap();
+    v.set_integer(1, 50);
+    ctl.elem_write(&v).unwrap();
+    let ev = ctl.read().unwrap().unwrap();
+    assert_eq!(ev.get_id().get_name().unwrap(), "Master Playback Volume");
+    assert!(ev.get_mask().value());
+    assert!(ctl.read().is_err());
+}
+
+#[test]
+fn ctl_ext_oversized_count() {
+    use crate::ctl::ElemIface;
+
+    struct Huge;
+
+    impl CtlExt for Huge {
+        fn elem_count(&mut self) -> u32 { 1 }
+        fn elem_list(&mut self, _: u32) -> Result<ElemId> {
+            let mut id = ElemId::new(ElemIface::Mixer);
+            id.set_name(c"Huge");
+            Ok(id)
+        }
+        fn get_attribute(&mut self, _: u32) -> Result<(ElemType, Access, u32)> { Ok((ElemType::Integer64, Access::READWRITE, 1000)) }
+        fn read(&mut self, _: u32, _: &mut ElemValue) -> Result<()> { Ok(()) }
+    }
+
+    let ctl = create("rust_ctl_huge", false, Huge).unwrap();
+    let id = ctl.elem_list().unwrap().get_id(0).unwrap();
+    let mut v = ElemValue::new(ElemType::Integer64).unwrap();
+    v.set_id(&id);
+    assert_eq!(ctl.elem_read(&mut v).unwrap_err().errno(), libc::EINVAL);
+    assert_eq!(ctl.elem_write(&v).unwrap_err().errno(), libc::EINVAL);
+}
@@ -0,0 +1,342 @@
+//! PCM filter plugins - process audio on its way to or from a slave PCM
+//!
+//! Implement the `ExtPlug` trait, then either create a `PCM` directly with `create`, or use
+//! `export_extplug!` in a `cdylib` so that any ALSA application can open the plugin by name.
+//! See the [External filter plugin SDK](https://www.alsa-project.org/alsa-doc/alsa-lib/pcm_external_plugins.html)
+//! for how alsa-lib calls the plugin.
+//!
+//! # Example
+//!
+//! ```
+//! use alsa::pcm::{Format, Frames};
+//! use alsa::plugin::extplug::{ExtPlug, Handle, Areas, HwParam};
+//!
+//! /// Attenuates the signal by 6 dB.
+//! struct Halve;
+//!
+//! impl ExtPlug for Halve {
+//!     fn hw_constraints(&mut self, ext: &mut Handle) -> alsa::Result<()> {
+//!         ext.set_formats(&[Format::s16()])?;
+//!         ext.set_slave_formats(&[Format::s16()])
+//!     }
+//!     fn transfer(&mut self, _: &mut Handle, dst: &mut Areas, src: &Areas) -> alsa::Result<Frames> {
+//!         let s = src.interleaved_as::<i16>().unwrap();
+//!         let d = dst.interleaved_as_mut::<i16>().unwrap();
+//!         for (d, s) in d.iter_mut().zip(s) { *d = *s / 2 }
+//!         Ok(src.frames())
+//!     }
+//! }
+//!
+//! // Built as "libasound_module_pcm_halve.so", this makes PCMs of type "halve" available,
+//! // e g by adding `pcm.quiet { type halve slave.pcm "default" }` to your .asoundrc.
+//! alsa::export_extplug!(halve, |_name, _conf, _dir| Ok(Halve));
+//! ```
+
+use super::{ffi, err_code, stream_to_dir, dir_to_stream};
+pub use super::Areas;
+use crate::alsa;
+use crate::chmap;
+use crate::config::Node;
+use crate::error::*;
+use crate::pcm::{self, PCM, Format, Frames, Chmap, ChmapType};
+use crate::Direction;
+use ::alloc::boxed::Box;
+use ::alloc::ffi::CString;
+use ::alloc::vec::Vec;
+use core::ffi::CStr;
+use core::marker::PhantomData;
+use core::{mem, ptr};
+use libc::{c_char, c_int, c_uint, c_void};
+
+/// Callbacks of a PCM filter plugin
+///
+/// The plugin is dropped when the PCM is closed.
+pub trait ExtPlug: Send + 'static {
+    /// Set to true if the plugin implements `query_chmaps`, `get_chmap` and `set_chmap`,
+    /// otherwise channel maps are forwarded to the slave.
+    const CHMAP: bool = false;
+
+    /// Sets up the supported formats and channels, for the client and the slave side.
+    ///
+    /// Called once, right after the PCM has been created. Parameters that are not constrained
+    /// are linked, i e, the same on both sides.
+    fn hw_constraints(&mut self, _ext: &mut Handle) -> Result<()> { Ok(()) }
+
+    /// Processes `src` into `dst`, and returns the number of frames processed.
+    ///
+    /// For playback, `src` is the application's buffer and `dst` the slave's buffer, for capture it is the other way around.
+    fn transfer(&mut self, ext: &mut Handle, dst: &mut Areas, src: &Areas) -> Result<Frames>;
+
+    /// Called when the hardware parameters have been chosen; they can be read from `ext`.
+    fn hw_params(&mut self, _ext: &mut Handle) -> Result<()> { Ok(()) }
+
+    fn hw_free(&mut self, _ext: &mut Handle) -> Result<()> { Ok(()) }
+
+    /// Called when the PCM is prepared, e g to reset filter state.
+    fn init(&mut self, _ext: &mut Handle) -> Result<()> { Ok(()) }
+
+    fn query_chmaps(&mut self, _ext: &mut Handle) -> Vec<(ChmapType, Chmap)> { Vec::new() }
+
+    fn get_chmap(&mut self, _ext: &mut Handle) -> Result<Chmap> { Err(Error::unsupported("get_chmap")) }
+
+    fn set_chmap(&mut self, _ext: &mut Handle, _map: &Chmap) -> Result<()> { Err(Error::unsupported("set_chmap")) }
+}
+
+/// Hardware parameters that can be constrained in `ExtPlug::hw_constraints`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HwParam {
+    Format = ffi::SND_PCM_EXTPLUG_HW_FORMAT as isize,
+    Channels = ffi::SND_PCM_EXTPLUG_HW_CHANNELS as isize,
+}
+
+/// [snd_pcm_extplug_t](https://www.alsa-project.org/alsa-doc/alsa-lib/group___plugin___s_d_k___e_x_t.html) wrapper
+///
+/// Gives plugin callbacks access to the current parameters of the PCM.
+#[derive(Debug)]
+pub struct Handle<'a>(*mut ffi::snd_pcm_extplug_t, PhantomData<&'a mut ()>);
+
+impl<'a> Handle<'a> {
+    pub fn get_stream(&self) -> Direction { stream_to_dir(unsafe { (*self.0).stream }) }
+    pub fn get_format(&self) -> Result<Format> { Format::from_c_int(unsafe { (*self.0).format } as c_int, "snd_pcm_extplug_t.format") }
+    pub fn get_channels(&self) -> u32 { unsafe { (*self.0).channels as u32 } }
+    pub fn get_rate(&self) -> u32 { unsafe { (*self.0).rate as u32 } }
+    pub fn get_slave_format(&self) -> Result<Format> {
+        Format::from_c_int(unsafe { (*self.0).slave_format } as c_int, "snd_pcm_extplug_t.slave_format")
+    }
+    pub fn get_slave_channels(&self) -> u32 { unsafe { (*self.0).slave_channels as u32 } }
+
+    pub fn set_param_minmax(&mut self, p: HwParam, min: u32, max: u32) -> Result<()> {
+        acheck!(ffi::snd_pcm_extplug_set_param_minmax(self.0, p as c_int, min as c_uint, max as c_uint)).map(|_| ())
+    }
+
+    pub fn set_param_list(&mut self, p: HwParam, list: &[u32]) -> Result<()> {
+        acheck!(ffi::snd_pcm_extplug_set_param_list(self.0, p as c_int, list.len() as c_uint, list.as_ptr())).map(|_| ())
+    }
+
+    pub fn set_slave_param_minmax(&mut self, p: HwParam, min: u32, max: u32) -> Result<()> {
+        acheck!(ffi::snd_pcm_extplug_set_slave_param_minmax(self.0, p as c_int, min as c_uint, max as c_uint)).map(|_| ())
+    }
+
+    pub fn set_slave_param_list(&mut self, p: HwParam, list: &[u32]) -> Result<()> {
+        acheck!(ffi::snd_pcm_extplug_set_slave_param_list(self.0, p as c_int, list.len() as c_uint, list.as_ptr())).map(|_| ())
+    }
+
+    /// Wrapper around `set_param_list` for `HwParam::Format`
+    pub fn set_formats(&mut self, list: &[Format]) -> Result<()> {
+        let v: Vec<u32> = list.iter().map(|f| f.to_c_int() as u32).collect();
+        self.set_param_list(HwParam::Format, &v)
+    }
+
+    /// Wrapper around `set_slave_param_list` for `HwParam::Format`
+    pub fn set_slave_formats(&mut self, list: &[Format]) -> Result<()> {
+        let v: Vec<u32> = list.iter().map(|f| f.to_c_int() as u32).collect();
+        self.set_slave_param_list(HwParam::Format, &v)
+    }
+
+    /// Sets whether the parameter must be the same on the client and the slave side.
+    pub fn set_param_link(&mut self, p: HwParam, keep_link: bool) -> Result<()> {
+        acheck!(ffi::snd_pcm_extplug_set_param_link(self.0, p as c_int, keep_link as c_int)).map(|_| ())
+    }
+
+    /// Removes all constraints set by the `set_*param*` functions.
+    pub fn params_reset(&mut self) { unsafe { ffi::snd_pcm_extplug_params_reset(self.0) } }
+}
+
+// The extplug struct given to alsa-lib, which must stay at the same address, is part of this allocation.
+struct Inner<P> {
+    ext: ffi::snd_pcm_extplug_t,
+    callback: ffi::snd_pcm_extplug_callback_t,
+    name: CString,
+    plugin: P,
+}
+
+unsafe fn from_ext<'a, P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t) -> (&'a mut P, Handle<'a>) {
+    let inner = (*ext).private_data as *mut Inner<P>;
+    (&mut (*inner).plugin, Handle(ext, PhantomData))
+}
+
+unsafe extern "C" fn cb_transfer<P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t,
+    dst_areas: *const alsa::snd_pcm_channel_area_t, dst_offset: alsa::snd_pcm_uframes_t,
+    src_areas: *const alsa::snd_pcm_channel_area_t, src_offset: alsa::snd_pcm_uframes_t,
+    size: alsa::snd_pcm_uframes_t) -> alsa::snd_pcm_sframes_t {
+    let client = ((*ext).channels, (*ext).format);
+    let slave = ((*ext).slave_channels, (*ext).slave_format);
+    let (d, s) = if (*ext).stream == alsa::SND_PCM_STREAM_PLAYBACK { (slave, client) } else { (client, slave) };
+    let mut dst = Areas::new(dst_areas, d.0, d.1, dst_offset, size);
+    let src = Areas::new(src_areas, s.0, s.1, src_offset, size);
+    let (p, mut h) = from_ext::<P>(ext);
+    p.transfer(&mut h, &mut dst, &src).map_or_else(|e| err_code(e) as alsa::snd_pcm_sframes_t, |x| x as alsa::snd_pcm_sframes_t)
+}
+
+unsafe extern "C" fn cb_close<P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t) -> c_int {
+    drop(Box::from_raw((*ext).private_data as *mut Inner<P>));
+    0
+}
+
+unsafe extern "C" fn cb_hw_params<P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t, _: *mut alsa::snd_pcm_hw_params_t) -> c_int {
+    let (p, mut h) = from_ext::<P>(ext);
+    p.hw_params(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_hw_free<P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t) -> c_int {
+    let (p, mut h) = from_ext::<P>(ext);
+    p.hw_free(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_init<P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t) -> c_int {
+    let (p, mut h) = from_ext::<P>(ext);
+    p.init(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_query_chmaps<P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t) -> *mut *mut alsa::snd_pcm_chmap_query_t {
+    let (p, mut h) = from_ext::<P>(ext);
+    chmap::chmaps_query_into_raw(&p.query_chmaps(&mut h))
+}
+
+unsafe extern "C" fn cb_get_chmap<P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t) -> *mut alsa::snd_pcm_chmap_t {
+    let (p, mut h) = from_ext::<P>(ext);
+    p.get_chmap(&mut h).map_or(ptr::null_mut(), chmap::chmap_into_raw)
+}
+
+unsafe extern "C" fn cb_set_chmap<P: ExtPlug>(ext: *mut ffi::snd_pcm_extplug_t, map: *const alsa::snd_pcm_chmap_t) -> c_int {
+    let (p, mut h) = from_ext::<P>(ext);
+    p.set_chmap(&mut h, &chmap::chmap_ref(map as *mut _)).map_or_else(err_code, |_| 0)
+}
+
+fn create_raw<P: ExtPlug>(name: &CStr, root: &Node, slave: &Node, stream: alsa::snd_pcm_stream_t, mode: c_int,
+    plugin: P) -> Result<*mut alsa::snd_pcm_t> {
+    let callback = ffi::snd_pcm_extplug_callback_t {
+        transfer: Some(cb_transfer::<P>),
+        close: Some(cb_close::<P>),
+        hw_params: Some(cb_hw_params::<P>),
+        hw_free: Some(cb_hw_free::<P>),
+        dump: None,
+        init: Some(cb_init::<P>),
+        query_chmaps: if P::CHMAP { Some(cb_query_chmaps::<P>) } else { None },
+        get_chmap: if P::CHMAP { Some(cb_get_chmap::<P>) } else { None },
+        set_chmap: if P::CHMAP { Some(cb_set_chmap::<P>) } else { None },
+    };
+    let inner = Box::into_raw(Box::new(Inner { ext: unsafe { mem::zeroed() }, callback, name: name.into(), plugin }));
+    let ext = unsafe {
+        let ext = ptr::addr_of_mut!((*inner).ext);
+        (*ext).version = ffi::SND_PCM_EXTPLUG_VERSION;
+        (*ext).name = (*inner).name.as_ptr();
+        (*ext).callback = ptr::addr_of!((*inner).callback);
+        (*ext).private_data = inner as *mut c_void;
+        ext
+    };
+    if let Err(e) = acheck!(ffi::snd_pcm_extplug_create(ext, name.as_ptr(), root.as_ptr(), slave.as_ptr(), stream, mode)) {
+        drop(unsafe { Box::from_raw(inner) });
+        return Err(e);
+    }
+    let (p, mut h) = unsafe { from_ext::<P>(ext) };
+    if let Err(e) = p.hw_constraints(&mut h) {
+        // Closes the PCM, which calls cb_close, which frees inner
+        unsafe { ffi::snd_pcm_extplug_delete(ext) };
+        return Err(e);
+    }
+    Ok(unsafe { (*ext).pcm })
+}
+
+/// Creates a new PCM, which passes audio through `plugin` to or from the slave PCM.
+///
+/// `slave` is the slave definition, e g the node `slave` in `slave.pcm "default"`. Names of PCMs
+/// are looked up in `root`, which is usually the global configuration.
+pub fn create<P: ExtPlug>(name: &str, root: &Node, slave: &Node, dir: Direction, nonblock: bool, plugin: P) -> Result<PCM> {
+    let mode = if nonblock { alsa::SND_PCM_NONBLOCK as c_int } else { 0 };
+    create_raw(&CString::new(name).unwrap(), root, slave, dir_to_stream(dir), mode, plugin).map(pcm::pcm_from_ptr)
+}
+
+#[doc(hidden)]
+/// Called from the entry point generated by `export_extplug!`.
+pub unsafe fn open_entry<P, F>(pcmp: *mut *mut c_void, name: *const c_char, root: *mut c_void, conf: *mut c_void,
+    stream: c_int, mode: c_int, f: F) -> c_int
+where P: ExtPlug, F: FnOnce(&str, &Node, Direction) -> Result<P> {
+    let stream = stream as alsa::snd_pcm_stream_t;
+    let root = Node::from_ptr(root as *mut alsa::snd_config_t);
+    let conf = Node::from_ptr(conf as *mut alsa::snd_config_t);
+    let r = from_const("_snd_pcm_open", name).and_then(|n| {
+        let slave = conf.search("slave")?;
+        let p = f(n, conf, stream_to_dir(stream))?;
+        create_raw(CStr::from_ptr(name), root, slave, stream, mode, p)
+    });
+    match r {
+        Ok(pcm) => { *pcmp = pcm as *mut c_void; 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+/// Exports a filter plugin from a `cdylib`.
+///
+/// `export_extplug!(foo, open)` creates the `_snd_pcm_foo_open` entry point which alsa-lib
+/// looks for in `libasound_module_pcm_foo.so`, when opening a PCM of type `foo`.
+///
+/// `open` is called as `open(name: &str, conf: &config::Node, dir: Direction) -> Result<impl ExtPlug>`,
+/// where `conf` is the PCM's definition in the configuration tree. It must contain a `slave` definition.
+#[macro_export]
+macro_rules! export_extplug {
+    ($name: ident, $open: expr) => {
+        const _: () = {
+            #[export_name = concat!("_snd_pcm_", stringify!($name), "_open")]
+            pub unsafe extern "C" fn open(pcmp: *mut *mut ::core::ffi::c_void, name: *const ::core::ffi::c_char,
+                root: *mut ::core::ffi::c_void, conf: *mut ::core::ffi::c_void,
+                stream: ::core::ffi::c_int, mode: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                $crate::plugin::extplug::open_entry(pcmp, name, root, conf, stream, mode, $open)
+            }
+
+            // alsa-lib checks that this symbol exists, to verify the plugin API version
+            #[export_name = concat!("__snd_pcm_", stringify!($name), "_open_dlsym_pcm_001")]
+            pub static DLSYM_VERSION: u8 = 0;
+        };
+    }
+}
+
+#[test]
+fn extplug_playback() {
+    extern crate std;
+    use std::sync::{Arc, Mutex};
+    use crate::pcm::{HwParams, Access};
+    use crate::config::Config;
+    use crate::ValueOr;
+
+    struct Halve { data: Arc<Mutex<Vec<i16>>>, chmap: Vec<pcm::ChmapPosition> }
+
+    impl ExtPlug for Halve {
+        const CHMAP: bool = true;
+        fn hw_constraints(&mut self, ext: &mut Handle) -> Result<()> {
+            ext.set_formats(&[Format::s16()])?;
+            ext.set_slave_formats(&[Format::s16()])
+        }
+        fn transfer(&mut self, ext: &mut Handle, dst: &mut Areas, src: &Areas) -> Result<Frames> {
+            assert_eq!(ext.get_channels(), 2);
+            assert_eq!(ext.get_slave_format(), Ok(Format::s16()));
+            let s = src.interleaved_as::<i16>().unwrap();
+            self.data.lock().unwrap().extend_from_slice(s);
+            let d = dst.interleaved_as_mut::<i16>().unwrap();
+            for (d, s) in d.iter_mut().zip(s) { *d = *s / 2 }
+            Ok(src.frames())
+        }
+        fn get_chmap(&mut self, _: &mut Handle) -> Result<Chmap> { Ok(Chmap::from(&self.chmap[..])) }
+    }
+
+    let root = Config::top().unwrap();
+    let slave = Config::load_string("slave.pcm.type null").unwrap();
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let chmap = ::alloc::vec![pcm::ChmapPosition::FL, pcm::ChmapPosition::FR];
+    let pcm = create("rust_extplug", &root, slave.search("slave").unwrap(), Direction::Playback, false,
+        Halve { data: data.clone(), chmap }).unwrap();
+    {
+        let hwp = HwParams::any(&pcm).unwrap();
+        assert!(hwp.set_format(Format::float()).is_err());
+        hwp.set_channels(2).unwrap();
+        hwp.set_rate(44100, ValueOr::Nearest).unwrap();
+        hwp.set_format(Format::s16()).unwrap();
+        hwp.set_access(Access::RWInterleaved).unwrap();
+        pcm.hw_params(&hwp).unwrap();
+    }
+    assert_eq!(Vec::<pcm::ChmapPosition>::from(&pcm.get_chmap().unwrap()), &[pcm::ChmapPosition::FL, pcm::ChmapPosition::FR]);
+    let io = pcm.io_i16().unwrap();
+    let buf: Vec<i16> = (0..512).collect();
+    assert_eq!(io.writei(&buf).unwrap(), 256);
+    assert_eq!(&*data.lock().unwrap(), &buf);
+}
@@ -0,0 +1,215 @@
+//! Some definitions from alsa-lib's plugin SDK headers, which are not part of alsa-sys
+
+#![allow(non_camel_case_types)]
+
+use crate::alsa;
+use libc::{c_char, c_int, c_long, c_uint, c_ulong, c_ushort, c_void, pollfd, size_t};
+
+// pcm_ioplug.h
+
+pub const SND_PCM_IOPLUG_VERSION: c_uint = (1 << 16) | (0 << 8) | 2;
+
+// pub const SND_PCM_IOPLUG_FLAG_LISTED: c_uint = 1 << 0;
+pub const SND_PCM_IOPLUG_FLAG_MONOTONIC: c_uint = 1 << 1;
+
+pub const SND_PCM_IOPLUG_HW_ACCESS: c_int = 0;
+pub const SND_PCM_IOPLUG_HW_FORMAT: c_int = 1;
+pub const SND_PCM_IOPLUG_HW_CHANNELS: c_int = 2;
+pub const SND_PCM_IOPLUG_HW_RATE: c_int = 3;
+pub const SND_PCM_IOPLUG_HW_PERIOD_BYTES: c_int = 4;
+pub const SND_PCM_IOPLUG_HW_BUFFER_BYTES: c_int = 5;
+pub const SND_PCM_IOPLUG_HW_PERIODS: c_int = 6;
+
+#[repr(C)]
+pub struct snd_pcm_ioplug_t {
+    pub version: c_uint,
+    pub name: *const c_char,
+    pub flags: c_uint,
+    pub poll_fd: c_int,
+    pub poll_events: c_uint,
+    pub mmap_rw: c_uint,
+    pub callback: *const snd_pcm_ioplug_callback_t,
+    pub private_data: *mut c_void,
+    pub pcm: *mut alsa::snd_pcm_t,
+    pub stream: alsa::snd_pcm_stream_t,
+    pub state: alsa::snd_pcm_state_t,
+    pub appl_ptr: alsa::snd_pcm_uframes_t,
+    pub hw_ptr: alsa::snd_pcm_uframes_t,
+    pub nonblock: c_int,
+    pub access: alsa::snd_pcm_access_t,
+    pub format: alsa::snd_pcm_format_t,
+    pub channels: c_uint,
+    pub rate: c_uint,
+    pub period_size: alsa::snd_pcm_uframes_t,
+    pub buffer_size: alsa::snd_pcm_uframes_t,
+}
+
+type IoplugFn = Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t) -> c_int>;
+
+#[repr(C)]
+pub struct snd_pcm_ioplug_callback_t {
+    pub start: IoplugFn,
+    pub stop: IoplugFn,
+    pub pointer: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t) -> alsa::snd_pcm_sframes_t>,
+    pub transfer: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, areas: *const alsa::snd_pcm_channel_area_t,
+        offset: alsa::snd_pcm_uframes_t, size: alsa::snd_pcm_uframes_t) -> alsa::snd_pcm_sframes_t>,
+    pub close: IoplugFn,
+    pub hw_params: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, params: *mut alsa::snd_pcm_hw_params_t) -> c_int>,
+    pub hw_free: IoplugFn,
+    pub sw_params: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, params: *mut alsa::snd_pcm_sw_params_t) -> c_int>,
+    pub prepare: IoplugFn,
+    pub drain: IoplugFn,
+    pub pause: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, enable: c_int) -> c_int>,
+    pub resume: IoplugFn,
+    pub poll_descriptors_count: IoplugFn,
+    pub poll_descriptors: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, pfd: *mut pollfd, space: c_uint) -> c_int>,
+    pub poll_revents: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, pfd: *mut pollfd, nfds: c_uint,
+        revents: *mut c_ushort) -> c_int>,
+    pub dump: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, out: *mut alsa::snd_output_t)>,
+    pub delay: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, delayp: *mut alsa::snd_pcm_sframes_t) -> c_int>,
+    pub query_chmaps: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t) -> *mut *mut alsa::snd_pcm_chmap_query_t>,
+    pub get_chmap: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t) -> *mut alsa::snd_pcm_chmap_t>,
+    pub set_chmap: Option<unsafe extern "C" fn(io: *mut snd_pcm_ioplug_t, map: *const alsa::snd_pcm_chmap_t) -> c_int>,
+}
+
+extern "C" {
+    pub fn snd_pcm_ioplug_create(io: *mut snd_pcm_ioplug_t, name: *const c_char, stream: alsa::snd_pcm_stream_t,
+        mode: c_int) -> c_int;
+    pub fn snd_pcm_ioplug_delete(io: *mut snd_pcm_ioplug_t) -> c_int;
+    pub fn snd_pcm_ioplug_reinit_status(io: *mut snd_pcm_ioplug_t) -> c_int;
+    pub fn snd_pcm_ioplug_params_reset(io: *mut snd_pcm_ioplug_t);
+    pub fn snd_pcm_ioplug_set_param_minmax(io: *mut snd_pcm_ioplug_t, type_: c_int, min: c_uint, max: c_uint) -> c_int;
+    pub fn snd_pcm_ioplug_set_param_list(io: *mut snd_pcm_ioplug_t, type_: c_int, num_list: c_uint,
+        list: *const c_uint) -> c_int;
+    pub fn snd_pcm_ioplug_set_state(io: *mut snd_pcm_ioplug_t, state: alsa::snd_pcm_state_t) -> c_int;
+    pub fn snd_pcm_ioplug_avail(io: *const snd_pcm_ioplug_t, hw_ptr: alsa::snd_pcm_uframes_t,
+        appl_ptr: alsa::snd_pcm_uframes_t) -> alsa::snd_pcm_uframes_t;
+    pub fn snd_pcm_ioplug_hw_avail(io: *const snd_pcm_ioplug_t, hw_ptr: alsa::snd_pcm_uframes_t,
+        appl_ptr: alsa::snd_pcm_uframes_t) -> alsa::snd_pcm_uframes_t;
+}
+
+// pcm_extplug.h
+
+pub const SND_PCM_EXTPLUG_VERSION: c_uint = (1 << 16) | (0 << 8) | 2;
+
+pub const SND_PCM_EXTPLUG_HW_FORMAT: c_int = 0;
+pub const SND_PCM_EXTPLUG_HW_CHANNELS: c_int = 1;
+
+#[repr(C)]
+pub struct snd_pcm_extplug_t {
+    pub version: c_uint,
+    pub name: *const c_char,
+    pub callback: *const snd_pcm_extplug_callback_t,
+    pub private_data: *mut c_void,
+    pub pcm: *mut alsa::snd_pcm_t,
+    pub stream: alsa::snd_pcm_stream_t,
+    pub format: alsa::snd_pcm_format_t,
+    pub subformat: alsa::snd_pcm_subformat_t,
+    pub channels: c_uint,
+    pub rate: c_uint,
+    pub slave_format: alsa::snd_pcm_format_t,
+    pub slave_subformat: alsa::snd_pcm_subformat_t,
+    pub slave_channels: c_uint,
+}
+
+type ExtplugFn = Option<unsafe extern "C" fn(ext: *mut snd_pcm_extplug_t) -> c_int>;
+
+#[repr(C)]
+pub struct snd_pcm_extplug_callback_t {
+    pub transfer: Option<unsafe extern "C" fn(ext: *mut snd_pcm_extplug_t,
+        dst_areas: *const alsa::snd_pcm_channel_area_t, dst_offset: alsa::snd_pcm_uframes_t,
+        src_areas: *const alsa::snd_pcm_channel_area_t, src_offset: alsa::snd_pcm_uframes_t,
+        size: alsa::snd_pcm_uframes_t) -> alsa::snd_pcm_sframes_t>,
+    pub close: ExtplugFn,
+    pub hw_params: Option<unsafe extern "C" fn(ext: *mut snd_pcm_extplug_t, params: *mut alsa::snd_pcm_hw_params_t) -> c_int>,
+    pub hw_free: ExtplugFn,
+    pub dump: Option<unsafe extern "C" fn(ext: *mut snd_pcm_extplug_t, out: *mut alsa::snd_output_t)>,
+    pub init: ExtplugFn,
+    pub query_chmaps: Option<unsafe extern "C" fn(ext: *mut snd_pcm_extplug_t) -> *mut *mut alsa::snd_pcm_chmap_query_t>,
+    pub get_chmap: Option<unsafe extern "C" fn(ext: *mut snd_pcm_extplug_t) -> *mut alsa::snd_pcm_chmap_t>,
+    pub set_chmap: Option<unsafe extern "C" fn(ext: *mut snd_pcm_extplug_t, map: *const alsa::snd_pcm_chmap_t) -> c_int>,
+}
+
+extern "C" {
+    pub fn snd_pcm_extplug_create(ext: *mut snd_pcm_extplug_t, name: *const c_char, root: *mut alsa::snd_config_t,
+        slave_conf: *mut alsa::snd_config_t, stream: alsa::snd_pcm_stream_t, mode: c_int) -> c_int;
+    pub fn snd_pcm_extplug_delete(ext: *mut snd_pcm_extplug_t) -> c_int;
+    pub fn snd_pcm_extplug_params_reset(ext: *mut snd_pcm_extplug_t);
+    pub fn snd_pcm_extplug_set_param_list(ext: *mut snd_pcm_extplug_t, type_: c_int, num_list: c_uint,
+        list: *const c_uint) -> c_int;
+    pub fn snd_pcm_extplug_set_param_minmax(ext: *mut snd_pcm_extplug_t, type_: c_int, min: c_uint, max: c_uint) -> c_int;
+    pub fn snd_pcm_extplug_set_slave_param_list(ext: *mut snd_pcm_extplug_t, type_: c_int, num_list: c_uint,
+        list: *const c_uint) -> c_int;
+    pub fn snd_pcm_extplug_set_slave_param_minmax(ext: *mut snd_pcm_extplug_t, type_: c_int, min: c_uint,
+        max: c_uint) -> c_int;
+    pub fn snd_pcm_extplug_set_param_link(ext: *mut snd_pcm_extplug_t, type_: c_int, keep_link: c_int) -> c_int;
+}
+
+// control_external.h
+
+pub const SND_CTL_EXT_VERSION: c_uint = (1 << 16) | (0 << 8) | 1;
+
+pub type snd_ctl_ext_key_t = c_ulong;
+
+pub const SND_CTL_EXT_KEY_NOT_FOUND: snd_ctl_ext_key_t = !0;
+
+#[repr(C)]
+pub struct snd_ctl_ext_t {
+    pub version: c_uint,
+    pub card_idx: c_int,
+    pub id: [c_char; 16],
+    pub driver: [c_char; 16],
+    pub name: [c_char; 32],
+    pub longname: [c_char; 80],
+    pub mixername: [c_char; 80],
+    pub poll_fd: c_int,
+    pub callback: *const snd_ctl_ext_callback_t,
+    pub private_data: *mut c_void,
+    pub handle: *mut alsa::snd_ctl_t,
+    pub nonblock: c_int,
+    pub subscribed: c_int,
+    pub tlv: *const c_void,
+}
+
+type CtlExtKeyFn<T> = Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, key: snd_ctl_ext_key_t, value: *mut T) -> c_int>;
+
+#[repr(C)]
+pub struct snd_ctl_ext_callback_t {
+    pub close: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t)>,
+    pub elem_count: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t) -> c_int>,
+    pub elem_list: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, offset: c_uint, id: *mut alsa::snd_ctl_elem_id_t) -> c_int>,
+    pub find_elem: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, id: *const alsa::snd_ctl_elem_id_t) -> snd_ctl_ext_key_t>,
+    pub free_key: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, key: snd_ctl_ext_key_t)>,
+    pub get_attribute: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, key: snd_ctl_ext_key_t, type_: *mut c_int,
+        acc: *mut c_uint, count: *mut c_uint) -> c_int>,
+    pub get_integer_info: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, key: snd_ctl_ext_key_t, imin: *mut c_long,
+        imax: *mut c_long, istep: *mut c_long) -> c_int>,
+    pub get_integer64_info: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, key: snd_ctl_ext_key_t, imin: *mut i64,
+        imax: *mut i64, istep: *mut i64) -> c_int>,
+    pub get_enumerated_info: CtlExtKeyFn<c_uint>,
+    pub get_enumerated_name: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, key: snd_ctl_ext_key_t, item: c_uint,
+        name: *mut c_char, name_max_len: size_t) -> c_int>,
+    pub read_integer: CtlExtKeyFn<c_long>,
+    pub read_integer64: CtlExtKeyFn<i64>,
+    pub read_enumerated: CtlExtKeyFn<c_uint>,
+    pub read_bytes: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, key: snd_ctl_ext_key_t, data: *mut u8,
+        max_bytes: size_t) -> c_int>,
+    pub read_iec958: CtlExtKeyFn<alsa::snd_aes_iec958_t>,
+    pub write_integer: CtlExtKeyFn<c_long>,
+    pub write_integer64: CtlExtKeyFn<i64>,
+    pub write_enumerated: CtlExtKeyFn<c_uint>,
+    pub write_bytes: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, key: snd_ctl_ext_key_t, data: *mut u8,
+        max_bytes: size_t) -> c_int>,
+    pub write_iec958: CtlExtKeyFn<alsa::snd_aes_iec958_t>,
+    pub subscribe_events: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, subscribe: c_int)>,
+    pub read_event: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, id: *mut alsa::snd_ctl_elem_id_t,
+        event_mask: *mut c_uint) -> c_int>,
+    pub poll_descriptors_count: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t) -> c_int>,
+    pub poll_descriptors: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, pfds: *mut pollfd, space: c_uint) -> c_int>,
+    pub poll_revents: Option<unsafe extern "C" fn(ext: *mut snd_ctl_ext_t, pfds: *mut pollfd, nfds: c_uint,
+        revents: *mut c_ushort) -> c_int>,
+}
+
+extern "C" {
+    pub fn snd_ctl_ext_create(ext: *mut snd_ctl_ext_t, name: *const c_char, mode: c_int) -> c_int;
+}
@@ -0,0 +1,415 @@
+//! PCM I/O plugins - implement a PCM device in Rust
+//!
+//! Implement the `IoPlug` trait, then either create a `PCM` directly with `create`, or use
+//! `export_ioplug!` in a `cdylib` so that any ALSA application can open the plugin by name.
+//! See the [External I/O plugin SDK](https://www.alsa-project.org/alsa-doc/alsa-lib/pcm_external_plugins.html)
+//! for how alsa-lib calls the plugin.
+//!
+//! # Example
+//!
+//! ```
+//! use alsa::pcm::{Access, Format, Frames};
+//! use alsa::plugin::ioplug::{IoPlug, Handle, Areas, HwParam};
+//!
+//! /// Discards everything written to it.
+//! struct Sink { pos: Frames }
+//!
+//! impl IoPlug for Sink {
+//!     fn hw_constraints(&mut self, io: &mut Handle) -> alsa::Result<()> {
+//!         io.set_access(&[Access::RWInterleaved])?;
+//!         io.set_formats(&[Format::s16()])?;
+//!         io.set_param_minmax(HwParam::Channels, 1, 8)?;
+//!         io.set_param_minmax(HwParam::Rate, 8000, 192000)?;
+//!         io.set_param_minmax(HwParam::PeriodBytes, 64, 64 * 1024)?;
+//!         io.set_param_minmax(HwParam::Periods, 2, 64)
+//!     }
+//!     fn start(&mut self, _: &mut Handle) -> alsa::Result<()> { Ok(()) }
+//!     fn stop(&mut self, _: &mut Handle) -> alsa::Result<()> { Ok(()) }
+//!     fn pointer(&mut self, io: &mut Handle) -> alsa::Result<Frames> { Ok(self.pos % io.get_buffer_size()) }
+//!     fn transfer(&mut self, _: &mut Handle, areas: &mut Areas) -> alsa::Result<Frames> {
+//!         self.pos += areas.frames();
+//!         Ok(areas.frames())
+//!     }
+//! }
+//!
+//! // Built as "libasound_module_pcm_sink.so", this makes PCMs of type "sink" available,
+//! // e g by adding `pcm.mysink { type sink }` to your .asoundrc.
+//! alsa::export_ioplug!(sink, |_name, _conf, _dir| Ok(Sink { pos: 0 }));
+//! ```
+
+use super::{ffi, err_code, stream_to_dir, dir_to_stream};
+pub use super::Areas;
+use crate::alsa;
+use crate::config::Node;
+use crate::error::*;
+use crate::pcm::{self, PCM, Access, Format, Frames, State};
+use crate::{Direction, poll};
+use ::alloc::boxed::Box;
+use ::alloc::ffi::CString;
+use ::alloc::vec::Vec;
+use core::ffi::CStr;
+use core::marker::PhantomData;
+use core::{mem, ptr};
+use libc::{c_char, c_int, c_uint, c_void};
+
+/// Callbacks of a PCM I/O plugin
+///
+/// The plugin is dropped when the PCM is closed.
+pub trait IoPlug: Send + 'static {
+    /// Set to true if the plugin implements `drain`, otherwise alsa-lib waits for the hardware
+    /// pointer to catch up with the application pointer.
+    const DRAIN: bool = false;
+    /// Set to true if the plugin implements `pause`.
+    const PAUSE: bool = false;
+    /// Set to true if the plugin implements `resume`.
+    const RESUME: bool = false;
+    /// Set to true if the plugin implements `delay`, otherwise alsa-lib calculates the delay
+    /// from the hardware pointer.
+    const DELAY: bool = false;
+
+    /// Sets up the supported access types, formats, channels etc, see `Handle::set_param_minmax`.
+    ///
+    /// Usually all parameters need to be constrained, including period size and number of periods,
+    /// or negotiating hardware parameters will fail.
+    ///
+    /// Called once, right after the PCM has been created.
+    fn hw_constraints(&mut self, io: &mut Handle) -> Result<()>;
+
+    fn start(&mut self, io: &mut Handle) -> Result<()>;
+
+    fn stop(&mut self, io: &mut Handle) -> Result<()>;
+
+    /// Returns the current hardware position inside the ring buffer, in frames.
+    ///
+    /// Return an `EPIPE` error to signal an xrun.
+    fn pointer(&mut self, io: &mut Handle) -> Result<Frames>;
+
+    /// Copies data from `areas` (playback) or to `areas` (capture), and returns the number of frames transferred.
+    fn transfer(&mut self, io: &mut Handle, areas: &mut Areas) -> Result<Frames>;
+
+    /// Called when the hardware parameters have been chosen; they can be read from `io`.
+    fn hw_params(&mut self, _io: &mut Handle) -> Result<()> { Ok(()) }
+
+    fn hw_free(&mut self, _io: &mut Handle) -> Result<()> { Ok(()) }
+
+    fn prepare(&mut self, _io: &mut Handle) -> Result<()> { Ok(()) }
+
+    fn drain(&mut self, _io: &mut Handle) -> Result<()> { Err(Error::unsupported("drain")) }
+
+    fn pause(&mut self, _io: &mut Handle, _enable: bool) -> Result<()> { Err(Error::unsupported("pause")) }
+
+    fn resume(&mut self, _io: &mut Handle) -> Result<()> { Err(Error::unsupported("resume")) }
+
+    fn delay(&mut self, _io: &mut Handle) -> Result<Frames> { Err(Error::unsupported("delay")) }
+}
+
+/// Hardware parameters that can be constrained in `IoPlug::hw_constraints`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HwParam {
+    Access = ffi::SND_PCM_IOPLUG_HW_ACCESS as isize,
+    Format = ffi::SND_PCM_IOPLUG_HW_FORMAT as isize,
+    Channels = ffi::SND_PCM_IOPLUG_HW_CHANNELS as isize,
+    Rate = ffi::SND_PCM_IOPLUG_HW_RATE as isize,
+    PeriodBytes = ffi::SND_PCM_IOPLUG_HW_PERIOD_BYTES as isize,
+    BufferBytes = ffi::SND_PCM_IOPLUG_HW_BUFFER_BYTES as isize,
+    Periods = ffi::SND_PCM_IOPLUG_HW_PERIODS as isize,
+}
+
+/// [snd_pcm_ioplug_t](https://www.alsa-project.org/alsa-doc/alsa-lib/group___plugin___s_d_k___i_o.html) wrapper
+///
+/// Gives plugin callbacks access to the current state and parameters of the PCM.
+#[derive(Debug)]
+pub struct Handle<'a>(*mut ffi::snd_pcm_ioplug_t, PhantomData<&'a mut ()>);
+
+impl<'a> Handle<'a> {
+    pub fn get_stream(&self) -> Direction { stream_to_dir(unsafe { (*self.0).stream }) }
+    pub fn get_state(&self) -> Result<State> { State::from_c_int(unsafe { (*self.0).state } as c_int, "snd_pcm_ioplug_t.state") }
+    pub fn get_nonblock(&self) -> bool { unsafe { (*self.0).nonblock != 0 } }
+    pub fn get_appl_ptr(&self) -> Frames { unsafe { ptr::read_volatile(&(*self.0).appl_ptr) as Frames } }
+    pub fn get_hw_ptr(&self) -> Frames { unsafe { ptr::read_volatile(&(*self.0).hw_ptr) as Frames } }
+
+    pub fn get_access(&self) -> Result<Access> { Access::from_c_int(unsafe { (*self.0).access } as c_int, "snd_pcm_ioplug_t.access") }
+    pub fn get_format(&self) -> Result<Format> { Format::from_c_int(unsafe { (*self.0).format } as c_int, "snd_pcm_ioplug_t.format") }
+    pub fn get_channels(&self) -> u32 { unsafe { (*self.0).channels as u32 } }
+    pub fn get_rate(&self) -> u32 { unsafe { (*self.0).rate as u32 } }
+    pub fn get_period_size(&self) -> Frames { unsafe { (*self.0).period_size as Frames } }
+    pub fn get_buffer_size(&self) -> Frames { unsafe { (*self.0).buffer_size as Frames } }
+
+    /// Frames available to the application, given the current pointers.
+    pub fn avail(&self) -> Frames {
+        unsafe { ffi::snd_pcm_ioplug_avail(self.0, (*self.0).hw_ptr, (*self.0).appl_ptr) as Frames }
+    }
+
+    /// Frames available to the plugin, given the current pointers.
+    pub fn hw_avail(&self) -> Frames {
+        unsafe { ffi::snd_pcm_ioplug_hw_avail(self.0, (*self.0).hw_ptr, (*self.0).appl_ptr) as Frames }
+    }
+
+    /// Changes the state of the PCM, e g to `State::XRun` or `State::Disconnected`.
+    pub fn set_state(&mut self, s: State) -> Result<()> {
+        acheck!(ffi::snd_pcm_ioplug_set_state(self.0, s as alsa::snd_pcm_state_t)).map(|_| ())
+    }
+
+    /// Sets the file descriptor applications should poll to wait for the plugin.
+    ///
+    /// Without a valid file descriptor, blocking reads and writes that need to wait will wait forever.
+    pub fn set_poll_fd(&mut self, fd: c_int, events: poll::Flags) -> Result<()> {
+        unsafe {
+            (*self.0).poll_fd = fd;
+            (*self.0).poll_events = events.bits() as u16 as c_uint;
+        }
+        acheck!(ffi::snd_pcm_ioplug_reinit_status(self.0)).map(|_| ())
+    }
+
+    /// Selects monotonic instead of wall clock timestamps.
+    pub fn set_monotonic(&mut self, v: bool) -> Result<()> {
+        unsafe {
+            if v { (*self.0).flags |= ffi::SND_PCM_IOPLUG_FLAG_MONOTONIC }
+            else { (*self.0).flags &= !ffi::SND_PCM_IOPLUG_FLAG_MONOTONIC }
+        }
+        acheck!(ffi::snd_pcm_ioplug_reinit_status(self.0)).map(|_| ())
+    }
+
+    pub fn set_param_minmax(&mut self, p: HwParam, min: u32, max: u32) -> Result<()> {
+        acheck!(ffi::snd_pcm_ioplug_set_param_minmax(self.0, p as c_int, min as c_uint, max as c_uint)).map(|_| ())
+    }
+
+    pub fn set_param_list(&mut self, p: HwParam, list: &[u32]) -> Result<()> {
+        acheck!(ffi::snd_pcm_ioplug_set_param_list(self.0, p as c_int, list.len() as c_uint, list.as_ptr())).map(|_| ())
+    }
+
+    /// Wrapper around `set_param_list` for `HwParam::Access`
+    pub fn set_access(&mut self, list: &[Access]) -> Result<()> {
+        let v: Vec<u32> = list.iter().map(|a| a.to_c_int() as u32).collect();
+        self.set_param_list(HwParam::Access, &v)
+    }
+
+    /// Wrapper around `set_param_list` for `HwParam::Format`
+    pub fn set_formats(&mut self, list: &[Format]) -> Result<()> {
+        let v: Vec<u32> = list.iter().map(|f| f.to_c_int() as u32).collect();
+        self.set_param_list(HwParam::Format, &v)
+    }
+
+    /// Removes all constraints set by `set_param_minmax` and `set_param_list`.
+    pub fn params_reset(&mut self) { unsafe { ffi::snd_pcm_ioplug_params_reset(self.0) } }
+}
+
+// The ioplug struct given to alsa-lib, which must stay at the same address, is part of this allocation.
+struct Inner<P> {
+    io: ffi::snd_pcm_ioplug_t,
+    callback: ffi::snd_pcm_ioplug_callback_t,
+    name: CString,
+    plugin: P,
+}
+
+unsafe fn from_io<'a, P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> (&'a mut P, Handle<'a>) {
+    let inner = (*io).private_data as *mut Inner<P>;
+    (&mut (*inner).plugin, Handle(io, PhantomData))
+}
+
+unsafe extern "C" fn cb_start<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.start(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_stop<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.stop(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_pointer<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> alsa::snd_pcm_sframes_t {
+    let (p, mut h) = from_io::<P>(io);
+    p.pointer(&mut h).map_or_else(|e| err_code(e) as alsa::snd_pcm_sframes_t, |x| x as alsa::snd_pcm_sframes_t)
+}
+
+unsafe extern "C" fn cb_transfer<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t, areas: *const alsa::snd_pcm_channel_area_t,
+    offset: alsa::snd_pcm_uframes_t, size: alsa::snd_pcm_uframes_t) -> alsa::snd_pcm_sframes_t {
+    let mut a = Areas::new(areas, (*io).channels, (*io).format, offset, size);
+    let (p, mut h) = from_io::<P>(io);
+    p.transfer(&mut h, &mut a).map_or_else(|e| err_code(e) as alsa::snd_pcm_sframes_t, |x| x as alsa::snd_pcm_sframes_t)
+}
+
+unsafe extern "C" fn cb_close<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> c_int {
+    drop(Box::from_raw((*io).private_data as *mut Inner<P>));
+    0
+}
+
+unsafe extern "C" fn cb_hw_params<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t, _: *mut alsa::snd_pcm_hw_params_t) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.hw_params(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_hw_free<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.hw_free(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_prepare<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.prepare(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_drain<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.drain(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_pause<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t, enable: c_int) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.pause(&mut h, enable != 0).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_resume<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.resume(&mut h).map_or_else(err_code, |_| 0)
+}
+
+unsafe extern "C" fn cb_delay<P: IoPlug>(io: *mut ffi::snd_pcm_ioplug_t, delayp: *mut alsa::snd_pcm_sframes_t) -> c_int {
+    let (p, mut h) = from_io::<P>(io);
+    p.delay(&mut h).map_or_else(err_code, |d| { *delayp = d as alsa::snd_pcm_sframes_t; 0 })
+}
+
+fn create_raw<P: IoPlug>(name: &CStr, stream: alsa::snd_pcm_stream_t, mode: c_int, plugin: P) -> Result<*mut alsa::snd_pcm_t> {
+    let callback = ffi::snd_pcm_ioplug_callback_t {
+        start: Some(cb_start::<P>),
+        stop: Some(cb_stop::<P>),
+        pointer: Some(cb_pointer::<P>),
+        transfer: Some(cb_transfer::<P>),
+        close: Some(cb_close::<P>),
+        hw_params: Some(cb_hw_params::<P>),
+        hw_free: Some(cb_hw_free::<P>),
+        sw_params: None,
+        prepare: Some(cb_prepare::<P>),
+        drain: if P::DRAIN { Some(cb_drain::<P>) } else { None },
+        pause: if P::PAUSE { Some(cb_pause::<P>) } else { None },
+        resume: if P::RESUME { Some(cb_resume::<P>) } else { None },
+        poll_descriptors_count: None,
+        poll_descriptors: None,
+        poll_revents: None,
+        dump: None,
+        delay: if P::DELAY { Some(cb_delay::<P>) } else { None },
+        query_chmaps: None,
+        get_chmap: None,
+        set_chmap: None,
+    };
+    let inner = Box::into_raw(Box::new(Inner { io: unsafe { mem::zeroed() }, callback, name: name.into(), plugin }));
+    let io = unsafe {
+        let io = ptr::addr_of_mut!((*inner).io);
+        (*io).version = ffi::SND_PCM_IOPLUG_VERSION;
+        (*io).name = (*inner).name.as_ptr();
+        (*io).poll_fd = -1;
+        (*io).callback = ptr::addr_of!((*inner).callback);
+        (*io).private_data = inner as *mut c_void;
+        io
+    };
+    if let Err(e) = acheck!(ffi::snd_pcm_ioplug_create(io, name.as_ptr(), stream, mode)) {
+        drop(unsafe { Box::from_raw(inner) });
+        return Err(e);
+    }
+    let (p, mut h) = unsafe { from_io::<P>(io) };
+    if let Err(e) = p.hw_constraints(&mut h) {
+        // Closes the PCM, which calls cb_close, which frees inner
+        unsafe { ffi::snd_pcm_ioplug_delete(io) };
+        return Err(e);
+    }
+    Ok(unsafe { (*io).pcm })
+}
+
+/// Creates a new PCM, backed by `plugin`.
+///
+/// This is useful within an application; to make the plugin available to other applications, see `export_ioplug!`.
+pub fn create<P: IoPlug>(name: &str, dir: Direction, nonblock: bool, plugin: P) -> Result<PCM> {
+    let mode = if nonblock { alsa::SND_PCM_NONBLOCK as c_int } else { 0 };
+    create_raw(&CString::new(name).unwrap(), dir_to_stream(dir), mode, plugin).map(pcm::pcm_from_ptr)
+}
+
+#[doc(hidden)]
+/// Called from the entry point generated by `export_ioplug!`.
+pub unsafe fn open_entry<P, F>(pcmp: *mut *mut c_void, name: *const c_char, _root: *mut c_void, conf: *mut c_void,
+    stream: c_int, mode: c_int, f: F) -> c_int
+where P: IoPlug, F: FnOnce(&str, &Node, Direction) -> Result<P> {
+    let stream = stream as alsa::snd_pcm_stream_t;
+    let r = from_const("_snd_pcm_open", name).and_then(|n| {
+        let p = f(n, Node::from_ptr(conf as *mut alsa::snd_config_t), stream_to_dir(stream))?;
+        create_raw(CStr::from_ptr(name), stream, mode, p)
+    });
+    match r {
+        Ok(pcm) => { *pcmp = pcm as *mut c_void; 0 },
+        Err(e) => err_code(e),
+    }
+}
+
+/// Exports an I/O plugin from a `cdylib`.
+///
+/// `export_ioplug!(foo, open)` creates the `_snd_pcm_foo_open` entry point which alsa-lib
+/// looks for in `libasound_module_pcm_foo.so`, when opening a PCM of type `foo`.
+///
+/// `open` is called as `open(name: &str, conf: &config::Node, dir: Direction) -> Result<impl IoPlug>`,
+/// where `conf` is the PCM's definition in the configuration tree.
+#[macro_export]
+macro_rules! export_ioplug {
+    ($name: ident, $open: expr) => {
+        const _: () = {
+            #[export_name = concat!("_snd_pcm_", stringify!($name), "_open")]
+            pub unsafe extern "C" fn open(pcmp: *mut *mut ::core::ffi::c_void, name: *const ::core::ffi::c_char,
+                root: *mut ::core::ffi::c_void, conf: *mut ::core::ffi::c_void,
+                stream: ::core::ffi::c_int, mode: ::core::ffi::c_int) -> ::core::ffi::c_int {
+                $crate::plugin::ioplug::open_entry(pcmp, name, root, conf, stream, mode, $open)
+            }
+
+            // alsa-lib checks that this symbol exists, to verify the plugin API version
+            #[export_name = concat!("__snd_pcm_", stringify!($name), "_open_dlsym_pcm_001")]
+            pub static DLSYM_VERSION: u8 = 0;
+        };
+    }
+}
+
+#[test]
+fn ioplug_playback() {
+    extern crate std;
+    use std::sync::{Arc, Mutex};
+    use crate::pcm::HwParams;
+    use crate::ValueOr;
+
+    struct Recorder { pos: Frames, started: bool, data: Arc<Mutex<Vec<u8>>> }
+
+    impl IoPlug for Recorder {
+        fn hw_constraints(&mut self, io: &mut Handle) -> Result<()> {
+            io.set_access(&[Access::RWInterleaved])?;
+            io.set_formats(&[Format::s16()])?;
+            io.set_param_list(HwParam::Channels, &[2])?;
+            io.set_param_minmax(HwParam::Rate, 8000, 48000)?;
+            io.set_param_minmax(HwParam::PeriodBytes, 256, 65536)?;
+            io.set_param_minmax(HwParam::Periods, 2, 64)
+        }
+        fn start(&mut self, _: &mut Handle) -> Result<()> { self.started = true; Ok(()) }
+        fn stop(&mut self, _: &mut Handle) -> Result<()> { self.started = false; Ok(()) }
+        fn pointer(&mut self, io: &mut Handle) -> Result<Frames> { Ok(self.pos % io.get_buffer_size()) }
+        fn transfer(&mut self, io: &mut Handle, areas: &mut Areas) -> Result<Frames> {
+            assert_eq!(io.get_stream(), Direction::Playback);
+            assert_eq!(io.get_format(), Ok(Format::s16()));
+            self.data.lock().unwrap().extend_from_slice(areas.interleaved().unwrap());
+            self.pos += areas.frames();
+            Ok(areas.frames())
+        }
+    }
+
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let pcm = create("rust_ioplug", Direction::Playback, false, Recorder { pos: 0, started: false, data: data.clone() }).unwrap();
+    {
+        let hwp = HwParams::any(&pcm).unwrap();
+        assert!(hwp.set_channels(1).is_err());
+        hwp.set_channels(2).unwrap();
+        hwp.set_rate(44100, ValueOr::Nearest).unwrap();
+        hwp.set_format(Format::s16()).unwrap();
+        hwp.set_access(Access::RWInterleaved).unwrap();
+        hwp.set_buffer_size_near(4096).unwrap();
+        pcm.hw_params(&hwp).unwrap();
+    }
+    let io = pcm.io_i16().unwrap();
+    let buf: Vec<i16> = (0..512).collect();
+    assert_eq!(io.writei(&buf).unwrap(), 256);
+    let d = data.lock().unwrap();
+    assert_eq!(d.len(), 1024);
+    assert_eq!(&d[..6], &[0, 0, 1, 0, 2, 0]);
+}
@@ -2,7 +2,8 @@
 
 use libc::{c_int, c_uint, c_void, size_t, c_short, pollfd};
 use super::ctl_int::{ctl_ptr, Ctl};
-use super::{Direction, poll};
+use super::{Direction, poll, config};
+use super::config::Config;
 use super::error::*;
 use crate::alsa;
 use ::alloc::ffi::CString;
@@ -153,6 +154,17 @@ impl Rawmidi {
             .map(|_| Rawmidi(h))
     }
 
+    /// Like open, but looks up the name in `lconf` instead of the global configuration.
+    pub fn open_lconf(name: &CStr, dir: Direction, nonblock: bool, lconf: &Config) -> Result<Rawmidi> {
+        let mut h = ptr::null_mut();
+        let flags = if nonblock { alsa::SND_RAWMIDI_NONBLOCK as i32 } else { 0 };
+        acheck!(snd_rawmidi_open_lconf(
+            if dir == Direction::Capture { &mut h } else { ptr::null_mut() },
+            if dir == Direction::Playback { &mut h } else { ptr::null_mut() },
+            name.as_ptr(), flags, config::config_ptr(lconf)))
+            .map(|_| Rawmidi(h))
+    }
+
     pub fn info(&self) -> Result<Info> {
         Info::new().and_then(|i| acheck!(snd_rawmidi_info(self.0, i.0)).map(|_| i))
     }
@@ -3,7 +3,8 @@
 use libc::{c_uint, c_int, c_short, c_uchar, c_void, c_long, size_t, pollfd};
 use super::error::*;
 use crate::alsa;
-use super::{Direction, poll};
+use super::{Direction, poll, config};
+use super::config::Config;
 use core::{ptr, fmt, mem, slice, time, cell};
 use core::str::{FromStr, Split};
 use core::ffi::CStr;
@@ -52,6 +53,19 @@ impl Seq {
             .map(|_| Seq(h, cell::Cell::new(false)))
     }
 
+    /// Like open, but looks up the name in `lconf` instead of the global configuration.
+    pub fn open_lconf(name: &CStr, dir: Option<Direction>, nonblock: bool, lconf: &Config) -> Result<Seq> {
+        let mut h = ptr::null_mut();
+        let mode = if nonblock { alsa::SND_SEQ_NONBLOCK as i32 } else { 0 };
+        let streams = match dir {
+            None => alsa::SND_SEQ_OPEN_DUPLEX as i32,
+            Some(Direction::Playback) => alsa::SND_SEQ_OPEN_OUTPUT as i32,
+            Some(Direction::Capture) => alsa::SND_SEQ_OPEN_INPUT as i32,
+        };
+        acheck!(snd_seq_open_lconf(&mut h, name.as_ptr(), streams, mode, config::config_ptr(lconf)))
+            .map(|_| Seq(h, cell::Cell::new(false)))
+    }
+
     pub fn set_client_name(&self, name: &CStr) -> Result<()> {
         acheck!(snd_seq_set_client_name(self.0, name.as_ptr())).map(|_| ())
     }
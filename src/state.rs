@@ -0,0 +1,379 @@
+//! Store and restore control values in the `asound.state` format, like `alsactl store` and `alsactl restore`
+//!
+//! The state of a card is a `state.<card id>` compound with one `control.<numid>` node per element.
+//! Enumerated values are saved by item name, and bytes and IEC958 values as a hex string. Read-only
+//! information, such as the range of an integer element, is saved in a `comment` node and ignored
+//! when restoring.
+//!
+//! # Example
+//! ```no_run
+//! let ctl = alsa::Ctl::new("hw:0", false).unwrap();
+//! let (saved, errors) = alsa::state::store_string(&ctl).unwrap();
+//! for e in errors { println!("Could not store control {} ({:?}): {}", e.control, e.name, e.error); }
+//!
+//! // ...later, e g when provisioning a new device
+//! for e in alsa::state::restore_string(&ctl, &saved).unwrap() {
+//!     println!("Could not restore control {} ({:?}): {}", e.control, e.name, e.error);
+//! }
+//! ```
+
+use crate::config::{Config, Node, Type};
+use crate::ctl_int::{self, Ctl, ElemId, ElemIface, ElemInfo, ElemType};
+use crate::iec958::Iec958Status;
+use crate::io::Output;
+use crate::tlv::Tlv;
+use crate::error::*;
+use ::alloc::ffi::CString;
+use ::alloc::format;
+use ::alloc::string::{String, ToString};
+use ::alloc::vec::Vec;
+
+/// An element that could not be stored or restored
+#[derive(Debug)]
+pub struct ControlError {
+    /// The id of the control node, i e the element's numid when the state was stored.
+    pub control: String,
+    /// The element name, if known.
+    pub name: Option<String>,
+    pub error: Error,
+}
+
+fn iface_name(i: ElemIface) -> &'static str {
+    match i {
+        ElemIface::Card => "CARD",
+        ElemIface::Hwdep => "HWDEP",
+        ElemIface::Mixer => "MIXER",
+        ElemIface::PCM => "PCM",
+        ElemIface::Rawmidi => "RAWMIDI",
+        ElemIface::Timer => "TIMER",
+        ElemIface::Sequencer => "SEQUENCER",
+    }
+}
+
+fn type_name(t: ElemType) -> &'static str {
+    match t {
+        ElemType::None => "NONE",
+        ElemType::Boolean => "BOOLEAN",
+        ElemType::Integer => "INTEGER",
+        ElemType::Enumerated => "ENUMERATED",
+        ElemType::Bytes => "BYTES",
+        ElemType::IEC958 => "IEC958",
+        ElemType::Integer64 => "INTEGER64",
+    }
+}
+
+fn to_hex(b: &[u8]) -> String { b.iter().map(|x| format!("{:02x}", x)).collect() }
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 { return None }
+    (0..s.len()).step_by(2).map(|i| s.get(i..i+2).and_then(|x| u8::from_str_radix(x, 16).ok())).collect()
+}
+
+fn access_string(info: &ElemInfo) -> String {
+    let mut s = Vec::new();
+    if info.is_readable() { s.push("read") }
+    if info.is_writable() { s.push("write") }
+    if info.is_inactive() { s.push("inactive") }
+    if info.is_volatile() { s.push("volatile") }
+    if info.is_locked() { s.push("locked") }
+    if info.is_user() { s.push("user") }
+    if info.is_tlv_readable() { s.push("tlv_read") }
+    if info.is_tlv_writable() { s.push("tlv_write") }
+    if info.is_tlv_commandable() { s.push("tlv_command") }
+    s.join(" ")
+}
+
+fn range_string(min: i64, max: i64, step: i64) -> String {
+    if step > 1 { format!("{} - {} (step {})", min, max, step) } else { format!("{} - {}", min, max) }
+}
+
+fn store_comment(ctl: &Ctl, info: &ElemInfo, id: &ElemId, items: &[String]) -> Result<Config> {
+    let mut c = Config::make_compound("comment", false)?;
+    c.add(Config::make_string("access", &access_string(info))?)?;
+    c.add(Config::make_string("type", type_name(info.get_type()))?)?;
+    c.add(Config::make_integer("count", info.get_count() as i64)?)?;
+    match info.get_type() {
+        ElemType::Integer => {
+            let (min, max) = (info.get_min().unwrap_or(0) as i64, info.get_max().unwrap_or(0) as i64);
+            c.add(Config::make_string("range", &range_string(min, max, info.get_step().unwrap_or(0) as i64))?)?;
+            let db = if info.is_tlv_readable() { ctl.elem_tlv_read(id).and_then(|t| Tlv::parse(&t)).ok() } else { None };
+            if let Some((dbmin, dbmax)) = db.as_ref().and_then(|t| t.db_range(min, max).ok()) {
+                c.add(Config::make_integer("dbmin", dbmin.0)?)?;
+                c.add(Config::make_integer("dbmax", dbmax.0)?)?;
+            }
+        }
+        ElemType::Integer64 => {
+            let (min, max, step) = (info.get_min64().unwrap_or(0), info.get_max64().unwrap_or(0), info.get_step64().unwrap_or(0));
+            c.add(Config::make_string("range", &range_string(min, max, step))?)?;
+        }
+        ElemType::Enumerated => {
+            let mut n = Config::make_compound("item", false)?;
+            for (i, name) in items.iter().enumerate() { n.add(Config::make_string(&i.to_string(), name)?)?; }
+            c.add(n)?;
+        }
+        _ => {}
+    }
+    Ok(c)
+}
+
+fn store_elem(ctl: &Ctl, info: &ElemInfo) -> Result<Config> {
+    let id = info.get_id();
+    let (t, count) = (info.get_type(), info.get_count());
+    let items = if t == ElemType::Enumerated { ctl.elem_item_names(&id)? } else { Vec::new() };
+
+    let mut v = ctl_int::elem_value_new(t, count)?;
+    v.set_id(&id);
+    ctl.elem_read(&mut v)?;
+
+    let mut c = Config::make_compound(&id.get_numid().to_string(), false)?;
+    c.add(Config::make_string("iface", iface_name(id.get_interface()))?)?;
+    if id.get_device() != 0 { c.add(Config::make_integer("device", id.get_device() as i64)?)? }
+    if id.get_subdevice() != 0 { c.add(Config::make_integer("subdevice", id.get_subdevice() as i64)?)? }
+    c.add(Config::make_string("name", id.get_name()?)?)?;
+    if id.get_index() != 0 { c.add(Config::make_integer("index", id.get_index() as i64)?)? }
+
+    let value = |key: &str, i: u32| -> Result<Config> {
+        let inval = || Error::new("snd_ctl_elem_read", libc::EINVAL);
+        match t {
+            ElemType::Boolean => Config::make_string(key, if v.get_boolean(i).ok_or_else(inval)? { "true" } else { "false" }),
+            ElemType::Integer => Config::make_integer(key, v.get_integer(i).ok_or_else(inval)? as i64),
+            ElemType::Integer64 => Config::make_integer64(key, v.get_integer64(i).ok_or_else(inval)?),
+            ElemType::Enumerated => {
+                let item = v.get_enumerated(i).ok_or_else(inval)?;
+                match items.get(item as usize) {
+                    Some(name) => Config::make_string(key, name),
+                    None => Config::make_integer(key, item as i64),
+                }
+            }
+            ElemType::Bytes => Config::make_string(key, &to_hex(v.get_bytes().ok_or_else(inval)?)),
+            ElemType::IEC958 => Config::make_string(key, &to_hex(&v.get_iec958().ok_or_else(inval)?.0)),
+            ElemType::None => Err(inval()),
+        }
+    };
+    if count == 1 || t == ElemType::Bytes || t == ElemType::IEC958 {
+        c.add(value("value", 0)?)?;
+    } else {
+        let mut n = Config::make_compound("value", false)?;
+        for i in 0..count { n.add(value(&i.to_string(), i)?)?; }
+        c.add(n)?;
+    }
+    c.add(store_comment(ctl, info, &id, &items)?)?;
+    Ok(c)
+}
+
+/// Reads all readable elements of a control device into a new configuration tree.
+///
+/// The returned tree contains a single `state.<card id>` node, and can be merged with the state of other cards.
+/// Elements that could not be read are left out of the tree and returned, rather than stopping the store.
+pub fn store(ctl: &Ctl) -> Result<(Config, Vec<ControlError>)> {
+    let card = ctl.card_info()?;
+    let mut controls = Config::make_compound("control", false)?;
+    let mut errors = Vec::new();
+    let list = ctl.elem_list()?;
+    for i in 0..list.get_used() {
+        let r = ctl.elem_info(&list.get_id(i)?).and_then(|info| {
+            if !info.is_readable() || info.get_type() == ElemType::None { return Ok(None) }
+            store_elem(ctl, &info).map(Some)
+        });
+        match r {
+            Ok(Some(c)) => controls.add(c)?,
+            Ok(None) => {},
+            Err(error) => errors.push(ControlError {
+                control: list.get_numid(i)?.to_string(),
+                name: list.get_name(i).ok().map(|n| n.to_string()),
+                error,
+            }),
+        }
+    }
+    let mut cardnode = Config::make_compound(card.get_id()?, false)?;
+    cardnode.add(controls)?;
+    let mut state = Config::make_compound("state", false)?;
+    state.add(cardnode)?;
+    let mut top = Config::top()?;
+    top.add(state)?;
+    Ok((top, errors))
+}
+
+/// Like `store`, but returns the state in `asound.state` text format.
+pub fn store_string(ctl: &Ctl) -> Result<(String, Vec<ControlError>)> {
+    let (top, errors) = store(ctl)?;
+    let mut o = Output::buffer_open()?;
+    top.save(&mut o)?;
+    Ok((o.buffer_string(|b| String::from_utf8_lossy(b).into_owned()), errors))
+}
+
+fn parse_iface(s: &str) -> Option<ElemIface> {
+    ElemIface::all().iter().copied().find(|&i| iface_name(i) == s)
+}
+
+fn get_integer(n: &Node) -> Result<i64> { n.get_integer().or_else(|_| n.get_integer64()) }
+
+fn restore_value(v: &mut ctl_int::ElemValue, t: ElemType, items: &[String], i: u32, n: &Node) -> Result<()> {
+    let r = match t {
+        ElemType::Boolean => v.set_boolean(i, n.get_bool()?),
+        ElemType::Integer => v.set_integer(i, get_integer(n)? as i32),
+        ElemType::Integer64 => v.set_integer64(i, get_integer(n)?),
+        ElemType::Enumerated => {
+            let item = match n.get_string() {
+                Ok(s) => items.iter().position(|x| x == s).map(|x| x as u32),
+                Err(_) => Some(get_integer(n)? as u32),
+            };
+            item.and_then(|x| v.set_enumerated(i, x))
+        }
+        ElemType::Bytes => from_hex(n.get_string()?).and_then(|b| v.set_bytes(&b)),
+        ElemType::IEC958 => from_hex(n.get_string()?).and_then(|b| {
+            let status: [u8; 24] = b.try_into().ok()?;
+            v.set_iec958(&Iec958Status(status))
+        }),
+        ElemType::None => None,
+    };
+    r.ok_or_else(|| Error::new("snd_ctl_elem_write", libc::EINVAL))
+}
+
+fn restore_elem(ctl: &Ctl, n: &Node) -> Result<()> {
+    let inval = || Error::new("snd_ctl_elem_write", libc::EINVAL);
+    let iface = n.search("iface")?.get_ascii()?;
+    let mut id = ElemId::new(parse_iface(&iface).ok_or_else(inval)?);
+    id.set_name(&CString::new(n.search("name")?.get_string()?).map_err(|_| inval())?);
+    if let Ok(x) = n.search("device") { id.set_device(get_integer(x)? as u32) }
+    if let Ok(x) = n.search("subdevice") { id.set_subdevice(get_integer(x)? as u32) }
+    if let Ok(x) = n.search("index") { id.set_index(get_integer(x)? as u32) }
+
+    let info = ctl.elem_info(&id)?;
+    if !info.is_writable() { return Ok(()) }
+    let (t, count) = (info.get_type(), info.get_count());
+    let items = if t == ElemType::Enumerated { ctl.elem_item_names(&id)? } else { Vec::new() };
+
+    let mut v = ctl_int::elem_value_new(t, count)?;
+    v.set_id(&info.get_id());
+    // Start from the current values, so that a partial value compound leaves the rest unchanged
+    ctl.elem_read(&mut v)?;
+    let value = n.search("value")?;
+    if value.get_type()? == Type::Compound {
+        for x in value.iter() {
+            let i = x.get_id()?.parse::<u32>().map_err(|_| inval())?;
+            if i >= count { return Err(inval()) }
+            restore_value(&mut v, t, items.as_slice(), i, x)?;
+        }
+    } else if t == ElemType::Bytes || t == ElemType::IEC958 {
+        restore_value(&mut v, t, &items, 0, value)?;
+    } else {
+        // A single value applies to all channels
+        for i in 0..count { restore_value(&mut v, t, &items, i, value)?; }
+    }
+    ctl.elem_write(&v)
+}
+
+/// Writes the values stored for this card to a control device.
+///
+/// `state` is a tree as returned by `store`, or loaded from an `asound.state` file. It must contain a
+/// `state.<card id>` node matching the card of `ctl`, or ENOENT is returned.
+///
+/// Read-only elements are skipped. Elements that could not be restored, e g because the driver no longer
+/// has them, are returned rather than stopping the restore.
+pub fn restore(ctl: &Ctl, state: &Node) -> Result<Vec<ControlError>> {
+    let card = ctl.card_info()?;
+    let key = format!("state.{}.control", card.get_id()?);
+    let controls = state.search(&key)?;
+    Ok(controls.iter().filter_map(|n| restore_elem(ctl, n).err().map(|error| ControlError {
+        control: n.get_id().unwrap_or("").to_string(),
+        name: n.search("name").and_then(|x| x.get_ascii()).ok(),
+        error,
+    })).collect())
+}
+
+/// Like `restore`, but takes the state in `asound.state` text format.
+pub fn restore_string(ctl: &Ctl, state: &str) -> Result<Vec<ControlError>> {
+    let top = Config::load_string(state)?;
+    restore(ctl, &top)
+}
+
+#[test]
+fn state_store_restore() {
+    use crate::plugin::ctl_ext::{self, Access, CtlExt, Handle};
+    use crate::ctl_int::ElemValue;
+
+    const NAMES: [&core::ffi::CStr; 5] = [c"Master Playback Volume", c"Master Playback Switch", c"Input Source",
+        c"Coefficients", c"IEC958 Playback Default"];
+
+    #[derive(Default)]
+    struct Card { vol: [i32; 2], switch: bool, source: u32, coef: [u8; 4], iec: [u8; 24], broken: bool }
+
+    impl CtlExt for Card {
+        fn setup(&mut self, ext: &mut Handle) -> Result<()> { ext.set_id("RustState"); Ok(()) }
+        fn elem_count(&mut self) -> u32 { NAMES.len() as u32 }
+        fn elem_list(&mut self, offset: u32) -> Result<ElemId> {
+            let mut id = ElemId::new(if offset == 4 { ElemIface::PCM } else { ElemIface::Mixer });
+            id.set_name(NAMES[offset as usize]);
+            Ok(id)
+        }
+        fn get_attribute(&mut self, key: u32) -> Result<(ElemType, Access, u32)> {
+            Ok(match key {
+                0 => (ElemType::Integer, Access::READWRITE, 2),
+                1 => (ElemType::Boolean, Access::READWRITE, 1),
+                2 => (ElemType::Enumerated, Access::READWRITE, 1),
+                3 => (ElemType::Bytes, Access::READWRITE, 4),
+                _ => (ElemType::IEC958, Access::READWRITE, 1),
+            })
+        }
+        fn get_integer_info(&mut self, _: u32) -> Result<(i32, i32, i32)> { Ok((0, 100, 1)) }
+        fn get_enumerated_info(&mut self, _: u32) -> Result<u32> { Ok(2) }
+        fn get_enumerated_name(&mut self, _: u32, item: u32) -> Result<&str> { Ok(["Mic", "Line In"][item as usize]) }
+        fn read(&mut self, key: u32, value: &mut ElemValue) -> Result<()> {
+            match key {
+                0 => { value.set_integer(0, self.vol[0]); value.set_integer(1, self.vol[1]); }
+                1 => { value.set_boolean(0, self.switch); }
+                2 => { value.set_enumerated(0, self.source); }
+                3 if self.broken => return Err(Error::new("read", libc::EIO)),
+                3 => for (i, &b) in self.coef.iter().enumerate() { value.set_byte(i as u32, b); },
+                _ => { value.set_iec958(&Iec958Status(self.iec)); }
+            }
+            Ok(())
+        }
+        fn write(&mut self, key: u32, value: &ElemValue) -> Result<bool> {
+            match key {
+                0 => self.vol = [value.get_integer(0).unwrap(), value.get_integer(1).unwrap()],
+                1 => self.switch = value.get_boolean(0).unwrap(),
+                2 => self.source = value.get_enumerated(0).unwrap(),
+                3 => self.coef.copy_from_slice(&value.get_bytes().unwrap()[..4]),
+                _ => self.iec = value.get_iec958().unwrap().0,
+            }
+            Ok(true)
+        }
+    }
+
+    let mut iec = Iec958Status::consumer();
+    iec.set_non_audio(true);
+    let src = Card { vol: [30, 70], switch: true, source: 1, coef: [1, 0x23, 0xab, 0xff], iec: iec.0, broken: false };
+    let ctl = ctl_ext::create("rust_state_src", false, src).unwrap();
+    let (saved, errors) = store_string(&ctl).unwrap();
+    assert!(errors.is_empty());
+    assert!(saved.contains("'Line In'"));
+    assert!(saved.contains("0123abff"));
+
+    let dst = ctl_ext::create("rust_state_dst", false, Card::default()).unwrap();
+    assert!(restore_string(&dst, &saved).unwrap().is_empty());
+    assert_eq!(store_string(&dst).unwrap().0, saved);
+
+    // Unknown elements are reported, but do not stop the others from being restored
+    let edited = saved.replace("Master Playback Switch", "Missing Switch").replace("value 'Line In'", "value Mic");
+    let dst = ctl_ext::create("rust_state_dst", false, Card::default()).unwrap();
+    let errors = restore_string(&dst, &edited).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].name.as_deref(), Some("Missing Switch"));
+    let mut v = ElemValue::new(ElemType::Enumerated).unwrap();
+    v.set_id(&dst.elem_list().unwrap().get_id(2).unwrap());
+    dst.elem_read(&mut v).unwrap();
+    assert_eq!(v.get_enumerated(0), Some(0));
+    let mut v = ElemValue::new(ElemType::Integer).unwrap();
+    v.set_id(&dst.elem_list().unwrap().get_id(0).unwrap());
+    dst.elem_read(&mut v).unwrap();
+    assert_eq!((v.get_integer(0), v.get_integer(1)), (Some(30), Some(70)));
+
+    // An element that cannot be read is reported, and the others are still stored
+    let broken = ctl_ext::create("rust_state_broken", false, Card { broken: true, ..Card::default() }).unwrap();
+    let (partial, errors) = store_string(&broken).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!((errors[0].control.as_str(), errors[0].name.as_deref()), ("4", Some("Coefficients")));
+    assert!(partial.contains("Input Source") && !partial.contains("Coefficients"));
+}
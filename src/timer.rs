@@ -0,0 +1,578 @@
+//! Timer API - for timing and synchronization
+//!
+//! # Example
+//! Wait for ten ticks of the system timer.
+//!
+//! ```
+//! use alsa::timer::{Timer, Params};
+//!
+//! let timer = Timer::new("hw:CLASS=1,SCLASS=0,CARD=0,DEV=0,SUBDEV=0", false, false).unwrap();
+//! let params = Params::new().unwrap();
+//! params.set_auto_start(true).unwrap();
+//! params.set_ticks(1);
+//! timer.params(&params).unwrap();
+//! timer.start().unwrap();
+//!
+//! let mut buf = [alsa::timer::Read::default(); 4];
+//! let mut ticks = 0;
+//! while ticks < 10 {
+//!     let n = timer.read(&mut buf).unwrap();
+//!     for r in &buf[..n] { ticks += r.get_ticks() }
+//! }
+//! timer.stop().unwrap();
+//! ```
+//!
+//! # Example
+//! List all timers and their resolution.
+//!
+//! ```
+//! use alsa::timer::{Query, QueryIter};
+//!
+//! let q = Query::new("hw").unwrap();
+//! for id in QueryIter::new(&q).map(|id| id.unwrap()) {
+//!     let info = q.info(&id).unwrap();
+//!     println!("{}: {} ({} ns)", id, info.get_name().unwrap(), info.get_resolution());
+//! }
+//! ```
+
+use libc::{c_int, c_uint, c_long, c_ulong, c_void, c_short, size_t, timespec, pollfd};
+use crate::alsa;
+use super::error::*;
+use super::poll;
+use crate::direct::asound_ioctl::{snd_timer_gparams, snd_timer_gstatus, snd_timer_id};
+use ::alloc::ffi::CString;
+use core::ffi::CStr;
+use core::{ptr, fmt, mem};
+
+alsa_enum!(
+    /// [SND_TIMER_CLASS_xxx](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) constants
+    Class, ALL_CLASSES[5],
+
+    None = SND_TIMER_CLASS_NONE,
+    Slave = SND_TIMER_CLASS_SLAVE,
+    Global = SND_TIMER_CLASS_GLOBAL,
+    Card = SND_TIMER_CLASS_CARD,
+    PCM = SND_TIMER_CLASS_PCM,
+);
+
+alsa_enum!(
+    /// [SND_TIMER_SCLASS_xxx](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) constants
+    SlaveClass, ALL_SLAVE_CLASSES[4],
+
+    None = SND_TIMER_SCLASS_NONE,
+    Application = SND_TIMER_SCLASS_APPLICATION,
+    Sequencer = SND_TIMER_SCLASS_SEQUENCER,
+    OssSequencer = SND_TIMER_SCLASS_OSS_SEQUENCER,
+);
+
+alsa_enum!(
+    /// [SND_TIMER_EVENT_xxx](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) constants
+    EventType, ALL_EVENT_TYPES[15],
+
+    Resolution = SND_TIMER_EVENT_RESOLUTION,
+    Tick = SND_TIMER_EVENT_TICK,
+    Start = SND_TIMER_EVENT_START,
+    Stop = SND_TIMER_EVENT_STOP,
+    Continue = SND_TIMER_EVENT_CONTINUE,
+    Pause = SND_TIMER_EVENT_PAUSE,
+    Early = SND_TIMER_EVENT_EARLY,
+    Suspend = SND_TIMER_EVENT_SUSPEND,
+    Resume = SND_TIMER_EVENT_RESUME,
+    MStart = SND_TIMER_EVENT_MSTART,
+    MStop = SND_TIMER_EVENT_MSTOP,
+    MContinue = SND_TIMER_EVENT_MCONTINUE,
+    MPause = SND_TIMER_EVENT_MPAUSE,
+    MSuspend = SND_TIMER_EVENT_MSUSPEND,
+    MResume = SND_TIMER_EVENT_MRESUME,
+);
+
+/// [snd_timer_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+///
+/// A timer is opened either in "read" or in "tread" (timestamped read) mode, and
+/// then only the corresponding `read` or `tread` function can be used.
+#[derive(Debug)]
+pub struct Timer(*mut alsa::snd_timer_t, bool);
+
+unsafe impl Send for Timer {}
+
+impl Drop for Timer {
+    fn drop(&mut self) { unsafe { alsa::snd_timer_close(self.0) }; }
+}
+
+impl Timer {
+    /// Wrapper around open that takes a &str instead of a &CStr
+    pub fn new(name: &str, nonblock: bool, tread: bool) -> Result<Timer> {
+        Self::open(&CString::new(name).unwrap(), nonblock, tread)
+    }
+
+    /// Opens a timer, e g "hw:CLASS=1,SCLASS=0,CARD=0,DEV=0,SUBDEV=0" for the system timer.
+    ///
+    /// If `tread` is true, the timer is opened in timestamped read mode, i e, read
+    /// events with the `tread` function instead of the `read` function.
+    /// Does not offer async mode (it's not very Rustic anyway)
+    pub fn open(name: &CStr, nonblock: bool, tread: bool) -> Result<Timer> {
+        let mut h = ptr::null_mut();
+        let mut mode = 0;
+        if nonblock { mode |= alsa::SND_TIMER_OPEN_NONBLOCK as c_int };
+        if tread { mode |= alsa::SND_TIMER_OPEN_TREAD as c_int };
+        acheck!(snd_timer_open(&mut h, name.as_ptr(), mode)).map(|_| Timer(h, tread))
+    }
+
+    /// Opens the timer identified by `id`, e g, as returned from `QueryIter`.
+    pub fn from_id(id: &Id, nonblock: bool, tread: bool) -> Result<Timer> {
+        Self::new(&::alloc::format!("{}", id), nonblock, tread)
+    }
+
+    pub fn info(&self) -> Result<Info> {
+        Info::new().and_then(|i| acheck!(snd_timer_info(self.0, i.0)).map(|_| i))
+    }
+
+    pub fn params(&self, p: &Params) -> Result<()> {
+        acheck!(snd_timer_params(self.0, p.0)).map(|_| ())
+    }
+
+    pub fn status(&self) -> Result<Status> {
+        Status::new().and_then(|s| acheck!(snd_timer_status(self.0, s.0)).map(|_| s))
+    }
+
+    pub fn start(&self) -> Result<()> { acheck!(snd_timer_start(self.0)).map(|_| ()) }
+    pub fn stop(&self) -> Result<()> { acheck!(snd_timer_stop(self.0)).map(|_| ()) }
+    pub fn continue_(&self) -> Result<()> { acheck!(snd_timer_continue(self.0)).map(|_| ()) }
+
+    /// Returns true if the timer was opened in timestamped read mode.
+    pub fn is_tread(&self) -> bool { self.1 }
+
+    fn read_raw(&self, buf: *mut c_void, size: usize) -> Result<usize> {
+        let r = unsafe { alsa::snd_timer_read(self.0, buf, size as size_t) };
+        if r < 0 { Err(Error::new("snd_timer_read", -r as c_int)) }
+        else { Ok(r as usize) }
+    }
+
+    /// Reads tick events. On success, returns the number of events read.
+    ///
+    /// Only available if the timer was not opened in timestamped read mode.
+    pub fn read(&self, buf: &mut [Read]) -> Result<usize> {
+        if self.1 { return Err(Error::unsupported("snd_timer_read")) }
+        self.read_raw(buf.as_mut_ptr() as *mut c_void, mem::size_of_val(buf))
+            .map(|r| r / mem::size_of::<Read>())
+    }
+
+    /// Reads timestamped events. On success, returns the number of events read.
+    ///
+    /// Only available if the timer was opened in timestamped read mode.
+    pub fn tread(&self, buf: &mut [TRead]) -> Result<usize> {
+        if !self.1 { return Err(Error::unsupported("snd_timer_read")) }
+        self.read_raw(buf.as_mut_ptr() as *mut c_void, mem::size_of_val(buf))
+            .map(|r| r / mem::size_of::<TRead>())
+    }
+}
+
+impl poll::Descriptors for Timer {
+    fn count(&self) -> usize {
+        unsafe { alsa::snd_timer_poll_descriptors_count(self.0) as usize }
+    }
+    fn fill(&self, p: &mut [pollfd]) -> Result<usize> {
+        let z = unsafe { alsa::snd_timer_poll_descriptors(self.0, p.as_mut_ptr(), p.len() as c_uint) };
+        from_code("snd_timer_poll_descriptors", z).map(|_| z as usize)
+    }
+    fn revents(&self, p: &[pollfd]) -> Result<poll::Flags> {
+        let mut r = 0;
+        let z = unsafe { alsa::snd_timer_poll_descriptors_revents(self.0, p.as_ptr() as *mut pollfd, p.len() as c_uint, &mut r) };
+        from_code("snd_timer_poll_descriptors_revents", z).map(|_| poll::Flags::from_bits_truncate(r as c_short))
+    }
+}
+
+/// [snd_timer_info_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+#[derive(Debug)]
+pub struct Info(*mut alsa::snd_timer_info_t);
+
+impl Drop for Info {
+    fn drop(&mut self) { unsafe { alsa::snd_timer_info_free(self.0) }; }
+}
+
+impl Info {
+    fn new() -> Result<Info> {
+        let mut p = ptr::null_mut();
+        acheck!(snd_timer_info_malloc(&mut p)).map(|_| Info(p))
+    }
+
+    pub fn is_slave(&self) -> bool { unsafe { alsa::snd_timer_info_is_slave(self.0) != 0 } }
+    pub fn get_card(&self) -> i32 { unsafe { alsa::snd_timer_info_get_card(self.0) as i32 } }
+    pub fn get_id(&self) -> Result<&str> {
+        from_const("snd_timer_info_get_id", unsafe { alsa::snd_timer_info_get_id(self.0) })
+    }
+    pub fn get_name(&self) -> Result<&str> {
+        from_const("snd_timer_info_get_name", unsafe { alsa::snd_timer_info_get_name(self.0) })
+    }
+    /// Timer resolution in ns
+    pub fn get_resolution(&self) -> i64 { unsafe { alsa::snd_timer_info_get_resolution(self.0) as i64 } }
+}
+
+/// [snd_timer_params_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+#[derive(Debug)]
+pub struct Params(*mut alsa::snd_timer_params_t);
+
+impl Drop for Params {
+    fn drop(&mut self) { unsafe { alsa::snd_timer_params_free(self.0) }; }
+}
+
+impl Params {
+    pub fn new() -> Result<Params> {
+        let mut p = ptr::null_mut();
+        acheck!(snd_timer_params_malloc(&mut p)).map(|_| Params(p))
+    }
+
+    pub fn set_auto_start(&self, v: bool) -> Result<()> {
+        acheck!(snd_timer_params_set_auto_start(self.0, if v { 1 } else { 0 })).map(|_| ())
+    }
+    pub fn get_auto_start(&self) -> bool { unsafe { alsa::snd_timer_params_get_auto_start(self.0) != 0 } }
+
+    pub fn set_exclusive(&self, v: bool) -> Result<()> {
+        acheck!(snd_timer_params_set_exclusive(self.0, if v { 1 } else { 0 })).map(|_| ())
+    }
+    pub fn get_exclusive(&self) -> bool { unsafe { alsa::snd_timer_params_get_exclusive(self.0) != 0 } }
+
+    pub fn set_early_event(&self, v: bool) -> Result<()> {
+        acheck!(snd_timer_params_set_early_event(self.0, if v { 1 } else { 0 })).map(|_| ())
+    }
+    pub fn get_early_event(&self) -> bool { unsafe { alsa::snd_timer_params_get_early_event(self.0) != 0 } }
+
+    /// Number of timer resolution units between events
+    pub fn set_ticks(&self, v: i64) { unsafe { alsa::snd_timer_params_set_ticks(self.0, v as c_long) } }
+    pub fn get_ticks(&self) -> i64 { unsafe { alsa::snd_timer_params_get_ticks(self.0) as i64 } }
+
+    pub fn set_queue_size(&self, v: i64) { unsafe { alsa::snd_timer_params_set_queue_size(self.0, v as c_long) } }
+    pub fn get_queue_size(&self) -> i64 { unsafe { alsa::snd_timer_params_get_queue_size(self.0) as i64 } }
+
+    /// Sets which events to receive in timestamped read mode.
+    pub fn set_filter(&self, events: &[EventType]) {
+        let f = events.iter().fold(0, |f, &e| f | (1 << e as c_uint));
+        unsafe { alsa::snd_timer_params_set_filter(self.0, f) }
+    }
+
+    pub fn get_filter(&self) -> impl Iterator<Item=EventType> {
+        let f = unsafe { alsa::snd_timer_params_get_filter(self.0) };
+        EventType::all().iter().cloned().filter(move |&e| f & (1 << e as c_uint) != 0)
+    }
+}
+
+/// [snd_timer_status_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+#[derive(Debug)]
+pub struct Status(*mut alsa::snd_timer_status_t);
+
+impl Drop for Status {
+    fn drop(&mut self) { unsafe { alsa::snd_timer_status_free(self.0) }; }
+}
+
+impl Status {
+    fn new() -> Result<Status> {
+        let mut p = ptr::null_mut();
+        acheck!(snd_timer_status_malloc(&mut p)).map(|_| Status(p))
+    }
+
+    pub fn get_timestamp(&self) -> timespec { unsafe { alsa::snd_timer_status_get_timestamp(self.0) } }
+    /// Timer resolution in ns
+    pub fn get_resolution(&self) -> i64 { unsafe { alsa::snd_timer_status_get_resolution(self.0) as i64 } }
+    /// Number of lost interrupts
+    pub fn get_lost(&self) -> i64 { unsafe { alsa::snd_timer_status_get_lost(self.0) as i64 } }
+    /// Number of overruns
+    pub fn get_overrun(&self) -> i64 { unsafe { alsa::snd_timer_status_get_overrun(self.0) as i64 } }
+    /// Number of events in the queue
+    pub fn get_queue(&self) -> i64 { unsafe { alsa::snd_timer_status_get_queue(self.0) as i64 } }
+}
+
+/// [snd_timer_read_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+pub struct Read(alsa::snd_timer_read_t);
+
+impl Default for Read {
+    fn default() -> Self { Read(alsa::snd_timer_read_t { resolution: 0, ticks: 0 }) }
+}
+
+impl Read {
+    /// Timer resolution in ns
+    pub fn get_resolution(&self) -> u32 { self.0.resolution as u32 }
+    pub fn get_ticks(&self) -> u32 { self.0.ticks as u32 }
+}
+
+impl fmt::Debug for Read {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Read(resolution: {} ns, ticks: {})", self.get_resolution(), self.get_ticks())
+    }
+}
+
+/// [snd_timer_tread_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+#[repr(transparent)]
+pub struct TRead(alsa::snd_timer_tread_t);
+
+impl Default for TRead {
+    fn default() -> Self { TRead(unsafe { mem::zeroed() }) }
+}
+
+impl Clone for TRead {
+    fn clone(&self) -> Self {
+        TRead(alsa::snd_timer_tread_t { event: self.0.event, tstamp: self.0.tstamp, val: self.0.val })
+    }
+}
+
+impl TRead {
+    pub fn get_event(&self) -> Result<EventType> {
+        EventType::from_c_int(self.0.event as c_int, "snd_timer_tread_t")
+    }
+    pub fn get_tstamp(&self) -> timespec { self.0.tstamp }
+    /// Event specific value, e g, the number of ticks for `EventType::Tick`
+    pub fn get_val(&self) -> u32 { self.0.val as u32 }
+}
+
+impl fmt::Debug for TRead {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TRead({:?}, tstamp: {}.{:09}, val: {})", self.get_event(),
+            self.0.tstamp.tv_sec, self.0.tstamp.tv_nsec, self.get_val())
+    }
+}
+
+/// [snd_timer_id_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+///
+/// No allocation (the timer id is a small, fixed size struct).
+/// The `Display` implementation formats the id as a device name that can be used to open the timer.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Id([c_int; 5]);
+
+impl Id {
+    pub fn new(class: Class, sclass: SlaveClass, card: i32, device: i32, subdevice: i32) -> Id {
+        assert!(unsafe { alsa::snd_timer_id_sizeof() } == mem::size_of::<Id>());
+        Id([class as c_int, sclass as c_int, card as c_int, device as c_int, subdevice as c_int])
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *mut alsa::snd_timer_id_t {
+        self.0.as_ptr() as *const _ as *mut alsa::snd_timer_id_t
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut alsa::snd_timer_id_t {
+        self.0.as_mut_ptr() as *mut alsa::snd_timer_id_t
+    }
+
+    pub fn get_class(&self) -> Class { Class::from_c_int(
+        unsafe { alsa::snd_timer_id_get_class(self.as_ptr()) }, "snd_timer_id_get_class").unwrap() }
+    pub fn get_sclass(&self) -> SlaveClass { SlaveClass::from_c_int(
+        unsafe { alsa::snd_timer_id_get_sclass(self.as_ptr()) }, "snd_timer_id_get_sclass").unwrap() }
+    pub fn get_card(&self) -> i32 { unsafe { alsa::snd_timer_id_get_card(self.as_ptr()) as i32 } }
+    pub fn get_device(&self) -> i32 { unsafe { alsa::snd_timer_id_get_device(self.as_ptr()) as i32 } }
+    pub fn get_subdevice(&self) -> i32 { unsafe { alsa::snd_timer_id_get_subdevice(self.as_ptr()) as i32 } }
+
+    pub fn set_class(&mut self, v: Class) { unsafe { alsa::snd_timer_id_set_class(self.as_mut_ptr(), v as c_int) } }
+    pub fn set_sclass(&mut self, v: SlaveClass) { unsafe { alsa::snd_timer_id_set_sclass(self.as_mut_ptr(), v as c_int) } }
+    pub fn set_card(&mut self, v: i32) { unsafe { alsa::snd_timer_id_set_card(self.as_mut_ptr(), v as c_int) } }
+    pub fn set_device(&mut self, v: i32) { unsafe { alsa::snd_timer_id_set_device(self.as_mut_ptr(), v as c_int) } }
+    pub fn set_subdevice(&mut self, v: i32) { unsafe { alsa::snd_timer_id_set_subdevice(self.as_mut_ptr(), v as c_int) } }
+}
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "hw:CLASS={},SCLASS={},CARD={},DEV={},SUBDEV={}", self.0[0], self.0[1], self.0[2], self.0[3], self.0[4])
+    }
+}
+
+impl fmt::Debug for Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Id({:?}, {:?}, card={}, device={}, subdevice={})", self.get_class(), self.get_sclass(),
+            self.get_card(), self.get_device(), self.get_subdevice())
+    }
+}
+
+/// [snd_timer_query_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+#[derive(Debug)]
+pub struct Query(*mut alsa::snd_timer_query_t);
+
+unsafe impl Send for Query {}
+
+impl Drop for Query {
+    fn drop(&mut self) { unsafe { alsa::snd_timer_query_close(self.0) }; }
+}
+
+impl Query {
+    /// Wrapper around open that takes a &str instead of a &CStr
+    pub fn new(name: &str) -> Result<Query> {
+        Self::open(&CString::new(name).unwrap())
+    }
+
+    /// Opens a timer query interface, usually named "hw".
+    pub fn open(name: &CStr) -> Result<Query> {
+        let mut h = ptr::null_mut();
+        acheck!(snd_timer_query_open(&mut h, name.as_ptr(), 0)).map(|_| Query(h))
+    }
+
+    pub fn iter(&self) -> QueryIter<'_> { QueryIter::new(self) }
+
+    pub fn info(&self, id: &Id) -> Result<GInfo> {
+        let i = GInfo::new()?;
+        acheck!(snd_timer_ginfo_set_tid(i.0, id.as_ptr()))?;
+        acheck!(snd_timer_query_info(self.0, i.0)).map(|_| i)
+    }
+
+    /// Sets the period of a global timer. Only a few timers support this.
+    pub fn params(&self, p: &mut GParams) -> Result<()> {
+        acheck!(snd_timer_query_params(self.0, &mut p.0 as *mut _ as *mut alsa::snd_timer_gparams_t)).map(|_| ())
+    }
+
+    pub fn status(&self, id: &Id) -> Result<GStatus> {
+        let mut s: GStatus = unsafe { mem::zeroed() };
+        s.0.tid = id.to_kernel();
+        acheck!(snd_timer_query_status(self.0, &mut s.0 as *mut _ as *mut alsa::snd_timer_gstatus_t)).map(|_| s)
+    }
+}
+
+/// Iterates over all timers of a `Query`, see [snd_timer_query_next_device](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html)
+#[derive(Debug)]
+pub struct QueryIter<'a>(&'a Query, Id);
+
+impl<'a> QueryIter<'a> {
+    pub fn new(q: &'a Query) -> QueryIter<'a> { QueryIter(q, Id::new(Class::None, SlaveClass::None, -1, -1, -1)) }
+}
+
+impl<'a> Iterator for QueryIter<'a> {
+    type Item = Result<Id>;
+    fn next(&mut self) -> Option<Result<Id>> {
+        match acheck!(snd_timer_query_next_device((self.0).0, self.1.as_mut_ptr())) {
+            Err(e) => Some(Err(e)),
+            Ok(_) if self.1.get_class() == Class::None => None,
+            Ok(_) => Some(Ok(self.1)),
+        }
+    }
+}
+
+/// [snd_timer_ginfo_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+#[derive(Debug)]
+pub struct GInfo(*mut alsa::snd_timer_ginfo_t);
+
+impl Drop for GInfo {
+    fn drop(&mut self) { unsafe { alsa::snd_timer_ginfo_free(self.0) }; }
+}
+
+impl GInfo {
+    fn new() -> Result<GInfo> {
+        let mut p = ptr::null_mut();
+        acheck!(snd_timer_ginfo_malloc(&mut p)).map(|_| GInfo(p))
+    }
+
+    pub fn get_tid(&self) -> Id {
+        let mut id = Id::new(Class::None, SlaveClass::None, -1, -1, -1);
+        unsafe { alsa::snd_timer_id_copy(id.as_mut_ptr(), alsa::snd_timer_ginfo_get_tid(self.0)) };
+        id
+    }
+    pub fn get_flags(&self) -> u32 { unsafe { alsa::snd_timer_ginfo_get_flags(self.0) as u32 } }
+    pub fn get_card(&self) -> i32 { unsafe { alsa::snd_timer_ginfo_get_card(self.0) as i32 } }
+    pub fn get_id(&self) -> Result<&str> {
+        from_const("snd_timer_ginfo_get_id", unsafe { alsa::snd_timer_ginfo_get_id(self.0) })
+    }
+    pub fn get_name(&self) -> Result<&str> {
+        from_const("snd_timer_ginfo_get_name", unsafe { alsa::snd_timer_ginfo_get_name(self.0) })
+    }
+    /// Timer resolution in ns
+    pub fn get_resolution(&self) -> u64 { unsafe { alsa::snd_timer_ginfo_get_resolution(self.0) as u64 } }
+    /// Minimal period resolution in ns
+    pub fn get_resolution_min(&self) -> u64 { unsafe { alsa::snd_timer_ginfo_get_resolution_min(self.0) as u64 } }
+    /// Maximal period resolution in ns
+    pub fn get_resolution_max(&self) -> u64 { unsafe { alsa::snd_timer_ginfo_get_resolution_max(self.0) as u64 } }
+    /// Number of clients currently using the timer
+    pub fn get_clients(&self) -> u32 { unsafe { alsa::snd_timer_ginfo_get_clients(self.0) as u32 } }
+}
+
+// alsa-lib has no accessor functions for snd_timer_gparams_t and snd_timer_gstatus_t,
+// so they wrap the kernel structs directly.
+
+impl Id {
+    fn from_kernel(t: &snd_timer_id) -> Id { Id([t.dev_class, t.dev_sclass, t.card, t.device, t.subdevice]) }
+    fn to_kernel(self) -> snd_timer_id {
+        snd_timer_id { dev_class: self.0[0], dev_sclass: self.0[1], card: self.0[2], device: self.0[3], subdevice: self.0[4] }
+    }
+}
+
+/// [snd_timer_gparams_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+///
+/// The period is given in seconds, as a fraction of `period_num` / `period_den`.
+#[derive(Copy, Clone)]
+pub struct GParams(snd_timer_gparams);
+
+impl GParams {
+    pub fn new(id: &Id, period_num: u64, period_den: u64) -> GParams {
+        GParams(snd_timer_gparams { tid: id.to_kernel(), period_num: period_num as c_ulong, period_den: period_den as c_ulong, reserved: [0; 32] })
+    }
+    pub fn get_tid(&self) -> Id { Id::from_kernel(&self.0.tid) }
+    pub fn get_period_num(&self) -> u64 { self.0.period_num as u64 }
+    pub fn get_period_den(&self) -> u64 { self.0.period_den as u64 }
+}
+
+impl fmt::Debug for GParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GParams({}, period: {}/{} s)", self.get_tid(), self.get_period_num(), self.get_period_den())
+    }
+}
+
+/// [snd_timer_gstatus_t](http://www.alsa-project.org/alsa-doc/alsa-lib/group___timer.html) wrapper
+#[derive(Copy, Clone)]
+pub struct GStatus(snd_timer_gstatus);
+
+impl GStatus {
+    pub fn get_tid(&self) -> Id { Id::from_kernel(&self.0.tid) }
+    /// Current period resolution in ns
+    pub fn get_resolution(&self) -> u64 { self.0.resolution as u64 }
+    /// Precise current period resolution in seconds, numerator
+    pub fn get_resolution_num(&self) -> u64 { self.0.resolution_num as u64 }
+    /// Precise current period resolution in seconds, denominator
+    pub fn get_resolution_den(&self) -> u64 { self.0.resolution_den as u64 }
+}
+
+impl fmt::Debug for GStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GStatus({}, resolution: {} ns)", self.get_tid(), self.get_resolution())
+    }
+}
+
+#[test]
+fn timer_id_name() {
+    use ::alloc::string::ToString;
+    let mut id = Id::new(Class::Global, SlaveClass::None, -1, alsa::SND_TIMER_GLOBAL_HRTIMER as i32, 0);
+    assert_eq!(id.to_string(), "hw:CLASS=1,SCLASS=0,CARD=-1,DEV=3,SUBDEV=0");
+    id.set_class(Class::PCM);
+    id.set_card(1);
+    assert_eq!(id.get_class(), Class::PCM);
+    assert_eq!(id.get_card(), 1);
+    assert_eq!(id.get_device(), 3);
+}
+
+#[test]
+fn print_timers() {
+    extern crate std;
+    let q = Query::new("hw").unwrap();
+    for id in q.iter().map(|id| id.unwrap()) {
+        let i = q.info(&id).unwrap();
+        assert_eq!(i.get_tid(), id);
+        std::println!("{:?}: {} ({}), resolution {} ns ({} - {}), {} clients", id, i.get_id().unwrap(), i.get_name().unwrap(),
+            i.get_resolution(), i.get_resolution_min(), i.get_resolution_max(), i.get_clients());
+    }
+}
+
+#[test]
+fn print_system_timer() {
+    extern crate std;
+    let t = Timer::new("hw:CLASS=1,SCLASS=0,CARD=0,DEV=0,SUBDEV=0", false, true).unwrap();
+    let i = t.info().unwrap();
+    std::println!("Timer {} ({}), resolution {} ns, slave: {}", i.get_id().unwrap(), i.get_name().unwrap(),
+        i.get_resolution(), i.is_slave());
+    let p = Params::new().unwrap();
+    p.set_auto_start(true).unwrap();
+    p.set_ticks(1);
+    p.set_filter(&[EventType::Tick, EventType::Start]);
+    assert_eq!(p.get_filter().collect::<::alloc::vec::Vec<_>>(), &[EventType::Tick, EventType::Start]);
+    t.params(&p).unwrap();
+    t.start().unwrap();
+    let mut buf = [TRead::default(), TRead::default()];
+    let n = t.tread(&mut buf).unwrap();
+    for e in &buf[..n] { std::println!("  {:?}", e) }
+    t.stop().unwrap();
+    std::println!("Status: queue {}, lost {}", t.status().unwrap().get_queue(), t.status().unwrap().get_lost());
+}
@@ -0,0 +1,346 @@
+//! TLV (type-length-value) metadata of control elements, such as dB scales and channel maps
+//!
+//! Read the raw data with `Ctl::elem_tlv_read` or `hctl::Elem::tlv_read`, then parse it with `Tlv::parse`.
+//! The dB conversions are done the same way as alsa-lib does them, but without any calls into alsa-lib.
+//!
+//! # Example
+//! ```
+//! use alsa::tlv::{Tlv, DbScale};
+//! use alsa::mixer::MilliBel;
+//!
+//! // A volume from -50 dB to 0 dB in 0.5 dB steps, where the lowest value mutes
+//! let raw = [1, 8, (-5000i32) as u32, 0x10032];
+//! let tlv = Tlv::parse(&raw).unwrap();
+//! assert_eq!(tlv, Tlv::DbScale(DbScale { min: MilliBel(-5000), step: MilliBel(50), mute: true }));
+//! assert_eq!(tlv.to_db(0, 100, 90).unwrap(), MilliBel(-500));
+//! ```
+
+use crate::chmap::{ChmapPosition, ChmapType};
+use crate::error::*;
+use crate::mixer::MilliBel;
+use crate::Round;
+use ::alloc::vec::Vec;
+use core::cmp;
+
+/// The dB value returned for muted volumes
+pub const DB_GAIN_MUTE: MilliBel = MilliBel(-9999999);
+
+const TLVT_CONTAINER: u32 = 0;
+const TLVT_DB_SCALE: u32 = 1;
+const TLVT_DB_LINEAR: u32 = 2;
+const TLVT_DB_RANGE: u32 = 3;
+const TLVT_DB_MINMAX: u32 = 4;
+const TLVT_DB_MINMAX_MUTE: u32 = 5;
+const TLVT_CHMAP_FIXED: u32 = 0x101;
+const TLVT_CHMAP_VAR: u32 = 0x102;
+const TLVT_CHMAP_PAIRED: u32 = 0x103;
+
+const DB_SCALE_MASK: u32 = 0xffff;
+const DB_SCALE_MUTE: u32 = 0x10000;
+const CHMAP_POSITION_MASK: u32 = 0xffff;
+
+#[cfg(feature = "std")]
+pub(crate) fn exp10(x: f64) -> f64 { 10f64.powf(x) }
+#[cfg(feature = "std")]
+pub(crate) fn log10(x: f64) -> f64 { x.log10() }
+
+#[cfg(not(feature = "std"))]
+mod libm {
+    #[link(name = "m")]
+    extern "C" {
+        pub fn pow(x: f64, y: f64) -> f64;
+        pub fn log10(x: f64) -> f64;
+    }
+}
+#[cfg(not(feature = "std"))]
+pub(crate) fn exp10(x: f64) -> f64 { unsafe { libm::pow(10.0, x) } }
+#[cfg(not(feature = "std"))]
+pub(crate) fn log10(x: f64) -> f64 { unsafe { libm::log10(x) } }
+
+/// SNDRV_CTL_TLVT_DB_SCALE: the volume changes by `step` for every raw value
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DbScale {
+    /// The dB value of the lowest raw value
+    pub min: MilliBel,
+    pub step: MilliBel,
+    /// Whether the lowest raw value mutes
+    pub mute: bool,
+}
+
+/// SNDRV_CTL_TLVT_DB_LINEAR: the raw value is proportional to the amplitude
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DbLinear {
+    pub min: MilliBel,
+    pub max: MilliBel,
+}
+
+/// SNDRV_CTL_TLVT_DB_MINMAX: the raw value is proportional to the dB value
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DbMinMax {
+    pub min: MilliBel,
+    pub max: MilliBel,
+}
+
+/// SNDRV_CTL_TLVT_DB_MINMAX_MUTE: like `DbMinMax`, but the lowest raw value mutes
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DbMinMaxMute {
+    pub min: MilliBel,
+    pub max: MilliBel,
+}
+
+/// SNDRV_CTL_TLVT_DB_RANGE: different dB scales for different ranges of raw values
+///
+/// Each entry is the first and last raw value of the range, and the dB scale for that range.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DbRange(pub Vec<(i64, i64, Tlv)>);
+
+/// A parsed TLV
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Tlv {
+    Container(Vec<Tlv>),
+    DbScale(DbScale),
+    DbLinear(DbLinear),
+    DbRange(DbRange),
+    DbMinMax(DbMinMax),
+    DbMinMaxMute(DbMinMaxMute),
+    /// A channel map of type `Fixed`, `Var` or `Paired`. Flags such as phase inversion are masked out of the positions.
+    Chmap(ChmapType, Vec<ChmapPosition>),
+    /// A TLV of a type not known to this crate
+    Unknown(u32, Vec<u32>),
+}
+
+fn db(v: u32) -> MilliBel { MilliBel(v as i32 as i64) }
+
+fn db_raw(m: MilliBel) -> u32 { m.0 as i32 as u32 }
+
+fn invalid() -> Error { Error::new("Tlv::parse", libc::EINVAL) }
+
+fn unsupported() -> Error { Error::unsupported("Tlv: not a dB scale") }
+
+impl Tlv {
+    /// Parses a TLV, e g as returned from `Ctl::elem_tlv_read`.
+    ///
+    /// Any words after the end of the TLV are ignored.
+    pub fn parse(raw: &[u32]) -> Result<Tlv> {
+        Tlv::parse_one(raw).map(|(t, _)| t)
+    }
+
+    /// Returns the TLV and its size in words.
+    fn parse_one(raw: &[u32]) -> Result<(Tlv, usize)> {
+        if raw.len() < 2 { return Err(invalid()) }
+        let words = (raw[1] as usize).div_ceil(4);
+        let d = raw.get(2..2 + words).ok_or_else(invalid)?;
+        let two = || if d.len() >= 2 { Ok((db(d[0]), db(d[1]))) } else { Err(invalid()) };
+        let t = match raw[0] {
+            TLVT_CONTAINER => {
+                let mut v = Vec::new();
+                let mut pos = 0;
+                while pos < d.len() {
+                    let (t, size) = Tlv::parse_one(&d[pos..])?;
+                    v.push(t);
+                    pos += size;
+                }
+                Tlv::Container(v)
+            }
+            TLVT_DB_SCALE => {
+                if d.len() < 2 { return Err(invalid()) }
+                Tlv::DbScale(DbScale { min: db(d[0]), step: MilliBel((d[1] & DB_SCALE_MASK) as i64), mute: d[1] & DB_SCALE_MUTE != 0 })
+            }
+            TLVT_DB_LINEAR => { let (min, max) = two()?; Tlv::DbLinear(DbLinear { min, max }) },
+            TLVT_DB_MINMAX => { let (min, max) = two()?; Tlv::DbMinMax(DbMinMax { min, max }) },
+            TLVT_DB_MINMAX_MUTE => { let (min, max) = two()?; Tlv::DbMinMaxMute(DbMinMaxMute { min, max }) },
+            TLVT_DB_RANGE => {
+                let mut v = Vec::new();
+                let mut pos = 0;
+                while pos + 4 <= d.len() {
+                    let (t, size) = Tlv::parse_one(&d[pos + 2..])?;
+                    v.push((d[pos] as i32 as i64, d[pos + 1] as i32 as i64, t));
+                    pos += 2 + size;
+                }
+                Tlv::DbRange(DbRange(v))
+            }
+            c @ (TLVT_CHMAP_FIXED | TLVT_CHMAP_VAR | TLVT_CHMAP_PAIRED) => {
+                let t = match c { TLVT_CHMAP_FIXED => ChmapType::Fixed, TLVT_CHMAP_VAR => ChmapType::Var, _ => ChmapType::Paired };
+                Tlv::Chmap(t, d.iter().map(|&p| ChmapPosition::from_c_int((p & CHMAP_POSITION_MASK) as libc::c_int, "")
+                    .unwrap_or(ChmapPosition::Unknown)).collect())
+            }
+            c => Tlv::Unknown(c, d.into()),
+        };
+        Ok((t, 2 + words))
+    }
+
+    /// Builds the raw TLV words, e g for `Ctl::elem_tlv_write`.
+    pub fn to_raw(&self) -> Vec<u32> {
+        let (t, d) = match self {
+            Tlv::Container(v) => (TLVT_CONTAINER, v.iter().flat_map(|t| t.to_raw()).collect()),
+            Tlv::DbScale(s) => (TLVT_DB_SCALE, ::alloc::vec![db_raw(s.min),
+                (s.step.0 as u32 & DB_SCALE_MASK) | if s.mute { DB_SCALE_MUTE } else { 0 }]),
+            Tlv::DbLinear(s) => (TLVT_DB_LINEAR, ::alloc::vec![db_raw(s.min), db_raw(s.max)]),
+            Tlv::DbMinMax(s) => (TLVT_DB_MINMAX, ::alloc::vec![db_raw(s.min), db_raw(s.max)]),
+            Tlv::DbMinMaxMute(s) => (TLVT_DB_MINMAX_MUTE, ::alloc::vec![db_raw(s.min), db_raw(s.max)]),
+            Tlv::DbRange(r) => (TLVT_DB_RANGE, r.0.iter().flat_map(|(min, max, t)| {
+                [*min as u32, *max as u32].into_iter().chain(t.to_raw())
+            }).collect()),
+            Tlv::Chmap(t, v) => (match t {
+                ChmapType::Var => TLVT_CHMAP_VAR, ChmapType::Paired => TLVT_CHMAP_PAIRED, _ => TLVT_CHMAP_FIXED,
+            }, v.iter().map(|&p| p as u32).collect()),
+            Tlv::Unknown(t, d) => (*t, d.clone()),
+        };
+        let mut r = ::alloc::vec![t, (d.len() * 4) as u32];
+        r.extend(d);
+        r
+    }
+
+    /// The dB scale in this TLV: itself, or the first dB scale of a container.
+    fn db_scale(&self) -> Result<&Tlv> {
+        match self {
+            Tlv::Container(v) => v.iter().find_map(|t| t.db_scale().ok()).ok_or_else(unsupported),
+            Tlv::Chmap(..) | Tlv::Unknown(..) => Err(unsupported()),
+            _ => Ok(self),
+        }
+    }
+
+    /// Returns the dB values of the raw values `min` and `max`, where `min..=max` is the range of the element.
+    pub fn db_range(&self, min: i64, max: i64) -> Result<(MilliBel, MilliBel)> {
+        match self.db_scale()? {
+            Tlv::DbRange(r) => {
+                let mut res: Option<(MilliBel, MilliBel)> = None;
+                for (submin, submax, t) in &r.0 {
+                    let submax = cmp::min(*submax, max);
+                    let (rmin, rmax) = t.db_range(*submin, submax)?;
+                    res = Some(res.map_or((rmin, rmax), |(a, b)| (cmp::min(a, rmin), cmp::max(b, rmax))));
+                    if submax == max { break }
+                }
+                res.ok_or_else(invalid)
+            }
+            Tlv::DbScale(s) => Ok((if s.mute { DB_GAIN_MUTE } else { s.min }, MilliBel(s.min.0 + s.step.0 * (max - min)))),
+            Tlv::DbLinear(s) => Ok((s.min, s.max)),
+            Tlv::DbMinMax(s) => Ok((s.min, s.max)),
+            Tlv::DbMinMaxMute(s) => Ok((DB_GAIN_MUTE, s.max)),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Converts the raw value `value` to dB, where `min..=max` is the range of the element.
+    pub fn to_db(&self, min: i64, max: i64, value: i64) -> Result<MilliBel> {
+        match self.db_scale()? {
+            Tlv::DbRange(r) => r.0.iter().find(|(submin, submax, _)| value >= *submin && value <= *submax)
+                .ok_or_else(invalid).and_then(|(submin, submax, t)| t.to_db(*submin, *submax, value)),
+            Tlv::DbScale(s) => Ok(if s.mute && value <= min { DB_GAIN_MUTE } else { MilliBel((value - min) * s.step.0 + s.min.0) }),
+            Tlv::DbMinMax(DbMinMax { min: mindb, max: maxdb }) => Ok(minmax_to_db(*mindb, *maxdb, false, min, max, value)),
+            Tlv::DbMinMaxMute(DbMinMaxMute { min: mindb, max: maxdb }) => Ok(minmax_to_db(*mindb, *maxdb, true, min, max, value)),
+            Tlv::DbLinear(s) => Ok(if value <= min || max <= min { s.min }
+                else if value >= max { s.max }
+                else {
+                    let val = (value - min) as f64 / (max - min) as f64;
+                    if s.min <= DB_GAIN_MUTE { MilliBel((2000.0 * log10(val)) as i64 + s.max.0) }
+                    else {
+                        let lmin = exp10(s.min.0 as f64 / 2000.0);
+                        let lmax = exp10(s.max.0 as f64 / 2000.0);
+                        MilliBel((2000.0 * log10((lmax - lmin) * val + lmin)) as i64)
+                    }
+                }),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Converts `db` to a raw value, where `min..=max` is the range of the element.
+    ///
+    /// `dir` decides which raw value to pick, if `db` is between two of them.
+    pub fn from_db(&self, min: i64, max: i64, db: MilliBel, dir: Round) -> Result<i64> {
+        let up = dir == Round::Ceil;
+        match self.db_scale()? {
+            Tlv::DbRange(r) => {
+                let mut prev_submax = 0;
+                for (i, (submin, submax, t)) in r.0.iter().enumerate() {
+                    let submax = cmp::min(*submax, max);
+                    if let Ok((dbmin, dbmax)) = t.db_range(*submin, submax) {
+                        if db >= dbmin && db <= dbmax { return t.from_db(*submin, submax, db, dir) }
+                        if db < dbmin { return Ok(if up || i == 0 { *submin } else { prev_submax }) }
+                    }
+                    prev_submax = submax;
+                    if submax == max { break }
+                }
+                Ok(prev_submax)
+            }
+            Tlv::DbScale(s) => {
+                let dbmax = s.min.0 + s.step.0 * (max - min);
+                Ok(linear_from_db(s.min.0, dbmax, s.mute, up, min, max, db))
+            }
+            Tlv::DbMinMax(s) => Ok(linear_from_db(s.min.0, s.max.0, false, up, min, max, db)),
+            Tlv::DbMinMaxMute(s) => Ok(linear_from_db(s.min.0, s.max.0, true, up, min, max, db)),
+            Tlv::DbLinear(s) => Ok(if db <= s.min { min } else if db >= s.max { max } else {
+                let vmin = if s.min <= DB_GAIN_MUTE { 0.0 } else { exp10(s.min.0 as f64 / 2000.0) };
+                let vmax = if s.max.0 == 0 { 1.0 } else { exp10(s.max.0 as f64 / 2000.0) };
+                let v = (exp10(db.0 as f64 / 2000.0) - vmin) * (max - min) as f64 / (vmax - vmin);
+                let v = if up && v > (v as i64) as f64 { v as i64 + 1 } else { v as i64 };
+                v + min
+            }),
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+fn minmax_to_db(mindb: MilliBel, maxdb: MilliBel, mute: bool, min: i64, max: i64, value: i64) -> MilliBel {
+    if value <= min || max <= min { if mute { DB_GAIN_MUTE } else { mindb } }
+    else if value >= max { maxdb }
+    else { MilliBel((maxdb.0 - mindb.0) * (value - min) / (max - min) + mindb.0) }
+}
+
+/// Shared by the scales where the dB value is linear to the raw value
+fn linear_from_db(dbmin: i64, dbmax: i64, mute: bool, up: bool, min: i64, max: i64, db: MilliBel) -> i64 {
+    if db.0 <= dbmin {
+        if mute && up && db > DB_GAIN_MUTE { min + 1 } else { min }
+    }
+    else if db.0 >= dbmax { max }
+    else {
+        let mut v = (db.0 - dbmin) * (max - min);
+        if up { v += (dbmax - dbmin) - 1 }
+        v / (dbmax - dbmin) + min
+    }
+}
+
+#[test]
+fn tlv_db_scale() {
+    let raw = [TLVT_CONTAINER, 16, TLVT_DB_SCALE, 8, (-4650i32) as u32, 150 | DB_SCALE_MUTE];
+    let t = Tlv::parse(&raw).unwrap();
+    assert_eq!(t, Tlv::Container(::alloc::vec![Tlv::DbScale(DbScale { min: MilliBel(-4650), step: MilliBel(150), mute: true })]));
+    assert_eq!(t.to_raw(), &raw);
+    assert_eq!(t.db_range(0, 31).unwrap(), (DB_GAIN_MUTE, MilliBel(0)));
+    assert_eq!(t.to_db(0, 31, 0).unwrap(), DB_GAIN_MUTE);
+    assert_eq!(t.to_db(0, 31, 1).unwrap(), MilliBel(-4500));
+    assert_eq!(t.to_db(0, 31, 31).unwrap(), MilliBel(0));
+    assert_eq!(t.from_db(0, 31, MilliBel(-100), Round::Floor).unwrap(), 30);
+    assert_eq!(t.from_db(0, 31, MilliBel(-100), Round::Ceil).unwrap(), 31);
+    assert_eq!(t.from_db(0, 31, MilliBel(-6000), Round::Ceil).unwrap(), 1);
+    assert_eq!(t.from_db(0, 31, DB_GAIN_MUTE, Round::Ceil).unwrap(), 0);
+}
+
+#[test]
+fn tlv_db_range_and_linear() {
+    // Values 0-1 are muted, 2-9 go from -30 dB in 3 dB steps, 10-20 from -6 dB to 0 dB
+    let t = Tlv::DbRange(DbRange(::alloc::vec![
+        (0, 1, Tlv::DbMinMaxMute(DbMinMaxMute { min: MilliBel(-3300), max: MilliBel(-3000) })),
+        (2, 9, Tlv::DbScale(DbScale { min: MilliBel(-3000), step: MilliBel(300), mute: false })),
+        (10, 20, Tlv::DbMinMax(DbMinMax { min: MilliBel(-600), max: MilliBel(0) })),
+    ]));
+    assert_eq!(Tlv::parse(&t.to_raw()).unwrap(), t);
+    assert_eq!(t.db_range(0, 20).unwrap(), (DB_GAIN_MUTE, MilliBel(0)));
+    assert_eq!(t.to_db(0, 20, 0).unwrap(), DB_GAIN_MUTE);
+    assert_eq!(t.to_db(0, 20, 5).unwrap(), MilliBel(-2100));
+    assert_eq!(t.to_db(0, 20, 15).unwrap(), MilliBel(-300));
+    assert_eq!(t.from_db(0, 20, MilliBel(-2100), Round::Floor).unwrap(), 5);
+    assert_eq!(t.from_db(0, 20, MilliBel(-300), Round::Floor).unwrap(), 15);
+    assert!(t.to_db(0, 20, 21).is_err());
+
+    let l = Tlv::DbLinear(DbLinear { min: DB_GAIN_MUTE, max: MilliBel(0) });
+    assert_eq!(l.to_db(0, 100, 100).unwrap(), MilliBel(0));
+    assert_eq!(l.to_db(0, 100, 50).unwrap(), MilliBel(-602));
+    assert_eq!(l.from_db(0, 100, MilliBel(-602), Round::Floor).unwrap(), 50);
+    assert_eq!(l.from_db(0, 100, MilliBel(-602), Round::Ceil).unwrap(), 51);
+
+    let c = Tlv::parse(&[TLVT_CHMAP_FIXED, 8, 3, 4 | 0x10000]).unwrap();
+    assert_eq!(c, Tlv::Chmap(ChmapType::Fixed, ::alloc::vec![ChmapPosition::FL, ChmapPosition::FR]));
+    assert!(c.to_db(0, 1, 0).is_err());
+    assert!(Tlv::parse(&[TLVT_DB_SCALE, 8, 0]).is_err());
+}
@@ -0,0 +1,198 @@
+//! Use Case Manager - find out which PCMs and mixer controls to use on a card
+//!
+//! A use case configuration describes, for one sound card, the "verbs" (e g "HiFi" or "Voice Call"),
+//! the "devices" (e g "Speaker" or "Headphones") and "modifiers" of each verb, together with
+//! values such as which PCM and mixer element to use for a device.
+//! See the [alsa-lib documentation](https://www.alsa-project.org/alsa-doc/alsa-lib/group__ucm.html) for the identifiers.
+//!
+//! # Example
+//! ```no_run
+//! use alsa::ucm::Manager;
+//!
+//! for card in alsa::card::Iter::new().map(|c| c.unwrap()) {
+//!     // Cards without a use case configuration fail to open
+//!     let Ok(ucm) = Manager::new(&format!("hw:{}", card.get_index())) else { continue };
+//!     for (verb, _comment) in ucm.verbs().unwrap() {
+//!         ucm.set_verb(&verb).unwrap();
+//!         for (device, _comment) in ucm.devices(None).unwrap() {
+//!             println!("{}/{}: PCM {:?}, mixer {:?}", verb, device, ucm.get_value("PlaybackPCM", Some(&device), None),
+//!                 ucm.get_value("PlaybackMixerElem", Some(&device), None));
+//!         }
+//!     }
+//! }
+//! ```
+
+use libc::c_long;
+use super::error::*;
+use super::mixer::SelemId;
+use ::alloc::ffi::CString;
+use ::alloc::format;
+use ::alloc::string::{String, ToString};
+use ::alloc::vec::Vec;
+use core::ffi::CStr;
+use core::ptr;
+
+/// The verb that is set when no use case is active
+pub const VERB_INACTIVE: &str = "Inactive";
+
+// use-case.h is not part of alsa-sys
+mod ffi {
+    use libc::{c_char, c_int, c_long};
+
+    #[repr(C)]
+    pub struct snd_use_case_mgr_t { _private: [u8; 0] }
+
+    extern "C" {
+        pub fn snd_use_case_mgr_open(uc_mgr: *mut *mut snd_use_case_mgr_t, card_name: *const c_char) -> c_int;
+        pub fn snd_use_case_mgr_reload(uc_mgr: *mut snd_use_case_mgr_t) -> c_int;
+        pub fn snd_use_case_mgr_close(uc_mgr: *mut snd_use_case_mgr_t) -> c_int;
+        pub fn snd_use_case_mgr_reset(uc_mgr: *mut snd_use_case_mgr_t) -> c_int;
+        pub fn snd_use_case_get_list(uc_mgr: *mut snd_use_case_mgr_t, identifier: *const c_char,
+            list: *mut *mut *const c_char) -> c_int;
+        pub fn snd_use_case_free_list(list: *mut *const c_char, items: c_int) -> c_int;
+        pub fn snd_use_case_get(uc_mgr: *mut snd_use_case_mgr_t, identifier: *const c_char, value: *mut *const c_char) -> c_int;
+        pub fn snd_use_case_geti(uc_mgr: *mut snd_use_case_mgr_t, identifier: *const c_char, value: *mut c_long) -> c_int;
+        pub fn snd_use_case_set(uc_mgr: *mut snd_use_case_mgr_t, identifier: *const c_char, value: *const c_char) -> c_int;
+        pub fn snd_use_case_parse_selem_id(dst: *mut crate::alsa::snd_mixer_selem_id_t, ucm_id: *const c_char,
+            value: *const c_char) -> c_int;
+    }
+}
+
+/// Appends "/modifier_or_device/verb" as needed, in the format alsa-lib expects.
+fn identifier(name: &str, dev: Option<&str>, verb: Option<&str>) -> CString {
+    let s = match (dev, verb) {
+        (None, None) => name.to_string(),
+        (Some(d), None) => format!("{}/{}", name, d),
+        (d, Some(v)) => format!("{}/{}/{}", name, d.unwrap_or(""), v),
+    };
+    CString::new(s).unwrap()
+}
+
+/// snd_use_case_mgr_t wrapper
+#[derive(Debug)]
+pub struct Manager(*mut ffi::snd_use_case_mgr_t);
+
+unsafe impl Send for Manager {}
+
+impl Drop for Manager {
+    fn drop(&mut self) { unsafe { ffi::snd_use_case_mgr_close(self.0) }; }
+}
+
+impl Manager {
+    /// Wrapper around open that takes a &str instead of a &CStr
+    pub fn new(card_name: &str) -> Result<Manager> {
+        Self::open(&CString::new(card_name).unwrap())
+    }
+
+    /// Opens the use case configuration of a card, given as e g "hw:0" or the card's name.
+    pub fn open(card_name: &CStr) -> Result<Manager> {
+        let mut p = ptr::null_mut();
+        acheck!(ffi::snd_use_case_mgr_open(&mut p, card_name.as_ptr())).map(|_| Manager(p))
+    }
+
+    /// Reloads the configuration from disk.
+    pub fn reload(&self) -> Result<()> { acheck!(ffi::snd_use_case_mgr_reload(self.0)).map(|_| ()) }
+
+    /// Sets the verb to `VERB_INACTIVE` and disables all devices and modifiers.
+    pub fn reset(&self) -> Result<()> { acheck!(ffi::snd_use_case_mgr_reset(self.0)).map(|_| ()) }
+
+    /// Returns the list for `identifier`, e g "_verbs" or "_enadevs".
+    ///
+    /// For some identifiers, the list has two entries per item (name and comment).
+    pub fn get_list(&self, identifier: &str) -> Result<Vec<String>> {
+        let id = CString::new(identifier).unwrap();
+        let mut list = ptr::null_mut();
+        let n = acheck!(ffi::snd_use_case_get_list(self.0, id.as_ptr(), &mut list))?;
+        let r = (0..n as usize).map(|i| unsafe {
+            let s = *list.add(i);
+            if s.is_null() { Ok(String::new()) } else { from_const("snd_use_case_get_list", s).map(|s| s.to_string()) }
+        }).collect();
+        if n > 0 { unsafe { ffi::snd_use_case_free_list(list, n) }; }
+        r
+    }
+
+    fn get_pairs(&self, identifier: CString) -> Result<Vec<(String, String)>> {
+        let v = self.get_list(identifier.to_str().unwrap())?;
+        Ok(v.chunks(2).map(|c| (c[0].clone(), c.get(1).cloned().unwrap_or_default())).collect())
+    }
+
+    /// Returns name and comment of all verbs.
+    pub fn verbs(&self) -> Result<Vec<(String, String)>> { self.get_pairs(identifier("_verbs", None, None)) }
+
+    /// Returns name and comment of all devices of `verb`, or of the current verb if None.
+    pub fn devices(&self, verb: Option<&str>) -> Result<Vec<(String, String)>> { self.get_pairs(identifier("_devices", verb, None)) }
+
+    /// Returns name and comment of all modifiers of `verb`, or of the current verb if None.
+    pub fn modifiers(&self, verb: Option<&str>) -> Result<Vec<(String, String)>> { self.get_pairs(identifier("_modifiers", verb, None)) }
+
+    pub fn enabled_devices(&self) -> Result<Vec<String>> { self.get_list("_enadevs") }
+
+    pub fn enabled_modifiers(&self) -> Result<Vec<String>> { self.get_list("_enamods") }
+
+    /// Returns the string value of `identifier`.
+    pub fn get(&self, identifier: &str) -> Result<String> {
+        let id = CString::new(identifier).unwrap();
+        let mut value = ptr::null();
+        acheck!(ffi::snd_use_case_get(self.0, id.as_ptr(), &mut value))?;
+        let r = from_const("snd_use_case_get", value).map(|s| s.to_string());
+        unsafe { libc::free(value as *mut libc::c_void) };
+        r
+    }
+
+    /// Returns the integer value of `identifier`.
+    pub fn geti(&self, identifier: &str) -> Result<i64> {
+        let id = CString::new(identifier).unwrap();
+        let mut value: c_long = 0;
+        acheck!(ffi::snd_use_case_geti(self.0, id.as_ptr(), &mut value)).map(|_| value as i64)
+    }
+
+    /// Sets `identifier` to `value`, e g "_verb" to "HiFi".
+    pub fn set(&self, identifier: &str, value: &str) -> Result<()> {
+        let id = CString::new(identifier).unwrap();
+        let value = CString::new(value).unwrap();
+        acheck!(ffi::snd_use_case_set(self.0, id.as_ptr(), value.as_ptr())).map(|_| ())
+    }
+
+    /// Returns a value from the configuration, e g "PlaybackPCM" or "PlaybackMixerElem".
+    ///
+    /// The value is looked up for the device or modifier `dev`, if any, in `verb`, or in the current verb if None.
+    pub fn get_value(&self, name: &str, dev: Option<&str>, verb: Option<&str>) -> Result<String> {
+        self.get(identifier(name, dev, verb).to_str().unwrap())
+    }
+
+    /// Like `get_value`, but parses a mixer element name such as "PlaybackMixerElem".
+    pub fn get_selem_id(&self, name: &str, dev: Option<&str>, verb: Option<&str>) -> Result<SelemId> {
+        let v = CString::new(self.get_value(name, dev, verb)?).unwrap();
+        let id = CString::new(name).unwrap();
+        let r = SelemId::empty();
+        acheck!(ffi::snd_use_case_parse_selem_id(r.as_ptr(), id.as_ptr(), v.as_ptr())).map(|_| r)
+    }
+
+    pub fn get_verb(&self) -> Result<String> { self.get("_verb") }
+
+    pub fn set_verb(&self, verb: &str) -> Result<()> { self.set("_verb", verb) }
+
+    pub fn enable_device(&self, device: &str) -> Result<()> { self.set("_enadev", device) }
+
+    pub fn disable_device(&self, device: &str) -> Result<()> { self.set("_disdev", device) }
+
+    /// Disables `old` and enables `new`, without resetting the common settings of the two.
+    pub fn switch_device(&self, old: &str, new: &str) -> Result<()> { self.set(&format!("_swdev/{}", old), new) }
+
+    pub fn device_status(&self, device: &str) -> Result<bool> { self.geti(&format!("_devstatus/{}", device)).map(|s| s != 0) }
+
+    pub fn enable_modifier(&self, modifier: &str) -> Result<()> { self.set("_enamod", modifier) }
+
+    pub fn disable_modifier(&self, modifier: &str) -> Result<()> { self.set("_dismod", modifier) }
+
+    pub fn modifier_status(&self, modifier: &str) -> Result<bool> { self.geti(&format!("_modstatus/{}", modifier)).map(|s| s != 0) }
+}
+
+#[test]
+fn ucm_identifier() {
+    assert_eq!(identifier("_devices", None, None).to_str().unwrap(), "_devices");
+    assert_eq!(identifier("_devices", Some("HiFi"), None).to_str().unwrap(), "_devices/HiFi");
+    assert_eq!(identifier("PlaybackPCM", Some("Speaker"), Some("HiFi")).to_str().unwrap(), "PlaybackPCM/Speaker/HiFi");
+    assert_eq!(identifier("PlaybackPCM", None, Some("HiFi")).to_str().unwrap(), "PlaybackPCM//HiFi");
+    assert!(Manager::new("hw:99").is_err());
+}